@@ -21,8 +21,8 @@ pub use error::{Error, Result};
 pub use liquid_derive::{
     Display_filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
 };
+pub use model::{from_value, to_value, Value, ValueCow};
 pub use model::{to_object, Object};
-pub use model::{to_value, Value, ValueCow};
 pub use model::{ObjectView, ValueView};
 pub use parser::Language;
 pub use parser::TagTokenIter;
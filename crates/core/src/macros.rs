@@ -383,9 +383,18 @@ macro_rules! call_filter {
     ($filter:expr, $input:expr) => {{
         $crate::call_filter!($filter, $input, )
     }};
+    ($filter:expr, $input:expr; $($key:ident = $kwargs:expr),*) => {{
+        $crate::call_filter!($filter, $input, ; $($key = $kwargs),*)
+    }};
     ($filter:expr, $input:expr, $($args:expr),*) => {{
+        $crate::call_filter!($filter, $input, $($args),* ; )
+    }};
+    ($filter:expr, $input:expr, $($args:expr),* ; $($key:ident = $kwargs:expr),*) => {{
         let positional = Box::new(vec![$($crate::Expression::Literal($crate::value!($args))),*].into_iter());
-        let keyword = Box::new(Vec::new().into_iter());
+        let keyword = Box::new(
+            vec![$((stringify!($key), $crate::Expression::Literal($crate::value!($kwargs)))),*]
+                .into_iter(),
+        );
         let args = $crate::parser::FilterArguments { positional, keyword };
 
         let runtime = $crate::runtime::RuntimeBuilder::new().build();
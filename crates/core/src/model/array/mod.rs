@@ -9,6 +9,10 @@ use crate::model::State;
 use crate::model::Value;
 use crate::model::ValueView;
 
+mod range;
+
+pub use range::*;
+
 /// Accessor for arrays.
 pub trait ArrayView: ValueView {
     /// Cast to ValueView
@@ -0,0 +1,237 @@
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::model::KStringCow;
+
+use super::{convert_index, Array, ArrayView};
+use crate::model::value::DisplayCow;
+use crate::model::State;
+use crate::model::Value;
+use crate::model::ValueView;
+
+/// An inclusive range of integers, like `(1..5)`, payload of the `Value::Range` variant.
+///
+/// A range acts as an array of its integers, but renders like Ruby's, as `1..5`.  Indexing
+/// computes just the requested integer, while iterating [`ArrayView::values`] allocates all of
+/// them at once, so templates can't create ranges of more than [`Range::MAX_SIZE`] integers.
+pub struct Range {
+    start: i64,
+    end: i64,
+    elements: OnceLock<Array>,
+    indexed: OnceLock<Box<Indexed>>,
+}
+
+/// An integer computed by [`ArrayView::get`], kept alive as long as its range so it can be
+/// borrowed.  Each range only has the few integers a template indexed, so they're linked.
+struct Indexed {
+    integer: i64,
+    value: Value,
+    next: OnceLock<Box<Indexed>>,
+}
+
+impl Range {
+    /// The most integers a range written in a template, like `(1..n)`, may have.
+    pub const MAX_SIZE: i64 = 1_000_000;
+
+    /// Create the range of integers from `start` to `end`, inclusive.
+    pub fn new(start: i64, end: i64) -> Self {
+        Self {
+            start,
+            end,
+            elements: OnceLock::new(),
+            indexed: OnceLock::new(),
+        }
+    }
+
+    /// The first integer of the range.
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    /// The last integer of the range.
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+
+    /// Convert into an array of the range's integers.
+    pub fn into_array(self) -> Array {
+        match self.elements.into_inner() {
+            Some(elements) => elements,
+            None => (self.start..=self.end).map(Value::scalar).collect(),
+        }
+    }
+
+    fn integer(&self, integer: i64) -> &Value {
+        let mut indexed = &self.indexed;
+        loop {
+            let node = indexed.get_or_init(|| {
+                Box::new(Indexed {
+                    integer,
+                    value: Value::scalar(integer),
+                    next: OnceLock::new(),
+                })
+            });
+            if node.integer == integer {
+                return &node.value;
+            }
+            indexed = &node.next;
+        }
+    }
+
+    fn elements(&self) -> &Array {
+        self.elements
+            .get_or_init(|| (self.start..=self.end).map(Value::scalar).collect())
+    }
+}
+
+impl Clone for Range {
+    fn clone(&self) -> Self {
+        Self::new(self.start, self.end)
+    }
+}
+
+impl fmt::Debug for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Range({}..{})", self.start, self.end)
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+impl PartialEq for Range {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.end == other.end
+    }
+}
+
+impl serde::Serialize for Range {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.start..=self.end)
+    }
+}
+
+impl ValueView for Range {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(format!("({})", self)))
+    }
+    fn type_name(&self) -> &'static str {
+        "range"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue | State::Empty | State::Blank => self.size() == 0,
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        KStringCow::from_string(self.to_string())
+    }
+    fn to_value(&self) -> Value {
+        Value::Range(Range::new(self.start, self.end))
+    }
+
+    fn as_array(&self) -> Option<&dyn ArrayView> {
+        Some(self)
+    }
+}
+
+impl ArrayView for Range {
+    fn as_value(&self) -> &dyn ValueView {
+        self
+    }
+
+    fn size(&self) -> i64 {
+        let size = i128::from(self.end) - i128::from(self.start) + 1;
+        size.clamp(0, i128::from(i64::MAX)) as i64
+    }
+
+    fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+        Box::new(self.elements().iter().map(|v| v as &dyn ValueView))
+    }
+
+    fn contains_key(&self, index: i64) -> bool {
+        let index = convert_index(index, self.size());
+        0 <= index && index < self.size()
+    }
+
+    fn get(&self, index: i64) -> Option<&dyn ValueView> {
+        if !self.contains_key(index) {
+            return None;
+        }
+        // Negative indexes count back from the end, which the clamped size could miss.
+        let integer = if index < 0 {
+            i128::from(self.end) + 1 + i128::from(index)
+        } else {
+            i128::from(self.start) + i128::from(index)
+        };
+        Some(self.integer(integer as i64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        let range = Range::new(1, 5);
+        assert_eq!(range.render().to_string(), "1..5");
+        assert_eq!(range.source().to_string(), "(1..5)");
+        assert_eq!(range.to_kstr(), "1..5");
+        assert_eq!(range.size(), 5);
+        assert_eq!(range.first().unwrap().to_kstr(), "1");
+        assert_eq!(range.last().unwrap().to_kstr(), "5");
+        assert_eq!(range.get(-2).unwrap().to_kstr(), "4");
+        assert!(range.get(5).is_none());
+        assert!(range.get(-6).is_none());
+        assert_eq!(range.values().count(), 5);
+        assert_eq!(
+            range.into_array(),
+            crate::value!([1, 2, 3, 4, 5]).into_array().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_empty_range() {
+        let range = Range::new(5, 1);
+        assert_eq!(range.size(), 0);
+        assert!(range.query_state(State::Empty));
+        assert!(range.first().is_none());
+        assert_eq!(range.values().count(), 0);
+    }
+
+    #[test]
+    fn test_long_range_size() {
+        let range = Range::new(i64::MIN, i64::MAX);
+        assert_eq!(range.size(), i64::MAX);
+        assert_eq!(Range::new(1, 1_000_000_000_000).size(), 1_000_000_000_000);
+    }
+
+    #[test]
+    fn test_long_range_index() {
+        let range = Range::new(0, i64::MAX);
+        assert_eq!(range.first().unwrap().to_kstr(), "0");
+        assert_eq!(range.last().unwrap().to_kstr(), i64::MAX.to_string());
+        assert_eq!(range.get(-2).unwrap().to_kstr(), (i64::MAX - 1).to_string());
+        assert_eq!(range.first().unwrap().to_kstr(), "0");
+        assert!(range.elements.get().is_none());
+
+        let range = Range::new(i64::MIN, i64::MAX);
+        assert_eq!(range.last().unwrap().to_kstr(), i64::MAX.to_string());
+    }
+}
@@ -158,6 +158,14 @@ fn try_find_owned<'o, 'i>(
     }
 }
 
+/// An object's entry as a `[key, value]` pair, like `{% for pair in object %}` iterates them.
+fn pair<'o>((key, value): (KStringCow<'o>, &'o dyn ValueView)) -> ValueCow<'o> {
+    ValueCow::Owned(Value::Array(vec![
+        Value::scalar(key.into_owned()),
+        value.to_value(),
+    ]))
+}
+
 fn augmented_get<'o>(value: &'o dyn ValueView, index: &ScalarCow<'_>) -> Option<ValueCow<'o>> {
     if let Some(arr) = value.as_array() {
         if let Some(index) = index.to_integer() {
@@ -172,18 +180,31 @@ fn augmented_get<'o>(value: &'o dyn ValueView, index: &ScalarCow<'_>) -> Option<
         }
     } else if let Some(obj) = value.as_object() {
         let index = index.to_kstr();
-        obj.get(index.as_str())
-            .map(ValueCow::Borrowed)
+        obj.resolve(index.as_str())
             .or_else(|| match index.as_str() {
+                "first" => obj.iter().next().map(pair),
+                "last" => obj.iter().last().map(pair),
                 "size" => Some(ValueCow::Owned(Value::scalar(obj.size()))),
                 _ => None,
             })
     } else if let Some(scalar) = value.as_scalar() {
         let index = index.to_kstr();
         match index.as_str() {
-            "size" => Some(ValueCow::Owned(Value::scalar(
-                scalar.to_kstr().as_str().len() as i64,
-            ))),
+            "first" if scalar.as_bytes().is_none() => {
+                let first = scalar.to_kstr().chars().next()?;
+                Some(ValueCow::Owned(Value::scalar(first.to_string())))
+            }
+            "last" if scalar.as_bytes().is_none() => {
+                let last = scalar.to_kstr().chars().last()?;
+                Some(ValueCow::Owned(Value::scalar(last.to_string())))
+            }
+            "size" => {
+                let size = match scalar.as_bytes() {
+                    Some(bytes) => bytes.len(),
+                    None => scalar.to_kstr().as_str().len(),
+                };
+                Some(ValueCow::Owned(Value::scalar(size as i64)))
+            }
             _ => None,
         }
     } else {
@@ -196,7 +217,7 @@ pub fn find<'o>(value: &'o dyn ValueView, path: &[ScalarCow<'_>]) -> Result<Valu
     if let Some(res) = try_find(value, path) {
         Ok(res)
     } else {
-        for cur_idx in 1..path.len() {
+        for cur_idx in 1..=path.len() {
             let subpath_end = path.len() - cur_idx;
             let subpath = &path[0..subpath_end];
             if let Some(parent) = try_find(value, subpath) {
@@ -234,3 +255,159 @@ pub fn find<'o>(value: &'o dyn ValueView, path: &[ScalarCow<'_>]) -> Result<Valu
         );
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{DisplayCow, KStringCow, ObjectRender, ObjectSource, ObjectView, State};
+
+    /// Computes `price` from `cents` only when it's asked for.
+    #[derive(Debug)]
+    struct Product {
+        cents: i64,
+    }
+
+    impl ValueView for Product {
+        fn as_debug(&self) -> &dyn fmt::Debug {
+            self
+        }
+        fn render(&self) -> DisplayCow<'_> {
+            DisplayCow::Owned(Box::new(ObjectRender::new(self)))
+        }
+        fn source(&self) -> DisplayCow<'_> {
+            DisplayCow::Owned(Box::new(ObjectSource::new(self)))
+        }
+        fn type_name(&self) -> &'static str {
+            "object"
+        }
+        fn query_state(&self, state: State) -> bool {
+            state == State::Truthy
+        }
+        fn to_kstr(&self) -> KStringCow<'_> {
+            ObjectRender::new(self).to_string().into()
+        }
+        fn to_value(&self) -> Value {
+            let mut object = crate::model::Object::new();
+            object.insert("cents".into(), Value::scalar(self.cents));
+            Value::Object(object)
+        }
+        fn as_object(&self) -> Option<&dyn ObjectView> {
+            Some(self)
+        }
+    }
+
+    impl ObjectView for Product {
+        fn as_value(&self) -> &dyn ValueView {
+            self
+        }
+        fn size(&self) -> i64 {
+            1
+        }
+        fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
+            Box::new(std::iter::once("cents".into()))
+        }
+        fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+            Box::new(std::iter::once(&self.cents as &dyn ValueView))
+        }
+        fn iter<'k>(
+            &'k self,
+        ) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
+            Box::new(self.keys().zip(self.values()))
+        }
+        fn contains_key(&self, index: &str) -> bool {
+            matches!(index, "cents" | "price")
+        }
+        fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
+            match index {
+                "cents" => Some(&self.cents),
+                _ => None,
+            }
+        }
+        fn resolve<'s>(&'s self, index: &str) -> Option<ValueCow<'s>> {
+            match index {
+                "price" => Some(ValueCow::Owned(Value::scalar(format!(
+                    "{}.{:02}",
+                    self.cents / 100,
+                    self.cents % 100
+                )))),
+                _ => self.get(index).map(ValueCow::Borrowed),
+            }
+        }
+    }
+
+    #[test]
+    fn find_resolves_computed_values() {
+        let mut globals = std::collections::HashMap::new();
+        globals.insert("product".to_owned(), Product { cents: 1250 });
+        let path = [ScalarCow::new("product"), ScalarCow::new("price")];
+        assert_eq!(
+            find(&globals, &path).unwrap().to_kstr(),
+            KStringCow::from_static("12.50")
+        );
+        let runtime = crate::runtime::RuntimeBuilder::new()
+            .set_globals(&globals)
+            .build();
+        assert_eq!(
+            crate::runtime::Runtime::get(&runtime, &path)
+                .unwrap()
+                .to_kstr(),
+            KStringCow::from_static("12.50")
+        );
+
+        let product = Product { cents: 1250 };
+        assert_eq!(
+            find(&product, &[ScalarCow::new("cents")])
+                .unwrap()
+                .to_kstr(),
+            KStringCow::from_static("1250")
+        );
+        assert!(try_find(&product, &[ScalarCow::new("weight")]).is_none());
+    }
+
+    #[test]
+    fn find_bytes_size() {
+        let mut globals = std::collections::HashMap::new();
+        globals.insert(
+            "data".to_owned(),
+            Value::scalar(crate::model::Bytes::from(vec![0xff, 0x00])),
+        );
+        let path = [ScalarCow::new("data"), ScalarCow::new("size")];
+        assert_eq!(
+            find(&globals, &path).unwrap().to_kstr(),
+            KStringCow::from_static("2")
+        );
+    }
+
+    #[test]
+    fn find_pseudo_properties() {
+        let globals = crate::object!({
+            "name": "héllo",
+            "empty": "",
+            "site": {"title": "Blog", "url": "example.com"},
+        });
+        let get = |path: &[&str]| {
+            let path: Vec<_> = path.iter().map(|i| ScalarCow::new(*i)).collect();
+            try_find(&globals, &path).map(|v| v.into_owned())
+        };
+        assert_eq!(get(&["name", "first"]), Some(Value::scalar("h")));
+        assert_eq!(get(&["name", "last"]), Some(Value::scalar("o")));
+        assert_eq!(get(&["empty", "first"]), None);
+        assert_eq!(
+            get(&["site", "first"]),
+            Some(crate::value!(["title", "Blog"]))
+        );
+        assert_eq!(
+            get(&["site", "last"]),
+            Some(crate::value!(["url", "example.com"]))
+        );
+        assert_eq!(get(&["site", "size"]), Some(Value::scalar(2)));
+    }
+
+    #[test]
+    fn find_unknown_root_index() {
+        let value = Value::scalar("abc");
+        let err = find(&value, &[ScalarCow::new("missing")]).unwrap_err();
+        assert!(err.to_string().contains("Unknown index"), "{}", err);
+    }
+}
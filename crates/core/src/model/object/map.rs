@@ -1,17 +1,20 @@
 //! Type representing a Liquid object, payload of the `Value::Object` variant
 
 use std::borrow::Borrow;
-use std::collections::hash_map;
 use std::fmt::{self, Debug};
 use std::hash::Hash;
 use std::iter::FromIterator;
 use std::ops;
 
+use indexmap::map as index_map;
 use serde::{de, ser};
 
 use super::Value;
 
 /// Type representing a Liquid object, payload of the `Value::Object` variant
+///
+/// Entries are kept in the order they were inserted, so iterating an object, like with
+/// `{% for pair in object %}`, is deterministic.  Replacing a value keeps the key in place.
 #[derive(Default, Clone, PartialEq, Eq)]
 pub struct Object {
     map: MapImpl<Key, Value>,
@@ -19,15 +22,15 @@ pub struct Object {
 
 type Key = crate::model::KString;
 
-type MapImpl<K, V> = hash_map::HashMap<K, V>;
-type VacantEntryImpl<'a> = hash_map::VacantEntry<'a, Key, Value>;
-type OccupiedEntryImpl<'a> = hash_map::OccupiedEntry<'a, Key, Value>;
-type IterImpl<'a> = hash_map::Iter<'a, Key, Value>;
-type IterMutImpl<'a> = hash_map::IterMut<'a, Key, Value>;
-type IntoIterImpl = hash_map::IntoIter<Key, Value>;
-type KeysImpl<'a> = hash_map::Keys<'a, Key, Value>;
-type ValuesImpl<'a> = hash_map::Values<'a, Key, Value>;
-type ValuesMutImpl<'a> = hash_map::ValuesMut<'a, Key, Value>;
+type MapImpl<K, V> = index_map::IndexMap<K, V>;
+type VacantEntryImpl<'a> = index_map::VacantEntry<'a, Key, Value>;
+type OccupiedEntryImpl<'a> = index_map::OccupiedEntry<'a, Key, Value>;
+type IterImpl<'a> = index_map::Iter<'a, Key, Value>;
+type IterMutImpl<'a> = index_map::IterMut<'a, Key, Value>;
+type IntoIterImpl = index_map::IntoIter<Key, Value>;
+type KeysImpl<'a> = index_map::Keys<'a, Key, Value>;
+type ValuesImpl<'a> = index_map::Values<'a, Key, Value>;
+type ValuesMutImpl<'a> = index_map::ValuesMut<'a, Key, Value>;
 
 impl Object {
     /// Makes a new empty Object.
@@ -97,6 +100,8 @@ impl Object {
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
+    /// The remaining entries keep their order.
+    ///
     /// The key may be any borrowed form of the map's key type, but the ordering
     /// on the borrowed form *must* match the ordering on the key type.
     #[inline]
@@ -105,7 +110,7 @@ impl Object {
         Key: Borrow<Q>,
         Q: Ord + Eq + Hash,
     {
-        self.map.remove(key)
+        self.map.shift_remove(key)
     }
 
     /// Gets the given key's corresponding entry in the map for in-place
@@ -114,7 +119,7 @@ impl Object {
     where
         S: Into<Key>,
     {
-        use std::collections::hash_map::Entry as EntryImpl;
+        use indexmap::map::Entry as EntryImpl;
         match self.map.entry(key.into()) {
             EntryImpl::Vacant(vacant) => Entry::Vacant(VacantEntry { vacant }),
             EntryImpl::Occupied(occupied) => Entry::Occupied(OccupiedEntry { occupied }),
@@ -597,7 +602,7 @@ impl<'a> OccupiedEntry<'a> {
     /// ```
     #[inline]
     pub fn remove(self) -> Value {
-        self.occupied.remove()
+        self.occupied.shift_remove()
     }
 }
 
@@ -11,7 +11,7 @@ use crate::model::KStringCow;
 
 use crate::model::value::DisplayCow;
 use crate::model::State;
-use crate::model::{Value, ValueView};
+use crate::model::{Value, ValueCow, ValueView};
 
 pub use map::Object;
 pub use ser::to_object;
@@ -35,6 +35,15 @@ pub trait ObjectView: ValueView {
     fn contains_key(&self, index: &str) -> bool;
     /// Access a contained `Value`.
     fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView>;
+
+    /// Look up `index` while resolving a variable, like `{{ product.price }}`.
+    ///
+    /// Defaults to [`ObjectView::get`]. Override it to compute values only when a template
+    /// asks for them, like a Ruby drop's methods, rather than storing them; `contains_key` must
+    /// still report those indexes.
+    fn resolve<'s>(&'s self, index: &str) -> Option<ValueCow<'s>> {
+        self.get(index).map(ValueCow::Borrowed)
+    }
 }
 
 impl ValueView for Object {
@@ -132,6 +141,44 @@ impl<'o, O: ObjectView + ?Sized> ObjectView for &'o O {
     fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
         <O as ObjectView>::get(self, index)
     }
+
+    fn resolve<'s>(&'s self, index: &str) -> Option<ValueCow<'s>> {
+        <O as ObjectView>::resolve(self, index)
+    }
+}
+
+impl<O: ObjectView + ?Sized> ObjectView for std::sync::Arc<O> {
+    fn as_value(&self) -> &dyn ValueView {
+        <O as ObjectView>::as_value(self)
+    }
+
+    fn size(&self) -> i64 {
+        <O as ObjectView>::size(self)
+    }
+
+    fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
+        <O as ObjectView>::keys(self)
+    }
+
+    fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+        <O as ObjectView>::values(self)
+    }
+
+    fn iter<'k>(&'k self) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
+        <O as ObjectView>::iter(self)
+    }
+
+    fn contains_key(&self, index: &str) -> bool {
+        <O as ObjectView>::contains_key(self, index)
+    }
+
+    fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
+        <O as ObjectView>::get(self, index)
+    }
+
+    fn resolve<'s>(&'s self, index: &str) -> Option<ValueCow<'s>> {
+        <O as ObjectView>::resolve(self, index)
+    }
 }
 
 /// Owned object index
@@ -371,4 +418,21 @@ mod test {
         let view: &dyn ValueView = object.as_value();
         println!("{}", view.source());
     }
+
+    #[test]
+    fn test_insertion_order() {
+        let mut obj = Object::new();
+        obj.insert("c".into(), Value::scalar(1));
+        obj.insert("a".into(), Value::scalar(2));
+        obj.insert("b".into(), Value::scalar(3));
+        obj.insert("a".into(), Value::scalar(4));
+        let keys: Vec<_> = obj.keys().map(|k| k.as_str()).collect();
+        assert_eq!(keys, ["c", "a", "b"]);
+
+        obj.remove("c");
+        obj.entry("d").or_insert(Value::scalar(5));
+        let keys: Vec<_> = ObjectView::keys(&obj).map(|k| k.into_owned()).collect();
+        assert_eq!(keys, ["a", "b", "d"]);
+        assert_eq!(obj.source().to_string(), r#"{"a": 4, "b": 3, "d": 5, }"#);
+    }
 }
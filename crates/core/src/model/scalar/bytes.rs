@@ -0,0 +1,157 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::model::KStringCow;
+
+use crate::model::value::{DisplayCow, State};
+use crate::model::{Value, ValueView};
+
+use super::ScalarCow;
+
+/// Binary data, like file contents, kept apart from strings so it isn't mangled into UTF-8.
+///
+/// Bytes always render as lowercase hex, even when they happen to be valid UTF-8, so output
+/// doesn't depend on the data.  Clones share the data.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(Arc<[u8]>);
+
+impl Bytes {
+    /// Create from binary data.
+    pub fn new(bytes: impl Into<Arc<[u8]>>) -> Self {
+        Self(bytes.into())
+    }
+
+    /// The binary data.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// The number of bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl fmt::Debug for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bytes({})", HexDisplay(self.as_slice()))
+    }
+}
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        HexDisplay(self.as_slice()).fmt(f)
+    }
+}
+
+struct HexDisplay<'b>(&'b [u8]);
+
+impl fmt::Display for HexDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+struct BytesSource<'b>(&'b [u8]);
+
+impl fmt::Display for BytesSource<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", HexDisplay(self.0))
+    }
+}
+
+impl serde::Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.as_slice())
+    }
+}
+
+impl ValueView for Bytes {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(BytesSource(self.as_slice())))
+    }
+    fn type_name(&self) -> &'static str {
+        "bytes"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue | State::Empty | State::Blank => self.is_empty(),
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        KStringCow::from_string(self.to_string())
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(self.clone())
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let text = Bytes::from(b"hello".to_vec());
+        assert_eq!(text.render().to_string(), "68656c6c6f");
+        assert_eq!(text.to_kstr(), "68656c6c6f");
+        assert_eq!(text.source().to_string(), "0x68656c6c6f");
+
+        let binary = Bytes::from(&[0xff, 0x00, 0x10][..]);
+        assert_eq!(binary.render().to_string(), "ff0010");
+        assert_eq!(binary.to_kstr(), "ff0010");
+        assert_eq!(binary.len(), 3);
+    }
+
+    #[test]
+    fn test_scalar() {
+        let value = Value::scalar(Bytes::from(&[0xff, 0x00][..]));
+        assert_eq!(value.type_name(), "bytes");
+        assert_eq!(value, Value::scalar(Bytes::from(vec![0xff, 0x00])));
+        assert_ne!(value, Value::scalar("ff00"));
+        assert!(Value::scalar(Bytes::from(Vec::new())).query_state(State::Empty));
+
+        let scalar = value.as_scalar().unwrap();
+        assert_eq!(scalar.as_bytes(), Some(&[0xff, 0x00][..]));
+        assert_eq!(Value::scalar("ff00").as_scalar().unwrap().as_bytes(), None);
+
+        let value = crate::model::to_value(&Bytes::from(vec![1, 2])).unwrap();
+        assert_eq!(value.type_name(), "bytes");
+    }
+}
@@ -0,0 +1,249 @@
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::model::KStringCow;
+
+use crate::model::value::{DisplayCow, State};
+use crate::model::{Value, ValueView};
+
+use super::ScalarCow;
+
+/// An application-defined scalar, like money or an identifier.
+///
+/// Wrapping a type in a scalar with [`ScalarCow::custom`] lets filters hand it to each other
+/// without turning it into a string; the next filter gets it back with
+/// [`ScalarCow::as_custom`] and [`downcast_ref`](trait.CustomScalar.html#method.downcast_ref).
+/// How it renders, its type name and its truthiness come from its `ValueView`, and how it
+/// compares from the methods here.  Serializing it, like with `to_value`, writes its rendered
+/// text.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::any::Any;
+/// use std::cmp::Ordering;
+///
+/// use liquid_core::model::{CustomScalar, DisplayCow, KStringCow, ScalarCow, State};
+/// use liquid_core::{Value, ValueView};
+///
+/// #[derive(Clone, Debug, PartialEq, PartialOrd)]
+/// struct Money {
+///     cents: i64,
+/// }
+///
+/// impl ValueView for Money {
+///     fn as_debug(&self) -> &dyn std::fmt::Debug {
+///         self
+///     }
+///     fn render(&self) -> DisplayCow<'_> {
+///         DisplayCow::Owned(Box::new(format!("${}.{:02}", self.cents / 100, self.cents % 100)))
+///     }
+///     fn source(&self) -> DisplayCow<'_> {
+///         self.render()
+///     }
+///     fn type_name(&self) -> &'static str {
+///         "money"
+///     }
+///     fn query_state(&self, state: State) -> bool {
+///         match state {
+///             State::Truthy => true,
+///             State::DefaultValue | State::Empty | State::Blank => false,
+///         }
+///     }
+///     fn to_kstr(&self) -> KStringCow<'_> {
+///         KStringCow::from_string(self.render().to_string())
+///     }
+///     fn to_value(&self) -> Value {
+///         Value::scalar(ScalarCow::custom(self.clone()))
+///     }
+///     fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+///         Some(ScalarCow::custom(self.clone()))
+///     }
+/// }
+///
+/// impl CustomScalar for Money {
+///     fn as_any(&self) -> &dyn Any {
+///         self
+///     }
+///     fn custom_eq(&self, other: &dyn CustomScalar) -> bool {
+///         other.downcast_ref::<Money>() == Some(self)
+///     }
+///     fn custom_cmp(&self, other: &dyn CustomScalar) -> Option<Ordering> {
+///         self.partial_cmp(other.downcast_ref::<Money>()?)
+///     }
+/// }
+///
+/// let price = Value::scalar(ScalarCow::custom(Money { cents: 1250 }));
+/// assert_eq!(price.render().to_string(), "$12.50");
+/// let scalar = price.as_scalar().unwrap();
+/// let money = scalar.as_custom().unwrap().downcast_ref::<Money>().unwrap();
+/// assert_eq!(money.cents, 1250);
+/// assert!(price < Value::scalar(ScalarCow::custom(Money { cents: 2000 })));
+/// ```
+pub trait CustomScalar: ValueView + Send + Sync + 'static {
+    /// Get as `Any`, to downcast to the concrete type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Whether this equals another custom scalar.
+    ///
+    /// Defaults to both having the same type name and rendering the same.
+    fn custom_eq(&self, other: &dyn CustomScalar) -> bool {
+        self.type_name() == other.type_name() && self.to_kstr() == other.to_kstr()
+    }
+
+    /// How this orders against another custom scalar.
+    ///
+    /// Defaults to not being ordered.
+    fn custom_cmp(&self, _other: &dyn CustomScalar) -> Option<Ordering> {
+        None
+    }
+}
+
+impl dyn CustomScalar {
+    /// Downcast to the concrete type, if it is `T`.
+    pub fn downcast_ref<T: CustomScalar>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
+
+/// A shared custom scalar, payload of the custom `ScalarCow` variant.
+#[derive(Clone)]
+pub(crate) struct Custom(pub(crate) Arc<dyn CustomScalar>);
+
+impl Custom {
+    pub(crate) fn as_dyn(&self) -> &dyn CustomScalar {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for Custom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.as_debug().fmt(f)
+    }
+}
+
+impl serde::Serialize for Custom {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.0.to_kstr().as_str())
+    }
+}
+
+impl ValueView for Custom {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        self.0.render()
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        self.0.source()
+    }
+    fn type_name(&self) -> &'static str {
+        self.0.type_name()
+    }
+    fn query_state(&self, state: State) -> bool {
+        self.0.query_state(state)
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.0.to_kstr()
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(ScalarCow::from(self.clone()))
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::from(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, PartialOrd)]
+    struct Version(u32, u32);
+
+    impl ValueView for Version {
+        fn as_debug(&self) -> &dyn fmt::Debug {
+            self
+        }
+        fn render(&self) -> DisplayCow<'_> {
+            DisplayCow::Owned(Box::new(format!("v{}.{}", self.0, self.1)))
+        }
+        fn source(&self) -> DisplayCow<'_> {
+            self.render()
+        }
+        fn type_name(&self) -> &'static str {
+            "version"
+        }
+        fn query_state(&self, state: State) -> bool {
+            match state {
+                State::Truthy => *self != Version(0, 0),
+                State::DefaultValue | State::Empty | State::Blank => *self == Version(0, 0),
+            }
+        }
+        fn to_kstr(&self) -> KStringCow<'_> {
+            KStringCow::from_string(self.render().to_string())
+        }
+        fn to_value(&self) -> Value {
+            Value::scalar(ScalarCow::custom(self.clone()))
+        }
+        fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+            Some(ScalarCow::custom(self.clone()))
+        }
+    }
+
+    impl CustomScalar for Version {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn custom_cmp(&self, other: &dyn CustomScalar) -> Option<Ordering> {
+            self.partial_cmp(other.downcast_ref::<Version>()?)
+        }
+    }
+
+    fn version(major: u32, minor: u32) -> Value {
+        Value::scalar(ScalarCow::custom(Version(major, minor)))
+    }
+
+    #[test]
+    fn test_view() {
+        let value = version(1, 2);
+        assert_eq!(value.render().to_string(), "v1.2");
+        assert_eq!(value.to_kstr(), "v1.2");
+        assert_eq!(value.type_name(), "version");
+        assert!(value.query_state(State::Truthy));
+        assert!(!version(0, 0).query_state(State::Truthy));
+        assert!(version(0, 0).query_state(State::Blank));
+        assert_eq!(
+            crate::model::to_value(&value).unwrap(),
+            Value::scalar("v1.2")
+        );
+    }
+
+    #[test]
+    fn test_downcast() {
+        let value = version(1, 2);
+        let scalar = value.as_scalar().unwrap();
+        let custom = scalar.as_custom().unwrap();
+        assert_eq!(custom.downcast_ref::<Version>(), Some(&Version(1, 2)));
+        assert!(Value::scalar(1).as_scalar().unwrap().as_custom().is_none());
+        assert_eq!(value.clone().into_scalar().unwrap().into_string(), "v1.2");
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(version(1, 2), version(1, 2));
+        assert_ne!(version(1, 2), version(1, 3));
+        assert_ne!(version(1, 2), Value::scalar("v1.2"));
+        assert!(version(1, 2) < version(1, 10));
+        assert_eq!(version(1, 2).partial_cmp(&Value::scalar(1)), None);
+    }
+}
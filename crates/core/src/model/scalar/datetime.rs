@@ -3,6 +3,9 @@ use std::fmt;
 use std::ops;
 
 mod strftime;
+mod timezone;
+
+pub use timezone::TimeZone;
 
 use super::Date;
 
@@ -44,6 +47,15 @@ impl DateTime {
         }
     }
 
+    /// Create a `DateTime`, in UTC, from the number of seconds since the Unix epoch.
+    ///
+    /// Returns `None` when out of range.
+    pub fn from_unix_timestamp(timestamp: i64) -> Option<Self> {
+        DateTimeImpl::from_unix_timestamp(timestamp)
+            .ok()
+            .map(|inner| Self { inner })
+    }
+
     /// Convert a `str` to `Self`
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(other: &str) -> Option<Self> {
@@ -64,6 +76,11 @@ impl DateTime {
         }
     }
 
+    /// The same moment, as seen in `zone`.
+    pub fn in_time_zone(self, zone: &TimeZone) -> Self {
+        self.with_offset(zone.offset_at(self))
+    }
+
     /// Retrieves a date component.
     pub fn date(self) -> Date {
         Date {
@@ -210,6 +227,11 @@ mod friendly_date_time {
 /// * `day_mon` - `DD Mon YYYY HH:MM:SS`
 /// * `mdy` -  `MM/DD/YYYY HH:MM:SS`
 /// * `dow_mon` - `Dow Mon DD HH:MM:SS YYYY`
+/// * `iso8601` - `YYYY-MM-DDTHH:MM:SS`
+/// * `date` - `YYYY-MM-DD`, `DD Month YYYY` or `DD Mon YYYY`, at midnight
+///
+/// As well as RFC 3339 (`2016-02-16T10:00:00Z`) and RFC 2822
+/// (`Tue, 16 Feb 2016 10:00:00 +0100`), with their own offsets.
 ///
 /// Offsets in one of the following forms, and are catenated with any of
 /// the above formats.
@@ -222,6 +244,7 @@ mod friendly_date_time {
 /// * `dow_mon` format with an offset: "Tue Feb 16 10:00:00 2016 +0100"
 fn parse_date_time(s: &str) -> Option<DateTimeImpl> {
     use regex::Regex;
+    use time::format_description::well_known;
     use time::macros::format_description;
 
     const USER_FORMATS: &[&[time::format_description::FormatItem<'_>]] = &[
@@ -231,12 +254,19 @@ fn parse_date_time(s: &str) -> Option<DateTimeImpl> {
         format_description!("[day] [month repr:short] [year] [hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"),
         format_description!("[month]/[day]/[year] [hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"),
         format_description!("[weekday repr:short] [month repr:short] [day padding:none] [hour]:[minute]:[second] [year] [offset_hour sign:mandatory][offset_minute]"),
+        format_description!("[year]-[month]-[day]T[hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"),
     ];
 
     if s.is_empty() {
         None
     } else if let "now" | "today" = s.to_lowercase().trim() {
         Some(DateTimeImpl::now_utc())
+    } else if let Ok(date) = DateTimeImpl::parse(s, &well_known::Rfc3339) {
+        Some(date)
+    } else if let Ok(date) = DateTimeImpl::parse(s, &well_known::Rfc2822) {
+        Some(date)
+    } else if let Some(date) = Date::from_str(s) {
+        Some(date.inner.midnight().assume_utc())
     } else {
         let offset_re = Regex::new(r"[+-][01][0-9]{3}$").unwrap();
 
@@ -403,4 +433,47 @@ mod test {
         let ser = serde_yaml::to_string(&data).expect("could serialize date");
         assert_eq!(yml, ser);
     }
+
+    #[test]
+    fn parse_date_time_iso8601_format() {
+        let input = "2016-02-16T10:00:00+01:00";
+        let actual = parse_date_time(input);
+        assert_eq!(actual.unwrap().unix_timestamp(), 1455613200);
+
+        let input = "2016-02-16T10:00:00Z";
+        let actual = parse_date_time(input);
+        assert_eq!(actual.unwrap().unix_timestamp(), 1455616800);
+
+        let input = "2016-02-16T10:00:00";
+        let actual = parse_date_time(input);
+        assert_eq!(actual.unwrap().unix_timestamp(), 1455616800);
+    }
+
+    #[test]
+    fn parse_date_time_rfc2822_format() {
+        let input = "Tue, 16 Feb 2016 10:00:00 +0100";
+        let actual = parse_date_time(input);
+        assert_eq!(actual.unwrap().unix_timestamp(), 1455613200);
+
+        let date = DateTime::from_str(input).unwrap();
+        assert_eq!(DateTime::from_str(&date.to_rfc2822()), Some(date));
+    }
+
+    #[test]
+    fn parse_date_time_date_only() {
+        let input = "2016-02-16";
+        let actual = parse_date_time(input);
+        assert_eq!(actual.unwrap().unix_timestamp(), 1455580800);
+
+        let input = "16 February 2016";
+        let actual = parse_date_time(input);
+        assert_eq!(actual.unwrap().unix_timestamp(), 1455580800);
+    }
+
+    #[test]
+    fn from_unix_timestamp() {
+        let actual = DateTime::from_unix_timestamp(1455616800).unwrap();
+        assert_eq!(actual, DateTime::from_str("2016-02-16 10:00:00").unwrap());
+        assert!(DateTime::from_unix_timestamp(i64::MAX).is_none());
+    }
 }
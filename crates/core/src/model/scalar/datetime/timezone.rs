@@ -0,0 +1,199 @@
+use std::fmt;
+
+use super::DateTime;
+
+/// A time zone to present a [`DateTime`] in.
+///
+/// Either a fixed offset, like `+05:30`, or, with the `tz` feature, a zone from the IANA time
+/// zone database, like `Europe/Paris`, which follows its daylight saving time.
+#[derive(Clone, PartialEq, Eq)]
+pub struct TimeZone {
+    name: String,
+    zone: Zone,
+}
+
+#[derive(Clone)]
+enum Zone {
+    Fixed(time::UtcOffset),
+    #[cfg(feature = "tz")]
+    Named(&'static time_tz::Tz),
+}
+
+impl PartialEq for Zone {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Fixed(a), Self::Fixed(b)) => a == b,
+            #[cfg(feature = "tz")]
+            (Self::Named(a), Self::Named(b)) => std::ptr::eq(*a, *b),
+            #[cfg(feature = "tz")]
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Zone {}
+
+impl TimeZone {
+    /// Coordinated Universal Time.
+    pub fn utc() -> Self {
+        Self::fixed(time::UtcOffset::UTC)
+    }
+
+    /// A zone always `offset` from UTC.
+    pub fn fixed(offset: time::UtcOffset) -> Self {
+        let name = if offset.is_utc() {
+            "UTC".to_owned()
+        } else {
+            let (h, m, _) = offset.as_hms();
+            let sign = if offset.is_negative() { '-' } else { '+' };
+            format!("{}{:02}:{:02}", sign, h.abs(), m.abs())
+        };
+        Self {
+            name,
+            zone: Zone::Fixed(offset),
+        }
+    }
+
+    /// Look up a time zone by name.
+    ///
+    /// Accepts `UTC`, offsets like `+05:30`, `-0800` or `+01`, and, with the `tz` feature, the
+    /// names of the IANA time zone database, like `America/New_York`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if let Some(offset) = parse_fixed_offset(name) {
+            return Some(Self {
+                name: name.to_owned(),
+                zone: Zone::Fixed(offset),
+            });
+        }
+
+        #[cfg(feature = "tz")]
+        {
+            let zone = time_tz::timezones::get_by_name(name)?;
+            Some(Self {
+                name: name.to_owned(),
+                zone: Zone::Named(zone),
+            })
+        }
+        #[cfg(not(feature = "tz"))]
+        {
+            None
+        }
+    }
+
+    /// The name the zone was looked up by.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The zone's offset from UTC at the moment `date`.
+    pub fn offset_at(&self, date: DateTime) -> time::UtcOffset {
+        #[cfg(not(feature = "tz"))]
+        let _ = date;
+        match &self.zone {
+            Zone::Fixed(offset) => *offset,
+            #[cfg(feature = "tz")]
+            Zone::Named(zone) => {
+                use time_tz::{Offset, TimeZone};
+                zone.get_offset_utc(&date).to_utc()
+            }
+        }
+    }
+}
+
+impl fmt::Debug for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TimeZone").field(&self.name).finish()
+    }
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+fn parse_fixed_offset(name: &str) -> Option<time::UtcOffset> {
+    if let "UTC" | "utc" | "Z" | "GMT" = name {
+        return Some(time::UtcOffset::UTC);
+    }
+
+    let (sign, rest) = match name.as_bytes().first()? {
+        b'+' => (1, &name[1..]),
+        b'-' => (-1, &name[1..]),
+        _ => return None,
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (&digits[..2], "0"),
+        4 => (&digits[..2], &digits[2..]),
+        _ => return None,
+    };
+    let hours: i8 = hours.parse().ok()?;
+    let minutes: i8 = minutes.parse().ok()?;
+    if 59 < minutes {
+        return None;
+    }
+    time::UtcOffset::from_hms(sign * hours, sign * minutes, 0).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fixed_offsets() {
+        let utc = time::UtcOffset::UTC;
+        assert_eq!(parse_fixed_offset("UTC"), Some(utc));
+        assert_eq!(
+            parse_fixed_offset("+05:30"),
+            time::UtcOffset::from_hms(5, 30, 0).ok()
+        );
+        assert_eq!(
+            parse_fixed_offset("-0800"),
+            time::UtcOffset::from_hms(-8, 0, 0).ok()
+        );
+        assert_eq!(
+            parse_fixed_offset("+01"),
+            time::UtcOffset::from_hms(1, 0, 0).ok()
+        );
+        assert_eq!(parse_fixed_offset("+1"), None);
+        assert_eq!(parse_fixed_offset("+05:75"), None);
+        assert_eq!(parse_fixed_offset("Europe/Paris"), None);
+
+        let tz = TimeZone::from_name("-03:00").unwrap();
+        let date = DateTime::from_str("2020-01-01 00:00:00 +0000").unwrap();
+        assert_eq!(tz.offset_at(date).whole_hours(), -3);
+        assert_eq!(TimeZone::utc().to_string(), "UTC");
+    }
+
+    #[test]
+    fn invalid_names() {
+        assert!(TimeZone::from_name("").is_none());
+        assert!(TimeZone::from_name("../../etc/passwd").is_none());
+        assert!(TimeZone::from_name("/etc/localtime").is_none());
+        assert!(TimeZone::from_name("Not/A_Zone").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "tz")]
+    fn named_zone() {
+        let tz = TimeZone::from_name("America/New_York").unwrap();
+        assert_eq!(tz.name(), "America/New_York");
+        assert_eq!(tz, TimeZone::from_name("America/New_York").unwrap());
+        assert_ne!(tz, TimeZone::from_name("-05:00").unwrap());
+        let winter = DateTime::from_str("2006-01-15 12:00:00 +0000").unwrap();
+        assert_eq!(tz.offset_at(winter).whole_hours(), -5);
+        let summer = DateTime::from_str("2006-07-15 12:00:00 +0000").unwrap();
+        assert_eq!(tz.offset_at(summer).whole_hours(), -4);
+    }
+
+    #[test]
+    #[cfg(not(feature = "tz"))]
+    fn named_zone_needs_feature() {
+        assert!(TimeZone::from_name("America/New_York").is_none());
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+use std::str::FromStr;
+
+use num_traits::ToPrimitive;
+use rust_decimal::RoundingStrategy;
+
+use crate::model::KStringCow;
+
+use crate::model::value::{DisplayCow, State};
+use crate::model::{Value, ValueView};
+
+use super::ScalarCow;
+
+/// The most digits a `Decimal` keeps after the decimal point.
+pub const MAX_SCALE: u32 = 28;
+
+/// The newtype name marking a serialized `Decimal`.
+pub(crate) const SERDE_NAME: &str = "$liquid_core::model::Decimal";
+
+/// An exact decimal number, like `10.25`, for amounts that must not pick up the rounding errors
+/// of floats, like money.
+///
+/// Backed by [`rust_decimal::Decimal`], so it keeps up to 28 significant digits.  Arithmetic is
+/// exact within that, except that division stops at the last digit kept.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal(rust_decimal::Decimal);
+
+impl Decimal {
+    /// Create the number `mantissa / 10^scale`, if it fits.
+    pub fn new(mantissa: i128, scale: u32) -> Option<Self> {
+        rust_decimal::Decimal::try_from_i128_with_scale(mantissa, scale)
+            .ok()
+            .map(Self)
+    }
+
+    /// The digits of the number, without the decimal point.
+    pub fn mantissa(self) -> i128 {
+        self.0.mantissa()
+    }
+
+    /// The number of digits after the decimal point.
+    pub fn scale(self) -> u32 {
+        self.0.scale()
+    }
+
+    /// Convert the shortest representation of a float, so `0.1` is exactly `0.1`.
+    pub fn from_f64(f: f64) -> Option<Self> {
+        if !f.is_finite() {
+            return None;
+        }
+        format!("{}", f).parse().ok().map(Self::normalize)
+    }
+
+    /// Convert to the nearest float.
+    pub fn to_f64(self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// Convert to an integer, if the number is whole.
+    pub fn to_i64(self) -> Option<i64> {
+        if !self.0.fract().is_zero() {
+            return None;
+        }
+        self.0.to_i64()
+    }
+
+    /// Remove trailing zeros after the decimal point.
+    pub fn normalize(self) -> Self {
+        Self(self.0.normalize())
+    }
+
+    /// The absolute value.
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// `self + other`, or `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// `self - other`, or `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// `self * other`, or `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        self.0.checked_mul(other.0).map(Self)
+    }
+
+    /// `self / other`, or `None` when dividing by zero or on overflow.
+    ///
+    /// The quotient keeps at least the decimal places of either number.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        let keep = self.scale().max(other.scale());
+        let mut quotient = self.0.checked_div(other.0)?.normalize();
+        if quotient.scale() < keep {
+            quotient.rescale(keep);
+        }
+        Some(Self(quotient))
+    }
+
+    /// The remainder of `self / other`, with the sign of `self`, or `None` when dividing by zero.
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        self.0.checked_rem(other.0).map(Self)
+    }
+
+    /// Round to `places` decimal places, with halves rounding away from zero.
+    pub fn round(self, places: u32) -> Self {
+        if self.scale() <= places {
+            return self;
+        }
+        Self(
+            self.0
+                .round_dp_with_strategy(places, RoundingStrategy::MidpointAwayFromZero),
+        )
+    }
+
+    /// Round down to a whole number.
+    pub fn floor(self) -> Self {
+        Self(self.0.floor())
+    }
+
+    /// Round up to a whole number.
+    pub fn ceil(self) -> Self {
+        Self(self.0.ceil())
+    }
+}
+
+/// Parse a plain decimal, like `-12.50`.
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Only plain notation, like Liquid's number literals
+        if s.contains(['e', 'E', '_']) {
+            return Err(ParseDecimalError(()));
+        }
+        s.parse().map(Self).map_err(|_| ParseDecimalError(()))
+    }
+}
+
+/// The error parsing anything but a plain decimal, like `-12.50`, into a [`Decimal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDecimalError(());
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid decimal")
+    }
+}
+
+impl std::error::Error for ParseDecimalError {}
+
+impl From<i64> for Decimal {
+    fn from(i: i64) -> Self {
+        Self(rust_decimal::Decimal::from(i))
+    }
+}
+
+impl From<rust_decimal::Decimal> for Decimal {
+    fn from(d: rust_decimal::Decimal) -> Self {
+        Self(d)
+    }
+}
+
+impl From<Decimal> for rust_decimal::Decimal {
+    fn from(d: Decimal) -> Self {
+        d.0
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Decimal({})", self)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl serde::Serialize for Decimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // Other formats see a string, while `to_value` recognizes the name and keeps a decimal.
+        serializer.serialize_newtype_struct(SERDE_NAME, &self.to_string())
+    }
+}
+
+impl ValueView for Decimal {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        DisplayCow::Borrowed(self)
+    }
+    fn type_name(&self) -> &'static str {
+        "decimal number"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => true,
+            State::DefaultValue => false,
+            State::Empty => false,
+            State::Blank => false,
+        }
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.render().to_string().into()
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(*self)
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::new(*self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_display() {
+        for s in ["0", "12.50", "-0.05", "1234567.891"] {
+            assert_eq!(d(s).to_string(), s);
+        }
+        assert_eq!(d("+.5").to_string(), "0.5");
+        assert_eq!(d("5.").to_string(), "5");
+        for s in ["", "-", ".", "1e5", "1.2.3", "abc", "inf"] {
+            assert!(s.parse::<Decimal>().is_err(), "{}", s);
+        }
+        assert_eq!(Decimal::from_f64(0.1).unwrap(), d("0.1"));
+    }
+
+    #[test]
+    fn test_arithmetic_is_exact() {
+        assert_eq!(d("0.1").checked_add(d("0.2")).unwrap().to_string(), "0.3");
+        assert_eq!(
+            d("10.00").checked_sub(d("0.01")).unwrap().to_string(),
+            "9.99"
+        );
+        assert_eq!(d("19.99").checked_mul(d("3")).unwrap().to_string(), "59.97");
+        assert_eq!(d("10.00").checked_div(d("4")).unwrap().to_string(), "2.50");
+        assert_eq!(
+            d("1").checked_div(d("3")).unwrap().to_string(),
+            "0.3333333333333333333333333333"
+        );
+        assert_eq!(d("2").checked_div(d("3")).unwrap().scale(), 28);
+        assert!(d("1").checked_div(d("0")).is_none());
+        assert_eq!(d("-7.5").checked_rem(d("2")).unwrap().to_string(), "-1.5");
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(d("2.345").round(2).to_string(), "2.35");
+        assert_eq!(d("-2.345").round(2).to_string(), "-2.35");
+        assert_eq!(d("2.344").round(2).to_string(), "2.34");
+        assert_eq!(d("2.5").floor().to_string(), "2");
+        assert_eq!(d("-2.5").floor().to_string(), "-3");
+        assert_eq!(d("2.5").ceil().to_string(), "3");
+        assert_eq!(d("-2.5").ceil().to_string(), "-2");
+        assert_eq!(d("3.00").to_i64(), Some(3));
+        assert_eq!(d("3.01").to_i64(), None);
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(d("1.50"), d("1.5"));
+        assert!(d("1.49") < d("1.5"));
+        assert!(d("-2") < d("-1.99"));
+        assert_eq!(
+            d("12345678901234567890.12345678").to_string(),
+            "12345678901234567890.12345678"
+        );
+    }
+
+    #[test]
+    fn test_scalar() {
+        let value = Value::scalar(d("2.50"));
+        assert_eq!(value.type_name(), "decimal number");
+        assert_eq!(value.render().to_string(), "2.50");
+        assert_eq!(value, Value::scalar(d("2.5")));
+        assert_eq!(Value::scalar(d("2.00")), Value::scalar(2));
+        assert_eq!(value, Value::scalar(2.5));
+        assert!(value.as_scalar().unwrap() < Value::scalar(3).as_scalar().unwrap());
+        assert_eq!(crate::model::to_json(&value), "2.50");
+
+        let value = crate::model::to_value(&d("2.50")).unwrap();
+        assert_eq!(value.type_name(), "decimal number");
+        assert_eq!(
+            serde_yaml::to_string(&d("2.50")).unwrap().trim(),
+            "---\n\"2.50\""
+        );
+    }
+}
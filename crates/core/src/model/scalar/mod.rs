@@ -2,8 +2,12 @@
 
 #![allow(clippy::eq_op)]
 
+mod bytes;
+mod custom;
 mod date;
 mod datetime;
+#[cfg(feature = "decimal")]
+mod decimal;
 pub(crate) mod ser;
 
 use std::cmp::Ordering;
@@ -16,8 +20,16 @@ use crate::model::KStringRef;
 use crate::model::value::{DisplayCow, State};
 use crate::model::{Value, ValueView};
 
+use custom::Custom;
+
+pub use bytes::*;
+pub use custom::CustomScalar;
 pub use date::*;
 pub use datetime::*;
+#[cfg(feature = "decimal")]
+pub(crate) use decimal::SERDE_NAME as SERDE_DECIMAL_NAME;
+#[cfg(feature = "decimal")]
+pub use decimal::*;
 pub use ser::to_scalar;
 
 /// A Liquid scalar value
@@ -39,6 +51,13 @@ enum ScalarCowEnum<'s> {
     DateTime(DateTime),
     Date(Date),
     Str(KStringCow<'s>),
+    #[cfg(feature = "decimal")]
+    #[serde(skip_deserializing)]
+    Decimal(Decimal),
+    #[serde(skip_deserializing)]
+    Bytes(Bytes),
+    #[serde(skip_deserializing)]
+    Custom(Custom),
 }
 
 impl<'s> ScalarCow<'s> {
@@ -47,6 +66,11 @@ impl<'s> ScalarCow<'s> {
         value.into()
     }
 
+    /// Wrap an application-defined scalar.
+    pub fn custom<T: CustomScalar>(value: T) -> Self {
+        Self::from(std::sync::Arc::new(value) as std::sync::Arc<dyn CustomScalar>)
+    }
+
     /// Create an owned version of the value.
     pub fn into_owned(self) -> Scalar {
         match self.0 {
@@ -56,6 +80,10 @@ impl<'s> ScalarCow<'s> {
             ScalarCowEnum::DateTime(x) => Scalar::new(x),
             ScalarCowEnum::Date(x) => Scalar::new(x),
             ScalarCowEnum::Str(x) => Scalar::new(x.into_owned()),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => Scalar::new(x),
+            ScalarCowEnum::Bytes(x) => Scalar::new(x),
+            ScalarCowEnum::Custom(x) => Scalar::new(x),
         }
     }
 
@@ -68,6 +96,10 @@ impl<'s> ScalarCow<'s> {
             ScalarCowEnum::DateTime(x) => ScalarCow::new(x),
             ScalarCowEnum::Date(x) => ScalarCow::new(x),
             ScalarCowEnum::Str(ref x) => ScalarCow::new(x.as_ref()),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => ScalarCow::new(x),
+            ScalarCowEnum::Bytes(ref x) => ScalarCow::new(x.clone()),
+            ScalarCowEnum::Custom(ref x) => ScalarCow::new(x.clone()),
         }
     }
 
@@ -80,6 +112,10 @@ impl<'s> ScalarCow<'s> {
             ScalarCowEnum::DateTime(ref x) => x,
             ScalarCowEnum::Date(ref x) => x,
             ScalarCowEnum::Str(ref x) => x,
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(ref x) => x,
+            ScalarCowEnum::Bytes(ref x) => x,
+            ScalarCowEnum::Custom(ref x) => x,
         }
     }
 
@@ -87,11 +123,15 @@ impl<'s> ScalarCow<'s> {
     pub fn into_string(self) -> KString {
         match self.0 {
             ScalarCowEnum::Integer(x) => x.to_string().into(),
-            ScalarCowEnum::Float(x) => x.to_string().into(),
+            ScalarCowEnum::Float(x) => FloatDisplay(x).to_string().into(),
             ScalarCowEnum::Bool(x) => x.to_string().into(),
             ScalarCowEnum::DateTime(x) => x.to_string().into(),
             ScalarCowEnum::Date(x) => x.to_string().into(),
             ScalarCowEnum::Str(x) => x.into_owned(),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(x) => x.to_string().into(),
+            ScalarCowEnum::Bytes(x) => x.to_string().into(),
+            ScalarCowEnum::Custom(x) => x.to_kstr().into_owned(),
         }
     }
 
@@ -110,6 +150,55 @@ impl<'s> ScalarCow<'s> {
             ScalarCowEnum::Integer(ref x) => Some(*x as f64),
             ScalarCowEnum::Float(ref x) => Some(*x),
             ScalarCowEnum::Str(ref x) => x.parse::<f64>().ok(),
+            #[cfg(feature = "decimal")]
+            ScalarCowEnum::Decimal(ref x) => Some(x.to_f64()),
+            _ => None,
+        }
+    }
+
+    /// Interpret as an exact decimal, if possible
+    ///
+    /// A float is read as its shortest representation, so `0.1` is exactly `0.1`.
+    #[cfg(feature = "decimal")]
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        match self.0 {
+            ScalarCowEnum::Integer(x) => Some(Decimal::from(x)),
+            ScalarCowEnum::Float(x) => Decimal::from_f64(x),
+            ScalarCowEnum::Decimal(x) => Some(x),
+            ScalarCowEnum::Str(ref x) => x.as_str().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Access an integer, without parsing strings
+    pub fn as_integer(&self) -> Option<i64> {
+        match self.0 {
+            ScalarCowEnum::Integer(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Access a float, without parsing strings
+    pub fn as_float(&self) -> Option<f64> {
+        match self.0 {
+            ScalarCowEnum::Float(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Access an exact decimal, without parsing strings
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self.0 {
+            ScalarCowEnum::Decimal(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Access a string, without converting other scalars
+    pub fn as_str(&self) -> Option<&str> {
+        match self.0 {
+            ScalarCowEnum::Str(ref x) => Some(x.as_str()),
             _ => None,
         }
     }
@@ -141,6 +230,52 @@ impl<'s> ScalarCow<'s> {
         }
     }
 
+    /// Interpret as a date or date time to compare with dates, if possible
+    ///
+    /// Strings like `2020-01-31` are parsed, but not relative ones like `now`.
+    pub fn to_date_like(&self) -> Option<Scalar> {
+        match self.0 {
+            ScalarCowEnum::DateTime(x) => Some(x.into()),
+            ScalarCowEnum::Date(x) => Some(x.into()),
+            ScalarCowEnum::Str(ref x) => parse_date_like(x.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Access a date time, without parsing strings
+    pub fn as_date_time(&self) -> Option<DateTime> {
+        match self.0 {
+            ScalarCowEnum::DateTime(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Access a date, without parsing strings
+    pub fn as_date(&self) -> Option<Date> {
+        match self.0 {
+            ScalarCowEnum::Date(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Interpret as binary data, if possible
+    ///
+    /// Only bytes are binary data; strings are text and are not converted.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self.0 {
+            ScalarCowEnum::Bytes(ref x) => Some(x.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Interpret as an application-defined scalar, if possible
+    pub fn as_custom(&self) -> Option<&dyn CustomScalar> {
+        match self.0 {
+            ScalarCowEnum::Custom(ref x) => Some(x.as_dyn()),
+            _ => None,
+        }
+    }
+
     /// Interpret as a Cow str, borrowing if possible
     pub fn into_cow_str(self) -> Cow<'s, str> {
         match self {
@@ -320,10 +455,10 @@ impl ValueView for f64 {
     }
 
     fn render(&self) -> DisplayCow<'_> {
-        DisplayCow::Borrowed(self)
+        DisplayCow::Owned(Box::new(FloatDisplay(*self)))
     }
     fn source(&self) -> DisplayCow<'_> {
-        DisplayCow::Borrowed(self)
+        DisplayCow::Owned(Box::new(FloatDisplay(*self)))
     }
     fn type_name(&self) -> &'static str {
         "fractional number"
@@ -349,6 +484,43 @@ impl ValueView for f64 {
     }
 }
 
+/// Displays a float like Ruby, keeping the `.0` of whole numbers, e.g. `5.0` or `1.0e+20`.
+struct FloatDisplay(f64);
+
+impl fmt::Display for FloatDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let x = self.0;
+        if x.is_nan() {
+            return f.write_str("NaN");
+        } else if x.is_infinite() {
+            return f.write_str(if x < 0.0 { "-Infinity" } else { "Infinity" });
+        }
+
+        let abs = x.abs();
+        if abs != 0.0 && !(1e-4..1e16).contains(&abs) {
+            let scientific = format!("{:e}", x);
+            let (mantissa, exponent) = scientific
+                .split_once('e')
+                .expect("`LowerExp` always writes an exponent");
+            let exponent: i32 = exponent
+                .parse()
+                .expect("`LowerExp` always writes an integer exponent");
+            f.write_str(mantissa)?;
+            if !mantissa.contains('.') {
+                f.write_str(".0")?;
+            }
+            let sign = if exponent < 0 { '-' } else { '+' };
+            write!(f, "e{}{:02}", sign, exponent.abs())
+        } else {
+            write!(f, "{}", x)?;
+            if x.fract() == 0.0 {
+                f.write_str(".0")?;
+            }
+            Ok(())
+        }
+    }
+}
+
 impl<'s> From<f64> for ScalarCow<'s> {
     fn from(s: f64) -> Self {
         ScalarCow(ScalarCowEnum::Float(s))
@@ -581,6 +753,31 @@ impl<'s> fmt::Display for StrSource<'s> {
     }
 }
 
+#[cfg(feature = "decimal")]
+impl<'s> From<Decimal> for ScalarCow<'s> {
+    fn from(s: Decimal) -> Self {
+        ScalarCow(ScalarCowEnum::Decimal(s))
+    }
+}
+
+impl<'s> From<Bytes> for ScalarCow<'s> {
+    fn from(s: Bytes) -> Self {
+        ScalarCow(ScalarCowEnum::Bytes(s))
+    }
+}
+
+impl<'s> From<std::sync::Arc<dyn CustomScalar>> for ScalarCow<'s> {
+    fn from(s: std::sync::Arc<dyn CustomScalar>) -> Self {
+        ScalarCow(ScalarCowEnum::Custom(Custom(s)))
+    }
+}
+
+impl<'s> From<Custom> for ScalarCow<'s> {
+    fn from(s: Custom) -> Self {
+        ScalarCow(ScalarCowEnum::Custom(s))
+    }
+}
+
 impl<'s> From<&'s str> for ScalarCow<'s> {
     fn from(s: &'s str) -> Self {
         ScalarCow(ScalarCowEnum::Str(s.into()))
@@ -650,6 +847,15 @@ impl<'s> From<&'s String> for ScalarCow<'s> {
     }
 }
 
+impl<'s> From<Cow<'s, str>> for ScalarCow<'s> {
+    fn from(s: Cow<'s, str>) -> ScalarCow<'s> {
+        match s {
+            Cow::Borrowed(s) => s.into(),
+            Cow::Owned(s) => s.into(),
+        }
+    }
+}
+
 impl<'s> PartialEq<String> for ScalarCow<'s> {
     fn eq(&self, other: &String) -> bool {
         let other = other.into();
@@ -846,6 +1052,22 @@ fn scalar_eq<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> bool {
         (&ScalarCowEnum::DateTime(x), &ScalarCowEnum::Date(y)) => x == x.with_date(y),
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::DateTime(y)) => y.with_date(x) == y,
         (ScalarCowEnum::Str(x), ScalarCowEnum::Str(y)) => x == y,
+        (ScalarCowEnum::Bytes(x), ScalarCowEnum::Bytes(y)) => x == y,
+        (ScalarCowEnum::Custom(x), ScalarCowEnum::Custom(y)) => x.as_dyn().custom_eq(y.as_dyn()),
+        #[cfg(feature = "decimal")]
+        (ScalarCowEnum::Decimal(_), ScalarCowEnum::Decimal(_) | ScalarCowEnum::Integer(_))
+        | (ScalarCowEnum::Integer(_), ScalarCowEnum::Decimal(_)) => {
+            lhs.to_decimal() == rhs.to_decimal()
+        }
+        #[cfg(feature = "decimal")]
+        (ScalarCowEnum::Decimal(_), ScalarCowEnum::Float(_))
+        | (ScalarCowEnum::Float(_), ScalarCowEnum::Decimal(_)) => lhs.to_float() == rhs.to_float(),
+        (ScalarCowEnum::DateTime(_) | ScalarCowEnum::Date(_), ScalarCowEnum::Str(y)) => {
+            parse_date_like(y.as_str()).is_some_and(|y| scalar_eq(lhs, &y))
+        }
+        (ScalarCowEnum::Str(x), ScalarCowEnum::DateTime(_) | ScalarCowEnum::Date(_)) => {
+            parse_date_like(x.as_str()).is_some_and(|x| scalar_eq(&x, rhs))
+        }
         // encode Ruby truthiness: all values except false and nil are true
         (_, &ScalarCowEnum::Bool(b)) | (&ScalarCowEnum::Bool(b), _) => b,
         _ => false,
@@ -864,10 +1086,40 @@ fn scalar_cmp<'s>(lhs: &ScalarCow<'s>, rhs: &ScalarCow<'s>) -> Option<Ordering>
         (&ScalarCowEnum::DateTime(x), &ScalarCowEnum::Date(y)) => x.partial_cmp(&x.with_date(y)),
         (&ScalarCowEnum::Date(x), &ScalarCowEnum::DateTime(y)) => y.with_date(x).partial_cmp(&y),
         (ScalarCowEnum::Str(x), ScalarCowEnum::Str(y)) => x.partial_cmp(y),
+        (ScalarCowEnum::Bytes(x), ScalarCowEnum::Bytes(y)) => x.partial_cmp(y),
+        (ScalarCowEnum::Custom(x), ScalarCowEnum::Custom(y)) => x.as_dyn().custom_cmp(y.as_dyn()),
+        #[cfg(feature = "decimal")]
+        (ScalarCowEnum::Decimal(_), ScalarCowEnum::Decimal(_) | ScalarCowEnum::Integer(_))
+        | (ScalarCowEnum::Integer(_), ScalarCowEnum::Decimal(_)) => {
+            lhs.to_decimal().partial_cmp(&rhs.to_decimal())
+        }
+        #[cfg(feature = "decimal")]
+        (ScalarCowEnum::Decimal(_), ScalarCowEnum::Float(_))
+        | (ScalarCowEnum::Float(_), ScalarCowEnum::Decimal(_)) => {
+            lhs.to_float().partial_cmp(&rhs.to_float())
+        }
+        (ScalarCowEnum::DateTime(_) | ScalarCowEnum::Date(_), ScalarCowEnum::Str(y)) => {
+            parse_date_like(y.as_str()).and_then(|y| scalar_cmp(lhs, &y))
+        }
+        (ScalarCowEnum::Str(x), ScalarCowEnum::DateTime(_) | ScalarCowEnum::Date(_)) => {
+            parse_date_like(x.as_str()).and_then(|x| scalar_cmp(&x, rhs))
+        }
         _ => None,
     }
 }
 
+/// Parse a string compared with a date, preferring a plain date like `2020-01-31`.
+///
+/// `now` and `today` aren't dates here, so comparisons don't depend on the wall clock.
+fn parse_date_like(s: &str) -> Option<ScalarCow<'static>> {
+    if let "now" | "today" = s.trim().to_lowercase().as_str() {
+        return None;
+    }
+    Date::from_str(s)
+        .map(ScalarCow::from)
+        .or_else(|| DateTime::from_str(s).map(ScalarCow::from))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -875,6 +1127,22 @@ mod test {
     static TRUE: ScalarCow<'_> = ScalarCow(ScalarCowEnum::Bool(true));
     static FALSE: ScalarCow<'_> = ScalarCow(ScalarCowEnum::Bool(false));
 
+    #[test]
+    fn test_strings_shared_on_clone() {
+        let text = "a string too long to be stored inline".repeat(10);
+        let value = Value::scalar(text);
+        let copy = value.clone();
+        assert!(std::ptr::eq(
+            value.to_kstr().as_str(),
+            copy.to_kstr().as_str()
+        ));
+
+        let borrowed = Scalar::new(Cow::Borrowed("static"));
+        assert_eq!(borrowed.into_string(), "static");
+        let owned = Scalar::new(Cow::<str>::Owned("owned".to_owned()));
+        assert_eq!(owned.into_string(), "owned");
+    }
+
     #[test]
     fn test_to_str_bool() {
         assert_eq!(TRUE.to_kstr(), "true");
@@ -889,12 +1157,33 @@ mod test {
     #[test]
     fn test_to_str_float() {
         let val: ScalarCow<'_> = 42f64.into();
-        assert_eq!(val.to_kstr(), "42");
+        assert_eq!(val.to_kstr(), "42.0");
 
         let val: ScalarCow<'_> = 42.34.into();
         assert_eq!(val.to_kstr(), "42.34");
     }
 
+    #[test]
+    fn test_to_str_float_like_ruby() {
+        for (x, expected) in [
+            (-0.0, "-0.0"),
+            (0.0001, "0.0001"),
+            (0.00001, "1.0e-05"),
+            (1.5e-7, "1.5e-07"),
+            (1e15, "1000000000000000.0"),
+            (1e16, "1.0e+16"),
+            (-2.5e20, "-2.5e+20"),
+            (1e100, "1.0e+100"),
+            (f64::INFINITY, "Infinity"),
+            (f64::NEG_INFINITY, "-Infinity"),
+            (f64::NAN, "NaN"),
+        ] {
+            let val: ScalarCow<'_> = x.into();
+            assert_eq!(val.to_kstr(), expected);
+            assert_eq!(val.into_string(), expected);
+        }
+    }
+
     #[test]
     fn test_to_str_str() {
         let val: ScalarCow<'_> = "foobar".into();
@@ -1082,6 +1371,33 @@ mod test {
         assert!(empty.query_state(State::Truthy));
     }
 
+    #[test]
+    fn dates_compare_with_strings() {
+        let date: ScalarCow<'_> = Date::from_ymd(2020, 1, 31).into();
+        let date_time: ScalarCow<'_> = DateTime::from_str("2020-01-31 10:00:00 +0000")
+            .unwrap()
+            .into();
+        let same_date: ScalarCow<'_> = "2020-01-31".into();
+        let later: ScalarCow<'_> = "2020-02-01 00:00:00 +0000".into();
+        let invalid: ScalarCow<'_> = "soon".into();
+
+        assert_eq!(date, same_date);
+        assert_eq!(same_date, date);
+        assert_eq!(date_time, same_date);
+        assert_eq!(date_time, ScalarCow::from("2020-01-31T11:00:00+01:00"));
+        assert!(date != later);
+        assert!(date != invalid);
+
+        assert_eq!(date.partial_cmp(&later), Some(Ordering::Less));
+        assert_eq!(later.partial_cmp(&date_time), Some(Ordering::Greater));
+        assert_eq!(date.partial_cmp(&invalid), None);
+
+        // Relative dates would depend on the wall clock.
+        let today: ScalarCow<'_> = "today".into();
+        assert!(date != today);
+        assert_eq!(date_time.partial_cmp(&ScalarCow::from("now")), None);
+    }
+
     #[test]
     fn borrows_from_scalar_cow() {
         fn is_borrowed(cow: Cow<'_, str>) -> bool {
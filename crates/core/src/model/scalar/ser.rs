@@ -5,6 +5,8 @@ use serde::{self, Serialize};
 use crate::model::ser::SerError;
 use crate::model::Scalar;
 
+use super::Bytes;
+
 /// Convert a `T` into `liquid_core::model::Scalar`.
 ///
 /// # Examples
@@ -105,8 +107,8 @@ impl serde::Serializer for ScalarSerializer {
         Ok(Scalar::new(KString::from_ref(value)))
     }
 
-    fn serialize_bytes(self, _value: &[u8]) -> Result<Self::Ok, Self::Error> {
-        Err(scalar_must_be_a_string())
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Scalar::new(Bytes::from(value)))
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
@@ -128,13 +130,23 @@ impl serde::Serializer for ScalarSerializer {
 
     fn serialize_newtype_struct<T: ?Sized>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Scalar, SerError>
     where
         T: Serialize,
     {
-        value.serialize(ScalarSerializer)
+        let scalar = value.serialize(ScalarSerializer)?;
+        #[cfg(feature = "decimal")]
+        if name == super::decimal::SERDE_NAME {
+            return scalar
+                .to_decimal()
+                .map(Scalar::new)
+                .ok_or_else(|| SerError::new(crate::error::Error::with_msg("Invalid decimal.")));
+        }
+        #[cfg(not(feature = "decimal"))]
+        let _ = name;
+        Ok(scalar)
     }
 
     fn serialize_newtype_variant<T: ?Sized>(
@@ -25,6 +25,11 @@ impl SerError {
         crate::error::Error::with_msg(format!("Unknown type: {}", unexpected.type_name())).into()
     }
 
+    /// Records where in the value the error happened.
+    pub(crate) fn context(self, key: &'static str, value: impl Into<KString>) -> Self {
+        Self(self.0.context(key, value))
+    }
+
     pub(crate) fn invalid_type(unexpected: &dyn ValueView, expected: &str) -> Self {
         crate::error::Error::with_msg(format!(
             "Invalid type: {}, expected {}",
@@ -0,0 +1,165 @@
+use std::fmt::{self, Write};
+
+use super::ValueView;
+
+/// Serialize a value as compact JSON.
+///
+/// `<`, `>`, `&` and the Unicode line separators are escaped within strings, so the result can be
+/// embedded in a `<script>` block.
+pub fn to_json(value: &dyn ValueView) -> String {
+    let mut json = String::new();
+    write_json(&mut json, value, None, 0).expect("writing to a `String` cannot fail");
+    json
+}
+
+/// Serialize a value as JSON, indented by two spaces per level.
+///
+/// Strings are escaped as for [`to_json`].
+pub fn to_json_pretty(value: &dyn ValueView) -> String {
+    let mut json = String::new();
+    write_json(&mut json, value, Some("  "), 0).expect("writing to a `String` cannot fail");
+    json
+}
+
+fn write_json(
+    f: &mut dyn Write,
+    value: &dyn ValueView,
+    indent: Option<&str>,
+    depth: usize,
+) -> fmt::Result {
+    if let Some(scalar) = value.as_scalar() {
+        if let Some(x) = scalar.as_float() {
+            // `Debug` keeps the `.0` of whole floats, like Ruby.
+            return if x.is_finite() {
+                write!(f, "{:?}", x)
+            } else {
+                f.write_str("null")
+            };
+        }
+        #[cfg(feature = "decimal")]
+        let is_decimal = scalar.as_decimal().is_some();
+        #[cfg(not(feature = "decimal"))]
+        let is_decimal = false;
+        if scalar.as_integer().is_some() || scalar.to_bool().is_some() || is_decimal {
+            return write!(f, "{}", scalar.render());
+        }
+        return write_str(f, scalar.to_kstr().as_str());
+    }
+
+    if let Some(array) = value.as_array() {
+        if array.size() == 0 {
+            return f.write_str("[]");
+        }
+        f.write_char('[')?;
+        for (i, item) in array.values().enumerate() {
+            if 0 < i {
+                f.write_char(',')?;
+            }
+            write_newline(f, indent, depth + 1)?;
+            write_json(f, item, indent, depth + 1)?;
+        }
+        write_newline(f, indent, depth)?;
+        return f.write_char(']');
+    }
+
+    if let Some(object) = value.as_object() {
+        if object.size() == 0 {
+            return f.write_str("{}");
+        }
+        f.write_char('{')?;
+        for (i, (key, item)) in object.iter().enumerate() {
+            if 0 < i {
+                f.write_char(',')?;
+            }
+            write_newline(f, indent, depth + 1)?;
+            write_str(f, key.as_str())?;
+            f.write_str(if indent.is_some() { ": " } else { ":" })?;
+            write_json(f, item, indent, depth + 1)?;
+        }
+        write_newline(f, indent, depth)?;
+        return f.write_char('}');
+    }
+
+    f.write_str("null")
+}
+
+fn write_newline(f: &mut dyn Write, indent: Option<&str>, depth: usize) -> fmt::Result {
+    if let Some(indent) = indent {
+        f.write_char('\n')?;
+        for _ in 0..depth {
+            f.write_str(indent)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_str(f: &mut dyn Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => write!(f, "\\u{:04x}", c as u32)?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{Object, Value};
+
+    #[test]
+    fn scalars() {
+        assert_eq!(to_json(&Value::Nil), "null");
+        assert_eq!(to_json(&Value::scalar(true)), "true");
+        assert_eq!(to_json(&Value::scalar(-42)), "-42");
+        assert_eq!(to_json(&Value::scalar(5.0)), "5.0");
+        assert_eq!(to_json(&Value::scalar(0.25)), "0.25");
+        assert_eq!(to_json(&Value::scalar(f64::NAN)), "null");
+        assert_eq!(to_json(&Value::scalar("5")), "\"5\"");
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            to_json(&Value::scalar("say \"hi\"\\\n\t\u{1}")),
+            r#""say \"hi\"\\\n\t\u0001""#
+        );
+        assert_eq!(
+            to_json(&Value::scalar("</script><!-- & \u{2028}")),
+            r#""\u003c/script\u003e\u003c!-- \u0026 \u2028""#
+        );
+        assert_eq!(to_json(&Value::scalar("héllo")), "\"héllo\"");
+    }
+
+    #[test]
+    fn nested() {
+        let mut inner = Object::new();
+        inner.insert("c".into(), Value::Object(Object::new()));
+        let mut object = Object::new();
+        object.insert(
+            "b".into(),
+            Value::array(vec![
+                Value::scalar("x"),
+                Value::Nil,
+                Value::Array(vec![]),
+                Value::Object(inner),
+            ]),
+        );
+        let value = Value::Object(object);
+
+        assert_eq!(to_json(&value), r#"{"b":["x",null,[],{"c":{}}]}"#);
+        assert_eq!(
+            to_json_pretty(&value),
+            "{\n  \"b\": [\n    \"x\",\n    null,\n    [],\n    {\n      \"c\": {}\n    }\n  ]\n}"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use crate::model::KStringCow;
+
+use super::DisplayCow;
+use super::State;
+use super::Value;
+use super::ValueView;
+use crate::model::ArrayView;
+use crate::model::ObjectView;
+use crate::model::ScalarCow;
+
+/// A value computed by a closure the first time it is read.
+///
+/// The closure is not called unless a template reads the value.  A `Lazy` is a [`Value`], so it
+/// can be a global or sit inside an [`Object`][crate::model::Object].  The computed value is kept
+/// by this `Lazy` until it is dropped, even across renders, while a clone computes it anew, so
+/// clone the globals for each render to compute it again.
+///
+/// # Examples
+///
+/// ```rust
+/// use liquid_core::model::{Lazy, Object, Value, ValueView};
+///
+/// let mut globals = Object::new();
+/// globals.insert("total".into(), Value::Lazy(Lazy::new(|| Value::scalar(42))));
+/// assert_eq!(globals.to_kstr(), "total42");
+/// ```
+pub struct Lazy {
+    init: Arc<dyn Fn() -> Value + Send + Sync>,
+    value: Box<OnceLock<Value>>,
+}
+
+impl Lazy {
+    /// Create a value computed by `init` when first read.
+    pub fn new<F>(init: F) -> Self
+    where
+        F: Fn() -> Value + Send + Sync + 'static,
+    {
+        Self {
+            init: Arc::new(init),
+            value: Default::default(),
+        }
+    }
+
+    /// Get the value, computing it if this is the first read.
+    pub fn force(&self) -> &Value {
+        self.value.get_or_init(|| (self.init)())
+    }
+}
+
+impl Clone for Lazy {
+    fn clone(&self) -> Self {
+        Self {
+            init: self.init.clone(),
+            value: Default::default(),
+        }
+    }
+}
+
+impl fmt::Debug for Lazy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None => f.write_str("Lazy(<uncomputed>)"),
+        }
+    }
+}
+
+impl serde::Serialize for Lazy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.force().serialize(serializer)
+    }
+}
+
+impl ValueView for Lazy {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        self.force().render()
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        self.force().source()
+    }
+    fn type_name(&self) -> &'static str {
+        self.force().type_name()
+    }
+    fn query_state(&self, state: State) -> bool {
+        self.force().query_state(state)
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.force().to_kstr()
+    }
+    fn to_value(&self) -> Value {
+        self.force().clone()
+    }
+
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        self.force().as_scalar()
+    }
+
+    fn as_array(&self) -> Option<&dyn ArrayView> {
+        self.force().as_array()
+    }
+
+    fn as_object(&self) -> Option<&dyn ObjectView> {
+        self.force().as_object()
+    }
+
+    fn as_state(&self) -> Option<State> {
+        self.force().as_state()
+    }
+
+    fn is_nil(&self) -> bool {
+        self.force().is_nil()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn computed_once_on_first_read() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let lazy = Lazy::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            crate::value!([1, 2, 3])
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(format!("{:?}", lazy), "Lazy(<uncomputed>)");
+
+        assert_eq!(lazy.as_array().unwrap().size(), 3);
+        assert_eq!(lazy.render().to_string(), "123");
+        assert_eq!(lazy.to_value(), crate::value!([1, 2, 3]));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Clones, like the globals of another render, compute the value again.
+        let value = Value::Lazy(lazy.clone());
+        assert_eq!(value, crate::value!([1, 2, 3]));
+        assert_eq!(value, crate::value!([1, 2, 3]));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn inside_object() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut object = crate::model::Object::new();
+        object.insert(
+            "total".into(),
+            Value::Lazy(Lazy::new(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Value::scalar(42)
+            })),
+        );
+        object.insert("name".into(), Value::scalar("cart"));
+        let path = [ScalarCow::new("name")];
+        assert_eq!(
+            crate::model::find(&object, &path).unwrap().to_kstr(),
+            "cart"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let path = [ScalarCow::new("total")];
+        assert_eq!(crate::model::find(&object, &path).unwrap().to_kstr(), "42");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
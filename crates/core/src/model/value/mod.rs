@@ -2,6 +2,8 @@
 
 mod cow;
 mod display;
+mod json;
+mod lazy;
 mod state;
 mod values;
 mod view;
@@ -10,6 +12,8 @@ pub(crate) mod ser;
 
 pub use cow::*;
 pub use display::*;
+pub use json::*;
+pub use lazy::*;
 pub use ser::*;
 pub use state::*;
 pub use values::*;
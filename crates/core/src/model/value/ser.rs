@@ -16,6 +16,24 @@ use crate::model::{KString, KStringCow};
 /// let value = liquid_core::model::to_value(&s).unwrap();
 /// assert_eq!(value, liquid_core::model::Value::scalar(s));
 /// ```
+///
+/// Any `Serialize` type can be used, so templates can be rendered from existing application
+/// types:
+///
+/// ```rust
+/// #[derive(serde::Serialize)]
+/// struct Product {
+///     title: String,
+///     price: i64,
+/// }
+///
+/// let product = Product { title: "Shirt".to_owned(), price: 10 };
+/// let value = liquid_core::model::to_value(&product).unwrap();
+/// assert_eq!(value, liquid_core::value!({"title": "Shirt", "price": 10}));
+/// ```
+///
+/// Integers outside the range of `i64` and map keys that are not strings or integers are
+/// reported as errors.
 pub fn to_value<T>(value: &T) -> Result<Value, crate::error::Error>
 where
     T: Serialize,
@@ -24,6 +42,28 @@ where
 }
 
 /// Convert a value into `T`.
+///
+/// This lets custom filters and tags parse their arguments into Rust types.  An enum's unit
+/// variant is read from a string and any other variant from an object with the variant as its
+/// only key.  Errors say which key or index held the unexpected value.
+///
+/// # Examples
+///
+/// ```rust
+/// #[derive(serde::Deserialize, Debug, PartialEq)]
+/// struct Options {
+///     limit: i64,
+///     reverse: Option<bool>,
+/// }
+///
+/// let value = liquid_core::value!({"limit": 5});
+/// let options: Options = liquid_core::model::from_value(&value).unwrap();
+/// assert_eq!(options, Options { limit: 5, reverse: None });
+///
+/// let value = liquid_core::value!({"limit": "five"});
+/// let error = liquid_core::model::from_value::<Options>(&value).unwrap_err();
+/// assert!(error.to_string().contains("limit"));
+/// ```
 pub fn from_value<'a, T>(v: &'a dyn ValueView) -> Result<T, crate::error::Error>
 where
     T: Deserialize<'a>,
@@ -111,8 +151,7 @@ impl serde::Serializer for ValueSerializer {
     }
 
     fn serialize_bytes(self, value: &[u8]) -> Result<Value, SerError> {
-        let vec = value.iter().map(|&b| Value::scalar(i64::from(b))).collect();
-        Ok(Value::Array(vec))
+        Ok(Value::scalar(crate::model::Bytes::from(value)))
     }
 
     #[inline]
@@ -140,12 +179,20 @@ impl serde::Serializer for ValueSerializer {
     #[inline]
     fn serialize_newtype_struct<T: ?Sized>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Value, SerError>
     where
         T: Serialize,
     {
+        #[cfg(feature = "decimal")]
+        if name == crate::model::scalar::SERDE_DECIMAL_NAME {
+            return ScalarSerializer
+                .serialize_newtype_struct(name, value)
+                .map(Value::Scalar);
+        }
+        #[cfg(not(feature = "decimal"))]
+        let _ = name;
         value.serialize(ValueSerializer)
     }
 
@@ -457,17 +504,23 @@ impl<'de, 'a> serde::Deserializer<'de> for &'a mut ValueDeserializer<'de> {
     {
         self.deserialize_str(visitor)
     }
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        Err(SerError::invalid_type(self.input, "bytes"))
+        match self.input.as_scalar().as_ref().and_then(|s| s.as_bytes()) {
+            Some(bytes) => visitor.visit_bytes(bytes),
+            None => Err(SerError::invalid_type(self.input, "bytes")),
+        }
     }
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        Err(SerError::invalid_type(self.input, "bytes"))
+        match self.input.as_scalar().as_ref().and_then(|s| s.as_bytes()) {
+            Some(bytes) => visitor.visit_bytes(bytes),
+            None => Err(SerError::invalid_type(self.input, "bytes")),
+        }
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -566,12 +619,23 @@ impl<'de, 'a> serde::Deserializer<'de> for &'a mut ValueDeserializer<'de> {
         self,
         _name: &'static str,
         _variants: &'static [&'static str],
-        _visitor: V,
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        Err(SerError::invalid_type(self.input, "enum"))
+        // Like `serde_json`, a unit variant is a string and any other variant is an object
+        // with the variant as its only key.
+        if let Some(scalar) = self.input.as_scalar() {
+            let variant = scalar.to_kstr();
+            visitor.visit_enum(variant.as_str().into_deserializer())
+        } else if let Some(object) = self.input.as_object().filter(|o| o.size() == 1) {
+            visitor.visit_enum(serde::de::value::MapAccessDeserializer::new(
+                ObjectDeserializer::new(object),
+            ))
+        } else {
+            Err(SerError::invalid_type(self.input, "enum"))
+        }
     }
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -589,7 +653,7 @@ impl<'de, 'a> serde::Deserializer<'de> for &'a mut ValueDeserializer<'de> {
 
 struct ObjectDeserializer<'de> {
     iter: Box<dyn Iterator<Item = (KStringCow<'de>, &'de (dyn ValueView + 'de))> + 'de>,
-    value: Option<&'de (dyn ValueView + 'de)>,
+    value: Option<(KStringCow<'de>, &'de (dyn ValueView + 'de))>,
 }
 
 impl<'de> ObjectDeserializer<'de> {
@@ -610,8 +674,9 @@ impl<'de> serde::de::MapAccess<'de> for ObjectDeserializer<'de> {
     {
         match self.iter.next() {
             Some((k, v)) => {
-                self.value = Some(v);
-                seed.deserialize(k.as_str().into_deserializer()).map(Some)
+                let key = seed.deserialize(k.as_str().into_deserializer()).map(Some);
+                self.value = Some((k, v));
+                key
             }
             None => Ok(None),
         }
@@ -622,7 +687,9 @@ impl<'de> serde::de::MapAccess<'de> for ObjectDeserializer<'de> {
         V: serde::de::DeserializeSeed<'de>,
     {
         match self.value {
-            Some(v) => seed.deserialize(&mut ValueDeserializer::from_value(v)),
+            Some((ref k, v)) => seed
+                .deserialize(&mut ValueDeserializer::from_value(v))
+                .map_err(|e| e.context("key", KString::from_ref(k.as_str()))),
             None => {
                 panic!("no more values in next_value_seed, internal error in ValueDeserializer")
             }
@@ -631,13 +698,13 @@ impl<'de> serde::de::MapAccess<'de> for ObjectDeserializer<'de> {
 }
 
 struct ArrayDeserializer<'de> {
-    iter: Box<dyn Iterator<Item = &'de dyn ValueView> + 'de>,
+    iter: std::iter::Enumerate<Box<dyn Iterator<Item = &'de dyn ValueView> + 'de>>,
 }
 
 impl<'de> ArrayDeserializer<'de> {
     fn new(input: &'de dyn ArrayView) -> Self {
         Self {
-            iter: input.values(),
+            iter: input.values().enumerate(),
         }
     }
 }
@@ -650,9 +717,10 @@ impl<'de> serde::de::SeqAccess<'de> for ArrayDeserializer<'de> {
         T: serde::de::DeserializeSeed<'de>,
     {
         match self.iter.next() {
-            Some(v) => seed
+            Some((i, v)) => seed
                 .deserialize(&mut ValueDeserializer::from_value(v))
-                .map(Some),
+                .map(Some)
+                .map_err(|e| e.context("index", i.to_string())),
             None => Ok(None),
         }
     }
@@ -882,7 +950,40 @@ Hello
 
     #[test]
     pub fn serialize_object() {
-        // Skipping due to HashMap ordering issues
+        let actual: crate::model::Object = [
+            ("Num".into(), crate::model::Value::scalar(1f64)),
+            ("Bool".into(), crate::model::Value::scalar(true)),
+            ("Str".into(), crate::model::Value::scalar("true")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let actual = crate::model::Value::Object(actual);
+        let actual = serde_yaml::to_string(&actual).unwrap();
+        assert_data_eq!(
+            actual.trim(),
+            str![[r#"
+---
+Num: 1.0
+Bool: true
+Str: "true"
+"#]]
+            .raw()
+        );
+    }
+
+    #[test]
+    pub fn deserialize_object_order() {
+        let actual: crate::model::Value = serde_yaml::from_str(
+            "---
+zebra: 1
+apple: 2
+mango: 3",
+        )
+        .unwrap();
+        let actual = actual.into_object().unwrap();
+        let keys: Vec<_> = actual.keys().map(|k| k.as_str()).collect();
+        assert_eq!(keys, ["zebra", "apple", "mango"]);
     }
 
     #[test]
@@ -900,4 +1001,94 @@ Hello
         let expected = crate::model::Value::Object(expected);
         assert_eq!(actual, expected);
     }
+
+    #[derive(serde::Serialize)]
+    struct Product {
+        title: &'static str,
+        price: u32,
+        tags: Vec<&'static str>,
+        discount: Option<f64>,
+        kind: Kind,
+        dimensions: Dimensions,
+    }
+
+    #[derive(serde::Serialize)]
+    enum Kind {
+        Physical,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Dimensions {
+        width: i64,
+    }
+
+    #[test]
+    pub fn to_value_struct() {
+        let product = Product {
+            title: "Shirt",
+            price: 10,
+            tags: vec!["cotton", "blue"],
+            discount: None,
+            kind: Kind::Physical,
+            dimensions: Dimensions { width: 40 },
+        };
+        let actual = super::to_value(&product).unwrap();
+        let expected = crate::value!({
+            "title": "Shirt",
+            "price": 10,
+            "tags": ["cotton", "blue"],
+            "discount": nil,
+            "kind": "Physical",
+            "dimensions": {"width": 40},
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    pub fn to_value_unrepresentable() {
+        super::to_value(&u64::MAX).unwrap_err();
+
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(vec![1], 1);
+        super::to_value(&map).unwrap_err();
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    enum Order {
+        Ascending,
+        By(String),
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Query {
+        limit: u8,
+        orders: Vec<Order>,
+    }
+
+    #[test]
+    pub fn from_value_struct() {
+        let value = crate::value!({
+            "limit": 3,
+            "orders": ["Ascending", {"By": "title"}],
+        });
+        let actual: Query = super::from_value(&value).unwrap();
+        let expected = Query {
+            limit: 3,
+            orders: vec![Order::Ascending, Order::By("title".to_owned())],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    pub fn from_value_error_location() {
+        let value = crate::value!({"limit": 3, "orders": ["Ascending", "Descending"]});
+        let error = super::from_value::<Query>(&value).unwrap_err().to_string();
+        assert!(error.contains("Descending"), "{}", error);
+        assert!(error.contains("index=1"), "{}", error);
+        assert!(error.contains("key=orders"), "{}", error);
+
+        let value = crate::value!({"limit": "three", "orders": []});
+        let error = super::from_value::<Query>(&value).unwrap_err().to_string();
+        assert!(error.contains("key=limit"), "{}", error);
+    }
 }
@@ -6,9 +6,10 @@ use std::fmt;
 use crate::model::KStringCow;
 
 use super::DisplayCow;
+use super::Lazy;
 use super::State;
 use super::{ValueView, ValueViewCmp};
-use crate::model::array::{Array, ArrayView};
+use crate::model::array::{Array, ArrayView, Range};
 use crate::model::object::{Object, ObjectView};
 use crate::model::scalar::{Scalar, ScalarCow};
 
@@ -20,8 +21,14 @@ pub enum Value {
     Scalar(Scalar),
     /// A sequence of `Value`s.
     Array(Array),
+    /// An inclusive range of integers.
+    #[serde(skip_deserializing)]
+    Range(Range),
     /// A sequence of key/`Value` pairs.
     Object(Object),
+    /// A value computed when first read.
+    #[serde(skip_deserializing)]
+    Lazy(Lazy),
     /// Query symbol.
     State(State),
     /// Nothing.
@@ -46,6 +53,8 @@ impl Value {
             Value::Scalar(ref x) => x,
             Value::Object(ref x) => x,
             Value::Array(ref x) => x,
+            Value::Range(ref x) => x,
+            Value::Lazy(ref x) => x.force().as_view(),
             Value::State(ref x) => x,
             Value::Nil => self,
         }
@@ -55,20 +64,31 @@ impl Value {
     pub fn into_scalar(self) -> Option<Scalar> {
         match self {
             Value::Scalar(s) => Some(s),
+            Value::Lazy(l) => l.force().clone().into_scalar(),
             _ => None,
         }
     }
 
-    /// Extracts the array value if it is an array.
+    /// Extracts the array value if it is an array, converting a range into its integers.
     pub fn into_array(self) -> Option<Array> {
         match self {
             Value::Array(s) => Some(s),
+            Value::Range(r) => Some(r.into_array()),
+            Value::Lazy(l) => l.force().clone().into_array(),
             _ => None,
         }
     }
 
-    /// Extracts the array value as mutable if it is a object.
+    /// Extracts the array value as mutable if it is an array, first converting a range into its
+    /// integers.
     pub fn as_array_mut(&mut self) -> Option<&mut Array> {
+        if let Value::Range(r) = self {
+            *self = Value::Array(r.clone().into_array());
+        }
+        if let Value::Lazy(l) = self {
+            *self = l.force().clone();
+            return self.as_array_mut();
+        }
         match *self {
             Value::Array(ref mut s) => Some(s),
             _ => None,
@@ -79,12 +99,16 @@ impl Value {
     pub fn into_object(self) -> Option<Object> {
         match self {
             Value::Object(s) => Some(s),
+            Value::Lazy(l) => l.force().clone().into_object(),
             _ => None,
         }
     }
 
-    /// Extracts the object value as mutable if it is a object.
+    /// Extracts the object value as mutable if it is a object, first computing a lazy value.
     pub fn as_object_mut(&mut self) -> Option<&mut Object> {
+        if let Value::Lazy(l) = self {
+            *self = l.force().clone();
+        }
         match *self {
             Value::Object(ref mut s) => Some(s),
             _ => None,
@@ -95,6 +119,7 @@ impl Value {
     pub fn into_state(self) -> Option<State> {
         match self {
             Value::State(s) => Some(s),
+            Value::Lazy(l) => l.force().clone().into_state(),
             _ => None,
         }
     }
@@ -109,6 +134,8 @@ impl ValueView for Value {
         match *self {
             Value::Scalar(ref x) => x.render(),
             Value::Array(ref x) => x.render(),
+            Value::Range(ref x) => x.render(),
+            Value::Lazy(ref x) => x.render(),
             Value::Object(ref x) => x.render(),
             Value::State(ref x) => x.render(),
             Value::Nil => DisplayCow::Borrowed(&""),
@@ -118,6 +145,8 @@ impl ValueView for Value {
         match *self {
             Value::Scalar(ref x) => x.source(),
             Value::Array(ref x) => x.source(),
+            Value::Range(ref x) => x.source(),
+            Value::Lazy(ref x) => x.source(),
             Value::Object(ref x) => x.source(),
             Value::State(ref x) => x.source(),
             Value::Nil => DisplayCow::Owned(Box::new(super::StrDisplay {
@@ -129,6 +158,8 @@ impl ValueView for Value {
         match *self {
             Value::Scalar(ref x) => x.type_name(),
             Value::Array(ref x) => x.type_name(),
+            Value::Range(ref x) => x.type_name(),
+            Value::Lazy(ref x) => x.type_name(),
             Value::Object(ref x) => x.type_name(),
             Value::State(ref x) => x.type_name(),
             Value::Nil => "nil",
@@ -138,6 +169,8 @@ impl ValueView for Value {
         match *self {
             Value::Scalar(ref x) => x.query_state(state),
             Value::Array(ref x) => x.query_state(state),
+            Value::Range(ref x) => x.query_state(state),
+            Value::Lazy(ref x) => x.query_state(state),
             Value::Object(ref x) => x.query_state(state),
             Value::State(ref x) => x.query_state(state),
             Value::Nil => match state {
@@ -153,6 +186,8 @@ impl ValueView for Value {
         match *self {
             Value::Scalar(ref x) => x.to_kstr(),
             Value::Array(ref x) => x.to_kstr(),
+            Value::Range(ref x) => x.to_kstr(),
+            Value::Lazy(ref x) => x.to_kstr(),
             Value::Object(ref x) => x.to_kstr(),
             Value::State(ref x) => x.to_kstr(),
             Value::Nil => KStringCow::from_static(""),
@@ -166,6 +201,8 @@ impl ValueView for Value {
                 Value::Scalar(x)
             }
             Value::Array(ref x) => Value::Array(x.clone()),
+            Value::Range(ref x) => x.to_value(),
+            Value::Lazy(ref x) => x.to_value(),
             Value::Object(ref x) => Value::Object(x.clone()),
             Value::State(ref x) => Value::State(*x),
             Value::Nil => Value::Nil,
@@ -175,29 +212,37 @@ impl ValueView for Value {
     fn as_scalar(&self) -> Option<ScalarCow<'_>> {
         match self {
             Value::Scalar(s) => Some(s.as_ref()),
+            Value::Lazy(l) => l.as_scalar(),
             _ => None,
         }
     }
     fn as_array(&self) -> Option<&dyn ArrayView> {
         match self {
             Value::Array(ref s) => Some(s),
+            Value::Range(ref s) => Some(s),
+            Value::Lazy(ref l) => l.as_array(),
             _ => None,
         }
     }
     fn as_object(&self) -> Option<&dyn ObjectView> {
         match self {
             Value::Object(ref s) => Some(s),
+            Value::Lazy(ref l) => l.as_object(),
             _ => None,
         }
     }
     fn as_state(&self) -> Option<State> {
         match self {
             Value::State(s) => Some(*s),
+            Value::Lazy(l) => l.as_state(),
             _ => None,
         }
     }
     fn is_nil(&self) -> bool {
-        matches!(self, Value::Nil)
+        match self {
+            Value::Lazy(l) => l.is_nil(),
+            _ => matches!(self, Value::Nil),
+        }
     }
 }
 
@@ -213,6 +258,18 @@ impl From<Array> for Value {
     }
 }
 
+impl From<Range> for Value {
+    fn from(other: Range) -> Self {
+        Value::Range(other)
+    }
+}
+
+impl From<Lazy> for Value {
+    fn from(other: Lazy) -> Self {
+        Value::Lazy(other)
+    }
+}
+
 impl From<Object> for Value {
     fn from(other: Object) -> Self {
         Value::Object(other)
@@ -325,8 +382,8 @@ mod test {
     #[test]
     fn test_to_string_scalar() {
         let val = Value::scalar(42f64);
-        assert_eq!(&val.render().to_string(), "42");
-        assert_eq!(&val.to_kstr(), "42");
+        assert_eq!(&val.render().to_string(), "42.0");
+        assert_eq!(&val.to_kstr(), "42.0");
     }
 
     #[test]
@@ -336,11 +393,16 @@ mod test {
             Value::scalar("test"),
             Value::scalar(5.3),
         ]);
-        assert_eq!(&val.render().to_string(), "3test5.3");
-        assert_eq!(&val.to_kstr(), "3test5.3");
+        assert_eq!(&val.render().to_string(), "3.0test5.3");
+        assert_eq!(&val.to_kstr(), "3.0test5.3");
     }
 
-    // TODO make a test for object, remember values are in arbitrary orders in HashMaps
+    #[test]
+    fn test_to_string_object() {
+        let val = crate::value!({"zebra": 1, "apple": "two", "mango": 3.5});
+        assert_eq!(&val.render().to_string(), "zebra1appletwomango3.5");
+        assert_eq!(&val.to_kstr(), "zebra1appletwomango3.5");
+    }
 
     #[test]
     fn test_to_string_nil() {
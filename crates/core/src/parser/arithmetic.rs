@@ -0,0 +1,283 @@
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::model::{Value, ValueView};
+
+/// An arithmetic operator, like the `+` in `{{ price + shipping }}`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Operator {
+    /// `+`
+    Add,
+    /// `-`
+    Subtract,
+    /// `*`
+    Multiply,
+    /// `/`
+    Divide,
+    /// `%`
+    Modulo,
+}
+
+impl Operator {
+    /// Parse an operator from its symbol.
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "+" => Some(Self::Add),
+            "-" => Some(Self::Subtract),
+            "*" => Some(Self::Multiply),
+            "/" => Some(Self::Divide),
+            "%" => Some(Self::Modulo),
+            _ => None,
+        }
+    }
+
+    /// The symbol for the operator.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Modulo => "%",
+        }
+    }
+
+    /// Whether the operator binds tighter than `+` and `-`.
+    fn is_multiplicative(self) -> bool {
+        matches!(self, Self::Multiply | Self::Divide | Self::Modulo)
+    }
+
+    /// Apply the operator to two numbers.
+    ///
+    /// Integers stay integers, unless either side is a float. Like Ruby, integer division
+    /// rounds down and a remainder takes the sign of `rhs`, so `-7 / 2` is `-4` and `-7 % 3`
+    /// is `2`. The `plus`, `minus`, `times`, `divided_by` and `modulo` filters share this.
+    pub fn apply(self, lhs: &dyn ValueView, rhs: &dyn ValueView) -> Result<Value> {
+        let lhs = lhs
+            .as_scalar()
+            .ok_or_else(|| operand_error(self, "Number expected", lhs))?;
+        let rhs = rhs
+            .as_scalar()
+            .ok_or_else(|| operand_error(self, "Number expected", rhs))?;
+
+        if let (Some(l), Some(r)) = (lhs.to_integer(), rhs.to_integer()) {
+            let result = match self {
+                Self::Add => l.checked_add(r),
+                Self::Subtract => l.checked_sub(r),
+                Self::Multiply => l.checked_mul(r),
+                Self::Divide | Self::Modulo if r == 0 => {
+                    return Err(operand_error(self, "Can't divide by zero", &rhs));
+                }
+                Self::Divide => l.checked_div(r).map(|quotient| {
+                    if l % r != 0 && (l < 0) != (r < 0) {
+                        quotient - 1
+                    } else {
+                        quotient
+                    }
+                }),
+                // `i64::MIN % -1` only overflows on the way to `0`
+                Self::Modulo => Some(floor_remainder(l.wrapping_rem(r), r, 0, |a, b| a + b)),
+            };
+            return result
+                .map(Value::scalar)
+                .ok_or_else(|| operand_error(self, "Integer overflow", &rhs));
+        }
+
+        #[cfg(feature = "decimal")]
+        if lhs.as_decimal().is_some() || rhs.as_decimal().is_some() {
+            if let (Some(l), Some(r)) = (lhs.to_decimal(), rhs.to_decimal()) {
+                let result = match self {
+                    Self::Add => l.checked_add(r),
+                    Self::Subtract => l.checked_sub(r),
+                    Self::Multiply => l.checked_mul(r),
+                    Self::Divide | Self::Modulo if r.mantissa() == 0 => {
+                        return Err(operand_error(self, "Can't divide by zero", &rhs));
+                    }
+                    Self::Divide => l.checked_div(r),
+                    Self::Modulo => l.checked_rem(r).and_then(|remainder| {
+                        if remainder.mantissa() != 0
+                            && (remainder.mantissa() < 0) != (r.mantissa() < 0)
+                        {
+                            remainder.checked_add(r)
+                        } else {
+                            Some(remainder)
+                        }
+                    }),
+                };
+                return result
+                    .map(Value::scalar)
+                    .ok_or_else(|| operand_error(self, "Decimal overflow", &rhs));
+            }
+        }
+
+        match (lhs.to_float(), rhs.to_float()) {
+            (Some(l), Some(r)) => {
+                let result = match self {
+                    Self::Add => l + r,
+                    Self::Subtract => l - r,
+                    Self::Multiply => l * r,
+                    Self::Divide | Self::Modulo if r == 0.0 => {
+                        return Err(operand_error(self, "Can't divide by zero", &rhs));
+                    }
+                    Self::Divide => l / r,
+                    Self::Modulo => floor_remainder(l % r, r, 0.0, |a, b| a + b),
+                };
+                Ok(Value::scalar(result))
+            }
+            (None, _) => Err(operand_error(self, "Number expected", &lhs)),
+            (_, None) => Err(operand_error(self, "Number expected", &rhs)),
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Give a truncated remainder the sign of the divisor, like Ruby.
+fn floor_remainder<N: Copy + PartialOrd>(
+    remainder: N,
+    divisor: N,
+    zero: N,
+    add: impl FnOnce(N, N) -> N,
+) -> N {
+    if remainder != zero && (remainder < zero) != (divisor < zero) {
+        add(remainder, divisor)
+    } else {
+        remainder
+    }
+}
+
+fn operand_error(op: Operator, msg: &'static str, operand: &dyn ValueView) -> Error {
+    Error::with_msg(msg)
+        .context("operator", op.symbol())
+        .context("operand", operand.source().to_string())
+}
+
+/// Evaluate `first` followed by `rest`, with `*`, `/` and `%` taking
+/// precedence over `+` and `-`.
+pub(crate) fn evaluate<'v>(
+    first: &dyn ValueView,
+    rest: impl Iterator<Item = (Operator, &'v dyn ValueView)>,
+) -> Result<Value> {
+    let mut sum: Option<(Value, Operator)> = None;
+    let mut term = first.to_value();
+    for (op, operand) in rest {
+        if op.is_multiplicative() {
+            term = op.apply(&term, operand)?;
+        } else {
+            let lhs = match sum.take() {
+                Some((lhs, sum_op)) => sum_op.apply(&lhs, &term)?,
+                None => term,
+            };
+            sum = Some((lhs, op));
+            term = operand.to_value();
+        }
+    }
+    match sum {
+        Some((lhs, op)) => op.apply(&lhs, &term),
+        None => Ok(term),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn eval(first: i64, rest: &[(Operator, Value)]) -> Result<Value> {
+        evaluate(
+            &first,
+            rest.iter().map(|(op, v)| (*op, v as &dyn ValueView)),
+        )
+    }
+
+    #[test]
+    fn precedence() {
+        let actual = eval(
+            1,
+            &[
+                (Operator::Add, Value::scalar(2)),
+                (Operator::Multiply, Value::scalar(3)),
+                (Operator::Subtract, Value::scalar(4)),
+                (Operator::Modulo, Value::scalar(3)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(actual, Value::scalar(6));
+    }
+
+    #[test]
+    fn left_associative() {
+        let actual = eval(
+            10,
+            &[
+                (Operator::Subtract, Value::scalar(3)),
+                (Operator::Subtract, Value::scalar(2)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(actual, Value::scalar(5));
+
+        let actual = eval(
+            12,
+            &[
+                (Operator::Divide, Value::scalar(3)),
+                (Operator::Divide, Value::scalar(2)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(actual, Value::scalar(2));
+    }
+
+    #[test]
+    fn mixed_numbers() {
+        let actual = eval(3, &[(Operator::Divide, Value::scalar(2.0))]).unwrap();
+        assert_eq!(actual, Value::scalar(1.5));
+
+        let actual = eval(3, &[(Operator::Divide, Value::scalar(2))]).unwrap();
+        assert_eq!(actual, Value::scalar(1));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimals() {
+        let d = |s: &str| Value::scalar(s.parse::<crate::model::Decimal>().unwrap());
+        let actual = eval(
+            3,
+            &[
+                (Operator::Multiply, d("0.10")),
+                (Operator::Add, Value::scalar(0.2)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(actual.render().to_string(), "0.50");
+        assert!(eval(3, &[(Operator::Modulo, d("0.0"))]).is_err());
+    }
+
+    #[test]
+    fn negative_division() {
+        let actual = eval(-7, &[(Operator::Divide, Value::scalar(2))]).unwrap();
+        assert_eq!(actual, Value::scalar(-4));
+        let actual = eval(7, &[(Operator::Divide, Value::scalar(-2))]).unwrap();
+        assert_eq!(actual, Value::scalar(-4));
+        let actual = eval(-7, &[(Operator::Modulo, Value::scalar(3))]).unwrap();
+        assert_eq!(actual, Value::scalar(2));
+        let actual = eval(7, &[(Operator::Modulo, Value::scalar(-3))]).unwrap();
+        assert_eq!(actual, Value::scalar(-2));
+        let actual = eval(-7, &[(Operator::Modulo, Value::scalar(3.0))]).unwrap();
+        assert_eq!(actual, Value::scalar(2.0));
+        let actual = eval(-7, &[(Operator::Divide, Value::scalar(2.0))]).unwrap();
+        assert_eq!(actual, Value::scalar(-3.5));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(eval(3, &[(Operator::Divide, Value::scalar(0))]).is_err());
+        assert!(eval(3, &[(Operator::Modulo, Value::scalar(0.0))]).is_err());
+        assert!(eval(3, &[(Operator::Add, Value::scalar("a"))]).is_err());
+        assert!(eval(3, &[(Operator::Add, Value::Nil)]).is_err());
+        assert!(eval(i64::MAX, &[(Operator::Add, Value::scalar(1))]).is_err());
+    }
+}
@@ -22,9 +22,11 @@ pub trait BlockReflection {
 }
 
 /// A trait for creating custom custom block-size tags (`{% if something %}{% endif %}`).
-/// This is a simple type alias for a function.
 ///
-/// This function will be called whenever the parser encounters a block and returns
+/// Implementors may carry state (configuration, handles, etc) captured when the parser is
+/// built.  For a one-off block, a closure can be wrapped in [`FnParseBlock`] instead.
+///
+/// `parse` will be called whenever the parser encounters a block and returns
 /// a new `Renderable` based on its parameters. The received parameters specify the name
 /// of the block, the argument [Tokens](crate::TagTokenIter) passed to
 /// the block, a [`TagBlock`] inside the block and
@@ -67,3 +69,84 @@ where
         Box::new(filter)
     }
 }
+
+/// A [`ParseBlock`] implemented by a closure, which may capture application state.
+#[derive(Clone)]
+pub struct FnParseBlock<F> {
+    start_tag: String,
+    end_tag: String,
+    description: String,
+    parse: F,
+}
+
+impl<F> FnParseBlock<F>
+where
+    F: Fn(TagTokenIter<'_>, TagBlock<'_, '_>, &Language) -> Result<Box<dyn Renderable>>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    /// Create a block opened by `start_tag`, closed by `end<start_tag>` and parsed by `parse`.
+    pub fn new(start_tag: impl Into<String>, description: impl Into<String>, parse: F) -> Self {
+        let start_tag = start_tag.into();
+        let end_tag = format!("end{}", start_tag);
+        Self {
+            start_tag,
+            end_tag,
+            description: description.into(),
+            parse,
+        }
+    }
+
+    /// Close the block with `end_tag` rather than `end<start_tag>`.
+    pub fn end_tag(mut self, end_tag: impl Into<String>) -> Self {
+        self.end_tag = end_tag.into();
+        self
+    }
+}
+
+impl<F> BlockReflection for FnParseBlock<F> {
+    fn start_tag(&self) -> &str {
+        &self.start_tag
+    }
+
+    fn end_tag(&self) -> &str {
+        &self.end_tag
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl<F> ParseBlock for FnParseBlock<F>
+where
+    F: Fn(TagTokenIter<'_>, TagBlock<'_, '_>, &Language) -> Result<Box<dyn Renderable>>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    fn parse(
+        &self,
+        arguments: TagTokenIter,
+        block: TagBlock,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        (self.parse)(arguments, block, options)
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+impl<F> std::fmt::Debug for FnParseBlock<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnParseBlock")
+            .field("start_tag", &self.start_tag)
+            .field("end_tag", &self.end_tag)
+            .finish()
+    }
+}
@@ -0,0 +1,12 @@
+use std::cmp;
+use std::fmt;
+
+use crate::model::ValueView;
+
+/// Orders values for filters like `sort`, e.g. `{{ versions | sort: comparator: "semver" }}`.
+///
+/// Register one by name with [`Language::comparators`][crate::Language].
+pub trait Comparator: fmt::Debug + Send + Sync {
+    /// Compare two non-nil elements, or the properties being sorted by.
+    fn compare(&self, a: &dyn ValueView, b: &dyn ValueView) -> cmp::Ordering;
+}
@@ -1,7 +1,10 @@
 use std::fmt;
 use std::io::Write;
+use std::sync::Arc;
 
+use super::arithmetic::{self, Operator};
 use super::Filter;
+use super::ValueFormatter;
 use crate::error::{Result, ResultLiquidExt, ResultLiquidReplaceExt};
 use crate::model::{ValueCow, ValueView};
 use crate::runtime::Expression;
@@ -12,20 +15,76 @@ use crate::runtime::Runtime;
 #[derive(Debug)]
 pub struct FilterChain {
     entry: Expression,
+    operations: Vec<(Operator, Expression)>,
     filters: Vec<Box<dyn Filter>>,
+    formatter: Option<Arc<dyn ValueFormatter>>,
 }
 
 impl FilterChain {
     /// Create a new expression.
     pub fn new(entry: Expression, filters: Vec<Box<dyn Filter>>) -> Self {
-        Self { entry, filters }
+        Self {
+            entry,
+            operations: Vec::new(),
+            filters,
+            formatter: None,
+        }
+    }
+
+    /// Apply arithmetic to the entry, like `price * quantity`, before the filters.
+    pub fn with_operations(mut self, operations: Vec<(Operator, Expression)>) -> Self {
+        self.operations = operations;
+        self
+    }
+
+    /// Render the result with `formatter`, when set, rather than as usual.
+    pub fn with_formatter(mut self, formatter: Option<Arc<dyn ValueFormatter>>) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
+    /// The expression the filters are applied to.
+    pub fn entry(&self) -> &Expression {
+        &self.entry
     }
 
     /// Process `Value` expression within `runtime`'s stack.
     pub fn evaluate<'s>(&'s self, runtime: &'s dyn Runtime) -> Result<ValueCow<'s>> {
         // take either the provided value or the value from the provided variable
         let mut entry = self.entry.evaluate(runtime)?;
+        if !self.operations.is_empty() {
+            let operands: Vec<_> = self
+                .operations
+                .iter()
+                .map(|(_, operand)| operand.evaluate(runtime))
+                .collect::<Result<_>>()?;
+            let operations = self
+                .operations
+                .iter()
+                .zip(operands.iter())
+                .map(|((op, _), operand)| (*op, operand.as_view()));
+            entry = ValueCow::Owned(
+                arithmetic::evaluate(entry.as_view(), operations)
+                    .trace_with(|| self.arithmetic().into())?,
+            );
+        }
+        self.filter(entry, runtime)
+    }
 
+    fn arithmetic(&self) -> String {
+        let mut arithmetic = self.entry.to_string();
+        for (op, operand) in &self.operations {
+            arithmetic.push_str(&format!(" {} {}", op, operand));
+        }
+        arithmetic
+    }
+
+    /// Apply the filters to `entry`, in place of the chain's own entry expression.
+    pub fn filter<'s>(
+        &'s self,
+        mut entry: ValueCow<'s>,
+        runtime: &'s dyn Runtime,
+    ) -> Result<ValueCow<'s>> {
         // apply all specified filters
         for filter in &self.filters {
             entry = ValueCow::Owned(
@@ -45,10 +104,13 @@ impl FilterChain {
 
 impl fmt::Display for FilterChain {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.filters.is_empty() {
+            return write!(f, "{}", self.arithmetic());
+        }
         write!(
             f,
             "{} | {}",
-            self.entry,
+            self.arithmetic(),
             itertools::join(&self.filters, " | ")
         )
     }
@@ -57,7 +119,29 @@ impl fmt::Display for FilterChain {
 impl Renderable for FilterChain {
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
         let entry = self.evaluate(runtime)?;
-        write!(writer, "{}", entry.render()).replace("Failed to render")?;
+        match self.formatter {
+            Some(ref formatter) => render_formatted(writer, entry.as_view(), formatter.as_ref())?,
+            None => write!(writer, "{}", entry.render()).replace("Failed to render")?,
+        }
         Ok(())
     }
 }
+
+fn render_formatted(
+    writer: &mut dyn Write,
+    value: &dyn ValueView,
+    formatter: &dyn ValueFormatter,
+) -> Result<()> {
+    if let Some(formatted) = formatter.format(value) {
+        writer
+            .write_all(formatted.as_bytes())
+            .replace("Failed to render")?;
+    } else if let Some(array) = value.as_array() {
+        for element in array.values() {
+            render_formatted(writer, element, formatter)?;
+        }
+    } else {
+        write!(writer, "{}", value.render()).replace("Failed to render")?;
+    }
+    Ok(())
+}
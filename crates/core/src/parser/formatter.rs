@@ -0,0 +1,12 @@
+use std::fmt;
+
+use crate::model::ValueView;
+
+/// Renders values output by `{{ }}`, like dates in ISO-8601 or floats with fixed precision.
+///
+/// Configure one with [`Language::formatter`][crate::Language].  Array elements are formatted
+/// one at a time.
+pub trait ValueFormatter: fmt::Debug + Send + Sync {
+    /// Render `value`, or `None` to render it as usual.
+    fn format(&self, value: &dyn ValueView) -> Option<String>;
+}
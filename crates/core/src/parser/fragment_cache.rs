@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+use std::sync::Mutex;
+
+/// Stores the output of blocks like `{% cache %}`.
+///
+/// Configure one with [`Language::fragment_cache`][crate::Language].  Keys are shared by every
+/// template parsed with that language.
+pub trait FragmentCache: fmt::Debug + Send + Sync {
+    /// Previously rendered output for `key`.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Store the rendered output for `key`.
+    fn insert(&self, key: &str, fragment: String);
+}
+
+/// An in-memory [`FragmentCache`], evicting the least recently used fragment once `capacity` is
+/// reached.
+#[derive(Debug)]
+pub struct LruFragmentCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+/// Fragments linked from the most to the least recently used, so lookups and evictions don't
+/// scan every fragment.
+#[derive(Debug, Default)]
+struct LruState {
+    index: HashMap<String, usize>,
+    entries: Vec<LruEntry>,
+    newest: Option<usize>,
+    oldest: Option<usize>,
+}
+
+#[derive(Debug)]
+struct LruEntry {
+    key: String,
+    fragment: String,
+    newer: Option<usize>,
+    older: Option<usize>,
+}
+
+impl LruState {
+    fn unlink(&mut self, i: usize) {
+        let LruEntry { newer, older, .. } = self.entries[i];
+        match newer {
+            Some(newer) => self.entries[newer].older = older,
+            None => self.newest = older,
+        }
+        match older {
+            Some(older) => self.entries[older].newer = newer,
+            None => self.oldest = newer,
+        }
+    }
+
+    fn push_newest(&mut self, i: usize) {
+        self.entries[i].newer = None;
+        self.entries[i].older = self.newest;
+        if let Some(newest) = self.newest {
+            self.entries[newest].newer = Some(i);
+        }
+        self.newest = Some(i);
+        if self.oldest.is_none() {
+            self.oldest = Some(i);
+        }
+    }
+}
+
+impl LruFragmentCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Default::default(),
+        }
+    }
+
+    /// The number of cached fragments.
+    pub fn len(&self) -> usize {
+        self.state.lock().expect("not poisoned").index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl FragmentCache for LruFragmentCache {
+    fn get(&self, key: &str) -> Option<String> {
+        let mut state = self.state.lock().expect("not poisoned");
+        let i = *state.index.get(key)?;
+        state.unlink(i);
+        state.push_newest(i);
+        Some(state.entries[i].fragment.clone())
+    }
+
+    fn insert(&self, key: &str, fragment: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock().expect("not poisoned");
+        let i = if let Some(&i) = state.index.get(key) {
+            state.entries[i].fragment = fragment;
+            state.unlink(i);
+            i
+        } else if state.entries.len() < self.capacity {
+            let i = state.entries.len();
+            state.entries.push(LruEntry {
+                key: key.to_owned(),
+                fragment,
+                newer: None,
+                older: None,
+            });
+            state.index.insert(key.to_owned(), i);
+            i
+        } else {
+            let i = state.oldest.expect("full so not empty");
+            state.unlink(i);
+            let evicted = mem::replace(&mut state.entries[i].key, key.to_owned());
+            state.entries[i].fragment = fragment;
+            state.index.remove(&evicted);
+            state.index.insert(key.to_owned(), i);
+            i
+        };
+        state.push_newest(i);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lru_eviction() {
+        let cache = LruFragmentCache::new(2);
+        cache.insert("a", "1".to_owned());
+        cache.insert("b", "2".to_owned());
+        assert_eq!(cache.get("a"), Some("1".to_owned()));
+
+        cache.insert("c", "3".to_owned());
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some("1".to_owned()));
+        assert_eq!(cache.get("c"), Some("3".to_owned()));
+
+        // Replacing a fragment refreshes it, leaving `a` the oldest.
+        cache.insert("c", "4".to_owned());
+        cache.insert("d", "5".to_owned());
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("c"), Some("4".to_owned()));
+        assert_eq!(cache.get("d"), Some("5".to_owned()));
+        assert_eq!(cache.len(), 2);
+
+        let cache = LruFragmentCache::new(1);
+        cache.insert("a", "1".to_owned());
+        cache.insert("b", "2".to_owned());
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some("2".to_owned()));
+
+        let cache = LruFragmentCache::new(0);
+        cache.insert("a", "1".to_owned());
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,11 @@
+use std::fmt;
+
+use crate::error::Result;
+
+/// Renders code for blocks like `{% highlight %}`.
+///
+/// Configure one with [`Language::highlighter`][crate::Language].
+pub trait SyntaxHighlighter: fmt::Debug + Send + Sync {
+    /// Render `code`, written in `language`, as HTML.
+    fn highlight(&self, code: &str, language: &str) -> Result<String>;
+}
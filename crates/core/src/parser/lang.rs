@@ -1,7 +1,14 @@
+use std::sync::Arc;
+
+use super::Comparator;
+use super::FragmentCache;
+use super::MarkdownRenderer;
 use super::ParseBlock;
 use super::ParseFilter;
 use super::ParseTag;
 use super::PluginRegistry;
+use super::SyntaxHighlighter;
+use super::ValueFormatter;
 
 #[derive(Clone, Default)]
 #[non_exhaustive]
@@ -9,6 +16,19 @@ pub struct Language {
     pub blocks: PluginRegistry<Box<dyn ParseBlock>>,
     pub tags: PluginRegistry<Box<dyn ParseTag>>,
     pub filters: PluginRegistry<Box<dyn ParseFilter>>,
+    /// Renders the code of blocks like `{% highlight %}`, when set.
+    pub highlighter: Option<Arc<dyn SyntaxHighlighter>>,
+    /// Renders the Markdown of filters like `markdownify`, when set.
+    pub markdown: Option<Arc<dyn MarkdownRenderer>>,
+    /// Orders values for filters like `sort`, by the name given to their `comparator` argument.
+    pub comparators: PluginRegistry<Arc<dyn Comparator>>,
+    /// Errors on looking up members of nil, like `city` in `{{ user.address.city }}` when
+    /// `address` is nil, rather than evaluating to nil.
+    pub strict_variables: bool,
+    /// Renders the values output by `{{ }}`, when set.
+    pub formatter: Option<Arc<dyn ValueFormatter>>,
+    /// Stores the output of blocks like `{% cache %}`, when set.
+    pub fragment_cache: Option<Arc<dyn FragmentCache>>,
 }
 
 impl Language {
@@ -0,0 +1,11 @@
+use std::fmt;
+
+use crate::error::Result;
+
+/// Renders Markdown for filters like `markdownify`.
+///
+/// Configure one with [`Language::markdown`][crate::Language].
+pub trait MarkdownRenderer: fmt::Debug + Send + Sync {
+    /// Render `markdown` as HTML.
+    fn render(&self, markdown: &str) -> Result<String>;
+}
@@ -1,16 +1,28 @@
+mod arithmetic;
 mod block;
+mod comparator;
 mod filter;
 mod filter_chain;
+mod formatter;
+mod fragment_cache;
+mod highlight;
 mod lang;
+mod markdown;
 mod parser;
 mod registry;
 mod tag;
 mod text;
 
+pub use arithmetic::Operator;
 pub use block::*;
+pub use comparator::*;
 pub use filter::*;
 pub use filter_chain::*;
+pub use formatter::*;
+pub use fragment_cache::*;
+pub use highlight::*;
 pub use lang::*;
+pub use markdown::*;
 pub use parser::*;
 pub use registry::*;
 pub use tag::*;
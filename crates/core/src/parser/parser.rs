@@ -7,11 +7,12 @@ use crate::error::{Error, Result, ResultLiquidExt};
 use crate::model::Value;
 use crate::runtime::Expression;
 use crate::runtime::Renderable;
+use crate::runtime::Template;
 use crate::runtime::Variable;
 
 use super::Language;
 use super::Text;
-use super::{Filter, FilterArguments, FilterChain};
+use super::{Filter, FilterArguments, FilterChain, Operator};
 
 use pest::Parser;
 
@@ -78,6 +79,78 @@ pub fn parse(text: &str, options: &Language) -> Result<Vec<Box<dyn Renderable>>>
     Ok(renderables)
 }
 
+/// Parses an expression, like the `post.date | date: '%Y'` of `{{ post.date | date: '%Y' }}`.
+///
+/// For filters and tags that compile expressions given to them while rendering.
+pub fn parse_expression(text: &str, options: &Language) -> Result<FilterChain> {
+    let chain = LiquidParser::parse(Rule::LiquidExpression, text)
+        .map_err(convert_pest_error)?
+        .next()
+        .expect("Unwrapping LiquidExpression to access the expression.")
+        .into_inner()
+        .next()
+        .expect("An expression always has a filter chain.");
+    parse_filter_chain(chain, options)
+}
+
+/// Splits the arguments of a tag, like the `item.price > 100` of `{% if item.price > 100 %}`,
+/// into tokens.
+///
+/// For filters that compile conditions given to them while rendering.
+pub fn parse_tag_tokens(text: &str) -> Result<TagTokenIter<'_>> {
+    let tokens = LiquidParser::parse(Rule::LiquidTagTokens, text)
+        .map_err(convert_pest_error)?
+        .next()
+        .expect("Unwrapping LiquidTagTokens to access the tokens.");
+    let position = tokens.as_span().start_pos();
+    let tokens = tokens
+        .into_inner()
+        .filter(|token| token.as_rule() != Rule::EOI);
+    Ok(TagTokenIter {
+        iter: Box::new(tokens.map(TagToken::from)),
+        position,
+    })
+}
+
+/// Parses the body of a `{% liquid %}` tag, a tag on each line without delimiters, into a
+/// number of Renderable items.
+///
+/// The grammar splits the body into lines, given to the tag as its `tokens`.
+pub fn parse_liquid_lines(
+    tokens: TagTokenIter<'_>,
+    options: &Language,
+) -> Result<Vec<Box<dyn Renderable>>> {
+    let mut lines = Vec::new();
+    for token in tokens {
+        if token.token.as_rule() != Rule::LiquidLine {
+            return Err(token.raise_custom_error("Expected a tag on each line."));
+        }
+        lines.push(token.token);
+    }
+
+    let mut lines = lines.into_iter();
+    let mut renderables = Vec::new();
+    while let Some(line) = lines.next() {
+        renderables.push(BlockElement::parse_pair(line.into(), &mut lines, options)?);
+    }
+    Ok(renderables)
+}
+
+/// Parses a variable path, like the `site.posts[0]["title"]` of `{{ site.posts[0]["title"] }}`.
+///
+/// Indexes may be identifiers after a `.`, or any value in brackets, including quoted keys and
+/// other variables.
+pub fn parse_variable_path(text: &str) -> Result<Variable> {
+    let variable = LiquidParser::parse(Rule::LiquidVariable, text)
+        .map_err(convert_pest_error)?
+        .next()
+        .expect("Unwrapping LiquidVariable to access the variable.")
+        .into_inner()
+        .next()
+        .expect("A lone variable always has a variable.");
+    Ok(parse_variable(variable))
+}
+
 /// Parses a `Scalar` from a `Pair` with a literal value.
 /// This `Pair` must be `Rule::Literal`.
 fn parse_literal(literal: Pair) -> Value {
@@ -138,14 +211,17 @@ fn parse_variable(variable: Pair) -> Variable {
         .to_owned();
     let mut variable = Variable::with_literal(first_identifier);
 
-    let indexes = indexes.map(|index| match index.as_rule() {
+    variable.extend(indexes.map(parse_index));
+    variable
+}
+
+/// Parses an index, like the `.b` or `[0]` of `a.b[0]`.
+fn parse_index(index: Pair) -> Expression {
+    match index.as_rule() {
         Rule::Identifier => Expression::with_literal(index.as_str().to_owned()),
         Rule::Value => parse_value(index),
         _ => unreachable!(),
-    });
-
-    variable.extend(indexes);
-    variable
+    }
 }
 
 /// Parses an `Expression` from a `Pair` with a value.
@@ -162,12 +238,61 @@ fn parse_value(value: Pair) -> Expression {
     let value = value.into_inner().next().expect("Get inside the value.");
 
     match value.as_rule() {
+        Rule::IndexedLiteral => {
+            let mut indexes = value.into_inner();
+            let base = indexes
+                .next()
+                .expect("An indexed literal starts with a literal.")
+                .into_inner()
+                .next()
+                .expect("Get inside the literal.");
+            let base = match base.as_rule() {
+                Rule::Literal => Expression::Literal(parse_literal(base)),
+                Rule::Range => parse_range(base),
+                Rule::ArrayLiteral => parse_array(base),
+                Rule::HashLiteral => parse_hash(base),
+                _ => unreachable!(),
+            };
+            Expression::Indexed(Box::new(base), indexes.map(parse_index).collect())
+        }
         Rule::Literal => Expression::Literal(parse_literal(value)),
         Rule::Variable => Expression::Variable(parse_variable(value)),
+        Rule::Range => parse_range(value),
+        Rule::ArrayLiteral => parse_array(value),
+        Rule::HashLiteral => parse_hash(value),
         _ => unreachable!(),
     }
 }
 
+fn parse_range(range: Pair) -> Expression {
+    let mut range = range.into_inner();
+    Expression::Range(
+        Box::new(parse_value(range.next().expect("start"))),
+        Box::new(parse_value(range.next().expect("end"))),
+    )
+}
+
+fn parse_array(array: Pair) -> Expression {
+    Expression::Array(array.into_inner().map(parse_value).collect())
+}
+
+fn parse_hash(hash: Pair) -> Expression {
+    Expression::Object(
+        hash.into_inner()
+            .map(|entry| {
+                let mut entry = entry.into_inner();
+                let key = entry
+                    .next()
+                    .expect("A hash entry starts with a key.")
+                    .as_str();
+                let key = key[1..key.len() - 1].to_owned().into();
+                let value = parse_value(entry.next().expect("A hash entry ends with a value."));
+                (key, value)
+            })
+            .collect(),
+    )
+}
+
 /// Parses a `FilterCall` from a `Pair` with a filter.
 /// This `Pair` must be `Rule::Filter`.
 fn parse_filter(filter: Pair, options: &Language) -> Result<Box<dyn Filter>> {
@@ -236,10 +361,23 @@ fn parse_filter_chain(chain: Pair, options: &Language) -> Result<FilterChain> {
             .next()
             .expect("A filterchain always has starts by a value."),
     );
+    let mut chain = chain.peekable();
+    let mut operations = Vec::new();
+    while let Some(operation) = chain.next_if(|p| p.as_rule() == Rule::Operation) {
+        let mut operation = operation.into_inner();
+        let op = operation
+            .next()
+            .expect("An operation starts with an operator.");
+        let op = Operator::from_symbol(op.as_str()).expect("Grammar only accepts operators.");
+        let operand = parse_value(operation.next().expect("An operation ends with a value."));
+        operations.push((op, operand));
+    }
     let filters: Result<Vec<_>> = chain.map(|f| parse_filter(f, options)).collect();
     let filters = filters?;
 
-    let filters = FilterChain::new(entry, filters);
+    let filters = FilterChain::new(entry, filters)
+        .with_operations(operations)
+        .with_formatter(options.formatter.clone());
     Ok(filters)
 }
 
@@ -275,7 +413,17 @@ impl<'a, 'b> TagBlock<'a, 'b> {
             return Ok(None);
         }
 
-        let element = self.iter.next().expect("File shouldn't end before EOI.");
+        // The lines of `{% liquid %}` end without an EOI.
+        let element = match self.iter.next() {
+            Some(element) => element,
+            None => {
+                return Error::with_msg(format!(
+                    "Unclosed block. {{% {} %}} tag expected.",
+                    self.end_tag
+                ))
+                .into_err();
+            }
+        };
 
         if element.as_rule() == Rule::EOI {
             return error_from_pair(
@@ -286,7 +434,7 @@ impl<'a, 'b> TagBlock<'a, 'b> {
         }
 
         // Tags are treated separately so as to check for a possible `{% endtag %}`
-        if element.as_rule() == Rule::Tag {
+        if matches!(element.as_rule(), Rule::Tag | Rule::LiquidLine) {
             let as_str = element.as_str();
             let mut tag = element
                 .into_inner()
@@ -361,7 +509,7 @@ impl<'a, 'b> TagBlock<'a, 'b> {
             }
 
             // Tags are potentially `{% endtag %}`
-            if element.as_rule() == Rule::Tag {
+            if matches!(element.as_rule(), Rule::Tag | Rule::LiquidLine) {
                 let mut tag = element
                     .into_inner()
                     .next()
@@ -396,7 +544,12 @@ impl<'a, 'b> TagBlock<'a, 'b> {
             end_pos = Some(element_as_span.end_pos());
         }
 
-        panic!("Function must eventually find either a Rule::EOI or a closing tag.")
+        // Only the lines of `{% liquid %}` end without an EOI.
+        Error::with_msg(format!(
+            "Unclosed block. {{% {} %}} tag expected.",
+            self.end_tag
+        ))
+        .into_err()
     }
 
     /// A convenient method that parses every element remaining in the block.
@@ -418,6 +571,40 @@ impl<'a, 'b> TagBlock<'a, 'b> {
         }
     }
 
+    /// Parses every element remaining in the block, splitting them into [`Branch`]es
+    /// wherever one of the `branches` tags (like `else`) appears.
+    ///
+    /// The first branch holds the elements before any branch tag and has no tag of its own, so
+    /// there is always at least one.
+    pub fn parse_branches(
+        &mut self,
+        branches: &[&str],
+        options: &Language,
+    ) -> Result<Vec<Branch<'a>>> {
+        let mut parsed = vec![Branch {
+            tag: None,
+            elements: Vec::new(),
+        }];
+        while let Some(element) = self.next()? {
+            let element = match element {
+                BlockElement::Tag(tag) if branches.contains(&tag.name()) => {
+                    parsed.push(Branch {
+                        tag: Some(tag),
+                        elements: Vec::new(),
+                    });
+                    continue;
+                }
+                element => element.parse(self, options)?,
+            };
+            parsed
+                .last_mut()
+                .expect("There is always a leading branch")
+                .elements
+                .push(element);
+        }
+        Ok(parsed)
+    }
+
     /// Checks whether the block was fully parsed its elements.
     ///
     /// This must be added at the end of every block right before returning, so as
@@ -470,10 +657,40 @@ pub struct Tag<'a> {
     as_str: &'a str,
 }
 
+/// A section of a block, as split by [`TagBlock::parse_branches`].
+pub struct Branch<'a> {
+    tag: Option<Tag<'a>>,
+    elements: Vec<Box<dyn Renderable>>,
+}
+
+impl<'a> Branch<'a> {
+    /// Returns the name of the tag that started this branch, or `None` for the
+    /// elements leading the block.
+    pub fn name(&self) -> Option<&str> {
+        self.tag.as_ref().map(|tag| tag.name())
+    }
+
+    /// Returns the tag that started this branch, or `None` for the elements
+    /// leading the block.
+    pub fn tag(&mut self) -> Option<&mut Tag<'a>> {
+        self.tag.as_mut()
+    }
+
+    /// Returns the parsed elements of this branch.
+    pub fn elements(&self) -> &[Box<dyn Renderable>] {
+        &self.elements
+    }
+
+    /// Consumes this structure to obtain ownership over its tag and elements.
+    pub fn into_parts(self) -> (Option<Tag<'a>>, Template) {
+        (self.tag, Template::new(self.elements))
+    }
+}
+
 impl<'a> From<Pair<'a>> for Tag<'a> {
     fn from(element: Pair<'a>) -> Self {
-        if element.as_rule() != Rule::Tag {
-            panic!("Only rule Tag can be converted to Tag.");
+        if !matches!(element.as_rule(), Rule::Tag | Rule::LiquidLine) {
+            panic!("Only rules Tag | LiquidLine can be converted to Tag.");
         }
         let as_str = element.as_str();
         let mut tag = element
@@ -691,7 +908,7 @@ impl<'a> From<Pair<'a>> for BlockElement<'a> {
     fn from(element: Pair<'a>) -> Self {
         match element.as_rule() {
             Rule::Raw => BlockElement::Raw(element.into()),
-            Rule::Tag => BlockElement::Tag(element.into()),
+            Rule::Tag | Rule::LiquidLine => BlockElement::Tag(element.into()),
             Rule::Expression => BlockElement::Expression(element.into()),
             Rule::InvalidLiquid => BlockElement::Invalid(element.into()),
             _ => panic!(
@@ -1008,14 +1225,20 @@ impl<'a> TagToken<'a> {
     ///
     /// The range is returned as a pair `(Expression, Expression)`.
     pub fn expect_range(mut self) -> TryMatchToken<'a, (Expression, Expression)> {
-        let token = self.token.clone();
-
-        if token.as_rule() != Rule::Range {
-            self.expected.push(Rule::Range);
-            return TryMatchToken::Fails(self);
-        }
+        let range = self
+            .unwrap_value()
+            .ok()
+            .and_then(|value| value.into_inner().next())
+            .filter(|value| value.as_rule() == Rule::Range);
+        let range = match range {
+            Some(range) => range,
+            None => {
+                self.expected.push(Rule::Range);
+                return TryMatchToken::Fails(self);
+            }
+        };
 
-        let mut range = token.into_inner();
+        let mut range = range.into_inner();
         TryMatchToken::Matches((
             parse_value(range.next().expect("start")),
             parse_value(range.next().expect("end")),
@@ -1041,6 +1264,7 @@ impl<'a> TagToken<'a> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::parser::{FnParseBlock, FnParseTag};
     use crate::runtime::{Runtime, RuntimeBuilder, Template};
 
     #[test]
@@ -1138,6 +1362,202 @@ mod test {
         expected.extend(indexes);
 
         assert_eq!(parse_variable(variable), expected);
+        assert_eq!(
+            parse_variable_path("foo[0].bar['baz'][foo.bar]").unwrap(),
+            expected
+        );
+        for text in ["", "foo.", "foo bar", "[0]", "{{ foo }}", "foo | size"] {
+            assert!(parse_variable_path(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_liquid_tag_lines() {
+        let mut tag =
+            Tag::new("{% liquid\n  assign x = 'a' | append: b\n  # c\n\n  echo x\n-%}").unwrap();
+        assert_eq!(tag.name(), "liquid");
+        let lines: Vec<_> = tag.tokens().map(|line| line.as_str().to_owned()).collect();
+        assert_eq!(lines, ["assign x = 'a' | append: b", "echo x"]);
+
+        let mut tag = Tag::new("{% liquid %}").unwrap();
+        assert_eq!(tag.name(), "liquid");
+        assert!(tag.tokens().next().is_none());
+
+        // Tokens stop at the end of their line
+        let tag = Tag::new("{% liquid\n  if a\n  endif\n%}").unwrap();
+        let mut lines = tag.into_tokens().map(|line| {
+            let tag: Tag<'_> = line.token.into();
+            (tag.name().to_owned(), tag.into_tokens().count())
+        });
+        assert_eq!(lines.next(), Some(("if".to_owned(), 1)));
+        assert_eq!(lines.next(), Some(("endif".to_owned(), 0)));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_raw_tag_body() {
+        let mut tag = Tag::new("{%# a comment %}").unwrap();
+        assert_eq!(tag.name(), "#");
+        assert_eq!(tag.tokens().next().unwrap().as_str(), "a comment");
+
+        let mut tag = Tag::new("{%- # -%}").unwrap();
+        assert_eq!(tag.name(), "#");
+        assert!(tag.tokens().next().is_none());
+
+        // Only the `liquid` tag itself splits its body into lines
+        let mut tag = Tag::new("{% liquidate x %}").unwrap();
+        assert_eq!(tag.name(), "liquidate");
+        assert_eq!(tag.tokens().next().unwrap().as_str(), "x");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let options = Language::default();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("exp".into(), Value::scalar(5));
+        runtime.set_global("exp-1".into(), Value::scalar("hyphenated"));
+
+        let text = "{{ exp + 2 * 3 }} {{ exp-1 }} {{ exp - 1 }} {{ exp*2-1 }} {{ 7 % exp }} {{ exp / 2.0 }}";
+        let template = parse(text, &options).map(Template::new).unwrap();
+        let output = template.render(&runtime).unwrap();
+
+        assert_eq!(output, "11 hyphenated 4 9 2 2.5");
+
+        // Whitespace control isn't mistaken for an operator
+        let text = "{{ exp -}} {{- exp }}";
+        let template = parse(text, &options).map(Template::new).unwrap();
+        let output = template.render(&runtime).unwrap();
+
+        assert_eq!(output, "55");
+    }
+
+    #[test]
+    fn test_range() {
+        let options = Language::default();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("n".into(), Value::scalar(3));
+        runtime.set_global("name".into(), Value::scalar("a"));
+
+        let text = "{{ (1..n) }} {{ ( n .. 4 ) }} {{ (5..n) }} {{ (n..n) }}";
+        let template = parse(text, &options).map(Template::new).unwrap();
+        let output = template.render(&runtime).unwrap();
+
+        assert_eq!(output, "1..3 3..4 5..3 3..3");
+
+        let text = "{{ (1..name) }}";
+        let template = parse(text, &options).map(Template::new).unwrap();
+        assert!(template.render(&runtime).is_err());
+
+        let text = "{{ (1..1000000).last }}";
+        let template = parse(text, &options).map(Template::new).unwrap();
+        assert_eq!(template.render(&runtime).unwrap(), "1000000");
+
+        let text = "{{ (1..1000000000000).last }}";
+        let template = parse(text, &options).map(Template::new).unwrap();
+        assert!(template.render(&runtime).is_err());
+    }
+
+    #[test]
+    fn test_collection_literals() {
+        let options = Language::default();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("n".into(), Value::scalar(3));
+        runtime.set_global("name".into(), Value::scalar("a"));
+
+        let text = r#"{{ [1, n, "b"] }} {{ {"size": n, 'name': name} }} {{ [] }}{{ {} }}"#;
+        let template = parse(text, &options).map(Template::new).unwrap();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "13b size3namea ");
+
+        let expression =
+            parse_expression(r#"[ 1, [n, (1..2)], {"a": {"b": name}}, ]"#, &options).unwrap();
+        assert_eq!(
+            expression.evaluate(&runtime).unwrap(),
+            crate::value!([1, [3, [1, 2]], {"a": {"b": "a"}}])
+        );
+        assert_eq!(
+            expression.entry().to_string(),
+            r#"[1, [n, (1..2)], {"a": {"b": name}}]"#
+        );
+
+        let text = "{{ [1, missing] }}";
+        let template = parse(text, &options).map(Template::new).unwrap();
+        assert!(template.render(&runtime).is_err());
+
+        for text in [
+            "[1, 2",
+            "[1 2]",
+            "[,]",
+            r#"{"a" 1}"#,
+            "{a: 1}",
+            r#"{"a": }"#,
+        ] {
+            assert!(parse_expression(text, &options).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_indexed_literals() {
+        let options = Language::default();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("i".into(), Value::scalar(1));
+
+        let text = r#"{{ "abc".size }} {{ "abc".first }}{{ 'abc'.last }} {{ [1, 2, 3].last }} {{ (1..5).first }} {{ [4, 5][i] }} {{ {"a": [7]}.a.first }}"#;
+        let template = parse(text, &options).map(Template::new).unwrap();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "3 ac 3 1 5 7");
+
+        let expression = parse_expression("(1..5).last", &options).unwrap();
+        assert_eq!(expression.evaluate(&runtime).unwrap(), Value::scalar(5));
+        assert_eq!(expression.entry().to_string(), r#"(1..5)["last"]"#);
+
+        let text = r#"{{ "abc".missing }}"#;
+        let template = parse(text, &options).map(Template::new).unwrap();
+        assert!(template.render(&runtime).is_err());
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let options = Language::default();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("n".into(), Value::scalar(3));
+
+        let expression = parse_expression(" n * 2 ", &options).unwrap();
+        assert_eq!(expression.evaluate(&runtime).unwrap(), Value::scalar(6));
+
+        for text in ["", "n }}", "{{ n }}", "n n", "n | unknown"] {
+            assert!(parse_expression(text, &options).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_tag_tokens() {
+        let tokens: Vec<_> = parse_tag_tokens(" item.price >= 100 and item.tags contains 'sale' ")
+            .unwrap()
+            .map(|token| token.as_str().to_owned())
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                "item.price",
+                ">=",
+                "100",
+                "and",
+                "item.tags",
+                "contains",
+                "'sale'"
+            ]
+        );
+        assert_eq!(parse_tag_tokens("").unwrap().count(), 0);
+
+        for text in ["n %}{% if n", "n }}", "n | "] {
+            assert!(parse_tag_tokens(text).is_err(), "{}", text);
+        }
     }
 
     #[test]
@@ -1172,6 +1592,35 @@ mod test {
         assert_eq!(output, "5");
     }
 
+    #[test]
+    fn test_parse_branches() {
+        let options = Language::default();
+        let text = "{% if %}lead{% a 1 %}first{% b %}{% a 2 %}second{% endif %}";
+        let mut elements = LiquidParser::parse(Rule::LiquidFile, text)
+            .unwrap()
+            .next()
+            .unwrap()
+            .into_inner();
+        elements.next();
+
+        let mut block = TagBlock::new("if", "endif", &mut elements);
+        let mut branches = block.parse_branches(&["a", "b"], &options).unwrap();
+        block.assert_empty();
+
+        let names: Vec<_> = branches.iter().map(|b| b.name()).collect();
+        assert_eq!(names, vec![None, Some("a"), Some("b"), Some("a")]);
+        let lengths: Vec<_> = branches.iter().map(|b| b.elements().len()).collect();
+        assert_eq!(lengths, vec![1, 1, 0, 1]);
+
+        let tag = branches[3].tag().unwrap();
+        assert_eq!(tag.tokens().next().unwrap().as_str(), "2");
+
+        let runtime = RuntimeBuilder::new().build();
+        let (tag, template) = branches.remove(1).into_parts();
+        assert_eq!(tag.unwrap().name(), "a");
+        assert_eq!(template.render(&runtime).unwrap(), "first");
+    }
+
     /// Macro implementation of custom block test.
     macro_rules! test_custom_block_tags_impl {
         ($start_tag:expr, $end_tag:expr) => {{
@@ -1253,4 +1702,63 @@ mod test {
         // Test that tags not of the form `<name>`-`end<name>` also work.
         test_custom_block_tags_impl!("startcustom", "stopcustom");
     }
+
+    #[test]
+    fn test_closure_tags() {
+        use crate::error::ResultLiquidReplaceExt;
+        use crate::model::ValueView;
+        use std::io::Write;
+        use std::sync::Arc;
+
+        #[derive(Debug)]
+        struct Greet {
+            greeting: Arc<String>,
+            name: Expression,
+        }
+
+        impl Renderable for Greet {
+            fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+                let name = self.name.evaluate(runtime)?;
+                write!(writer, "{}, {}", self.greeting, name.render())
+                    .replace("Failed to render")?;
+                Ok(())
+            }
+        }
+
+        // State captured when the parser is configured
+        let greeting = Arc::new("Hello".to_owned());
+
+        let mut options = Language::default();
+        let tag = FnParseTag::new("greet", "greets someone", move |mut arguments, _options| {
+            let name = arguments
+                .expect_next("Name expected")?
+                .expect_value()
+                .into_result()?;
+            arguments.expect_nothing()?;
+            let greeting = greeting.clone();
+            Ok(Box::new(Greet { greeting, name }) as Box<dyn Renderable>)
+        });
+        options.tags.register("greet".to_owned(), tag.into());
+        let block = FnParseBlock::new(
+            "shout",
+            "upper-cases its content",
+            |mut arguments, mut block, options| {
+                arguments.expect_nothing()?;
+                let inside = block.parse_all(options)?;
+                block.assert_empty();
+                Ok(Box::new(Template::new(inside)) as Box<dyn Renderable>)
+            },
+        )
+        .end_tag("quiet");
+        options.blocks.register("shout".to_owned(), block.into());
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("user".into(), Value::scalar("Liquid"));
+
+        let text = "{% shout %}{% greet user %}!{% quiet %}";
+        let template = parse(text, &options).map(Template::new).unwrap();
+        let output = template.render(&runtime).unwrap();
+
+        assert_eq!(output, "Hello, Liquid!");
+    }
 }
@@ -18,9 +18,12 @@ pub trait TagReflection {
     }
 }
 
-/// A trait for creating custom tags. This is a simple type alias for a function.
+/// A trait for creating custom tags.
 ///
-/// This function will be called whenever the parser encounters a tag and returns
+/// Implementors may carry state (configuration, handles, etc) captured when the parser is
+/// built.  For a one-off tag, a closure can be wrapped in [`FnParseTag`] instead.
+///
+/// `parse` will be called whenever the parser encounters a tag and returns
 /// a new [Renderable] based on its parameters. The received parameters
 /// specify the name of the tag, the argument [Tokens](crate::TagTokenIter) passed to
 /// the tag and the global [`Language`].
@@ -57,3 +60,64 @@ where
         Box::new(filter)
     }
 }
+
+/// A [`ParseTag`] implemented by a closure, which may capture application state.
+#[derive(Clone)]
+pub struct FnParseTag<F> {
+    tag: String,
+    description: String,
+    parse: F,
+}
+
+impl<F> FnParseTag<F>
+where
+    F: Fn(TagTokenIter<'_>, &Language) -> Result<Box<dyn Renderable>>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    /// Create a tag named `tag`, parsed by `parse`.
+    pub fn new(tag: impl Into<String>, description: impl Into<String>, parse: F) -> Self {
+        Self {
+            tag: tag.into(),
+            description: description.into(),
+            parse,
+        }
+    }
+}
+
+impl<F> TagReflection for FnParseTag<F> {
+    fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl<F> ParseTag for FnParseTag<F>
+where
+    F: Fn(TagTokenIter<'_>, &Language) -> Result<Box<dyn Renderable>>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    fn parse(&self, arguments: TagTokenIter, options: &Language) -> Result<Box<dyn Renderable>> {
+        (self.parse)(arguments, options)
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+impl<F> std::fmt::Debug for FnParseTag<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnParseTag")
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
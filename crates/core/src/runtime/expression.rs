@@ -1,12 +1,18 @@
 use std::fmt;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::model::ArrayView;
+use crate::model::KString;
+use crate::model::Object;
+use crate::model::Range;
 use crate::model::Scalar;
+use crate::model::ScalarCow;
 use crate::model::Value;
 use crate::model::ValueCow;
 use crate::model::ValueView;
 
-use super::variable::Variable;
+use super::variable::{evaluate_index, try_evaluate_index, Variable};
+use super::LanguageRegister;
 use super::Runtime;
 
 /// An un-evaluated `Value`.
@@ -16,6 +22,14 @@ pub enum Expression {
     Variable(Variable),
     /// Evaluated.
     Literal(Value),
+    /// Un-evaluated bounds of an inclusive range of integers, like `(1..n)`.
+    Range(Box<Expression>, Box<Expression>),
+    /// Un-evaluated elements of an array literal, like `["a", b]`.
+    Array(Vec<Expression>),
+    /// Un-evaluated entries of a hash literal, like `{"size": n}`.
+    Object(Vec<(KString, Expression)>),
+    /// Un-evaluated indexes into a literal, like `"abc".size` or `(1..5).last`.
+    Indexed(Box<Expression>, Vec<Expression>),
 }
 
 impl Expression {
@@ -28,14 +42,22 @@ impl Expression {
     pub fn into_literal(self) -> Option<Value> {
         match self {
             Expression::Literal(x) => Some(x),
-            Expression::Variable(_) => None,
+            Expression::Variable(_)
+            | Expression::Range(..)
+            | Expression::Array(_)
+            | Expression::Object(_)
+            | Expression::Indexed(..) => None,
         }
     }
 
     /// Convert into a variable, if possible.
     pub fn into_variable(self) -> Option<Variable> {
         match self {
-            Expression::Literal(_) => None,
+            Expression::Literal(_)
+            | Expression::Range(..)
+            | Expression::Array(_)
+            | Expression::Object(_)
+            | Expression::Indexed(..) => None,
             Expression::Variable(x) => Some(x),
         }
     }
@@ -46,7 +68,39 @@ impl Expression {
             Expression::Literal(ref x) => Some(ValueCow::Borrowed(x)),
             Expression::Variable(ref x) => {
                 let path = x.try_evaluate(runtime)?;
-                runtime.try_get(&path)
+                runtime
+                    .try_get(&path)
+                    .or_else(|| nil_safe(&path, runtime).then_some(ValueCow::Owned(Value::Nil)))
+            }
+            Expression::Range(ref start, ref end) => {
+                let start = start.try_evaluate(runtime)?;
+                let end = end.try_evaluate(runtime)?;
+                evaluate_range(start.as_view(), end.as_view())
+                    .ok()
+                    .map(ValueCow::Owned)
+            }
+            Expression::Array(ref elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|e| e.try_evaluate(runtime).map(|v| v.into_owned()))
+                    .collect::<Option<_>>()?;
+                Some(ValueCow::Owned(Value::Array(elements)))
+            }
+            Expression::Object(ref entries) => {
+                let entries = entries
+                    .iter()
+                    .map(|(k, e)| Some((k.clone(), e.try_evaluate(runtime)?.into_owned())))
+                    .collect::<Option<Object>>()?;
+                Some(ValueCow::Owned(Value::Object(entries)))
+            }
+            Expression::Indexed(ref base, ref indexes) => {
+                let base = base.try_evaluate(runtime)?;
+                let path = indexes
+                    .iter()
+                    .map(|i| try_evaluate_index(i, runtime))
+                    .collect::<Option<Vec<_>>>()?;
+                let value = crate::model::try_find(base.as_view(), &path)?.into_owned();
+                Some(ValueCow::Owned(value))
             }
         }
     }
@@ -57,18 +111,119 @@ impl Expression {
             Expression::Literal(ref x) => ValueCow::Borrowed(x),
             Expression::Variable(ref x) => {
                 let path = x.evaluate(runtime)?;
-                runtime.get(&path)?
+                match runtime.get(&path) {
+                    Ok(value) => value,
+                    Err(_) if nil_safe(&path, runtime) => ValueCow::Owned(Value::Nil),
+                    Err(err) => return Err(err),
+                }
+            }
+            Expression::Range(ref start, ref end) => {
+                let start = start.evaluate(runtime)?;
+                let end = end.evaluate(runtime)?;
+                ValueCow::Owned(evaluate_range(start.as_view(), end.as_view())?)
+            }
+            Expression::Array(ref elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|e| e.evaluate(runtime).map(|v| v.into_owned()))
+                    .collect::<Result<_>>()?;
+                ValueCow::Owned(Value::Array(elements))
+            }
+            Expression::Object(ref entries) => {
+                let entries = entries
+                    .iter()
+                    .map(|(k, e)| Ok((k.clone(), e.evaluate(runtime)?.into_owned())))
+                    .collect::<Result<Object>>()?;
+                ValueCow::Owned(Value::Object(entries))
+            }
+            Expression::Indexed(ref base, ref indexes) => {
+                let base = base.evaluate(runtime)?;
+                let path = indexes
+                    .iter()
+                    .map(|i| evaluate_index(i, runtime))
+                    .collect::<Result<Vec<_>>>()?;
+                ValueCow::Owned(crate::model::find(base.as_view(), &path)?.into_owned())
             }
         };
         Ok(val)
     }
 }
 
+/// Whether looking up `path` failed on a member of nil, like `city` in `user.address.city` when
+/// `address` is nil, and that should evaluate to nil rather than fail.
+fn nil_safe(path: &[ScalarCow<'_>], runtime: &dyn Runtime) -> bool {
+    let strict = runtime
+        .registers()
+        .get_mut::<LanguageRegister>()
+        .language()
+        .is_some_and(|language| language.strict_variables);
+    !strict
+        && (1..path.len()).any(|end| {
+            runtime
+                .try_get(&path[..end])
+                .is_some_and(|value| value.is_nil())
+        })
+}
+
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Expression::Literal(ref x) => write!(f, "{}", x.source()),
             Expression::Variable(ref x) => write!(f, "{}", x),
+            Expression::Range(ref start, ref end) => write!(f, "({}..{})", start, end),
+            Expression::Array(ref elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if 0 < i {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            Expression::Object(ref entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if 0 < i {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, r#""{}": {}"#, key, value)?;
+                }
+                write!(f, "}}")
+            }
+            Expression::Indexed(ref base, ref indexes) => {
+                write!(f, "{}", base)?;
+                for index in indexes {
+                    write!(f, "[{}]", index)?;
+                }
+                Ok(())
+            }
         }
     }
 }
+
+/// The integers from `start` to `end`, inclusive.
+fn evaluate_range(start: &dyn ValueView, end: &dyn ValueView) -> Result<Value> {
+    let bound = |value: &dyn ValueView, name: &'static str| {
+        value
+            .as_scalar()
+            .and_then(|s| s.to_integer())
+            .ok_or_else(|| {
+                Error::with_msg(format!(
+                    "Expected whole number, found `{}`",
+                    value.type_name()
+                ))
+                .context(name, value.to_kstr().into_owned())
+            })
+    };
+    let start = bound(start, "start")?;
+    let end = bound(end, "end")?;
+    let range = Range::new(start, end);
+    if Range::MAX_SIZE < range.size() {
+        return Error::with_msg("Range is too long")
+            .context("range", range.to_string())
+            .context("maximum size", Range::MAX_SIZE.to_string())
+            .into_err();
+    }
+    Ok(Value::Range(range))
+}
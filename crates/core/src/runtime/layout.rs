@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::error::{ResultLiquidExt, ResultLiquidReplaceExt};
+use crate::model::KString;
+
+use super::InterruptRegister;
+use super::Renderable;
+use super::Runtime;
+
+/// Template inheritance state.
+///
+/// A template requests a layout (a partial to render in its place) with
+/// [`LayoutRegister::set_layout`].  While a layout is pending, the template
+/// records the content of its named blocks so the layout can use them in place
+/// of its own defaults.  Blocks recorded first take precedence, so the most
+/// derived template wins when layouts are chained.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LayoutRegister {
+    layout: Option<KString>,
+    blocks: HashMap<KString, String>,
+}
+
+impl LayoutRegister {
+    /// The layout requested by the template being rendered, if any.
+    pub fn layout(&self) -> Option<&str> {
+        self.layout.as_deref()
+    }
+
+    /// Request `name` be rendered in place of the current template.  Any
+    /// previously requested layout is replaced.
+    pub fn set_layout(&mut self, name: KString) {
+        self.layout = Some(name);
+    }
+
+    /// Fetches and clears the requested layout.
+    pub fn take_layout(&mut self) -> Option<KString> {
+        self.layout.take()
+    }
+
+    /// Content recorded for the block `name`.
+    pub fn block(&self, name: &str) -> Option<&str> {
+        self.blocks.get(name).map(|s| s.as_str())
+    }
+
+    /// Record the content of the block `name`, unless a more derived template
+    /// already has.
+    pub fn set_block(&mut self, name: KString, content: String) {
+        self.blocks.entry(name).or_insert(content);
+    }
+}
+
+/// Render `template`, replacing its output with that of any layout it
+/// requests through the [`LayoutRegister`].
+///
+/// Output is written directly to `writer`, so a layout must be requested
+/// before anything but whitespace is output.
+pub fn render_with_layouts(
+    template: &dyn Renderable,
+    writer: &mut dyn Write,
+    runtime: &dyn Runtime,
+) -> Result<()> {
+    let mut output = LayoutWriter::new(writer, runtime);
+    template.render_to(&mut output, runtime)?;
+
+    let mut seen: Vec<KString> = Vec::new();
+    loop {
+        let layout = runtime
+            .registers()
+            .get_mut::<LayoutRegister>()
+            .take_layout();
+        let layout = match layout {
+            Some(layout) => layout,
+            None => break,
+        };
+        if output.written {
+            return Err(
+                Error::with_msg("Layouts must be requested before any output")
+                    .context("layout", layout),
+            );
+        }
+        if seen.contains(&layout) {
+            return Err(Error::with_msg("Layouts form a cycle")
+                .context("layout", layout)
+                .context("layouts", itertools::join(seen.iter(), ", ")));
+        }
+
+        // A `break` outside of any loop stops the template, not its layout.
+        runtime.registers().get_mut::<InterruptRegister>().reset();
+
+        let partial = runtime
+            .partials()
+            .get(&layout)
+            .trace_with(|| format!("{{% layout \"{}\" %}}", layout).into())?;
+        output.whitespace.clear();
+        partial
+            .render_to(&mut output, runtime)
+            .trace_with(|| format!("{{% layout \"{}\" %}}", layout).into())?;
+        seen.push(layout);
+    }
+
+    output.finish().replace("Failed to render")?;
+    Ok(())
+}
+
+/// Passes output through until a layout is requested, after which the
+/// output is dropped for the layout's.
+///
+/// Whitespace is held back, so blank lines before `{% layout %}` don't end up
+/// ahead of the layout.
+struct LayoutWriter<'w> {
+    writer: &'w mut dyn Write,
+    runtime: &'w dyn Runtime,
+    whitespace: Vec<u8>,
+    written: bool,
+}
+
+impl<'w> LayoutWriter<'w> {
+    fn new(writer: &'w mut dyn Write, runtime: &'w dyn Runtime) -> Self {
+        Self {
+            writer,
+            runtime,
+            whitespace: Vec::new(),
+            written: false,
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        self.writer.write_all(&self.whitespace)
+    }
+}
+
+impl<'w> Write for LayoutWriter<'w> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let layout = self.runtime.registers().get_mut::<LayoutRegister>();
+        if layout.layout().is_some() {
+            return Ok(buf.len());
+        }
+        drop(layout);
+
+        match buf.iter().rposition(|b| !b.is_ascii_whitespace()) {
+            Some(i) => {
+                self.writer.write_all(&self.whitespace)?;
+                self.whitespace.clear();
+                self.writer.write_all(&buf[..=i])?;
+                self.whitespace.extend_from_slice(&buf[i + 1..]);
+                self.written = true;
+            }
+            None => self.whitespace.extend_from_slice(buf),
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -4,6 +4,7 @@
 #![warn(unused_extern_crates)]
 
 mod expression;
+mod layout;
 mod partials;
 mod renderable;
 mod runtime;
@@ -12,6 +13,7 @@ mod template;
 mod variable;
 
 pub use self::expression::*;
+pub use self::layout::*;
 pub use self::partials::*;
 pub use self::renderable::*;
 pub use self::runtime::*;
@@ -2,7 +2,11 @@ use std::sync;
 
 use crate::error::Error;
 use crate::error::Result;
-use crate::model::{Object, ObjectView, Scalar, ScalarCow, Value, ValueCow, ValueView};
+use crate::model::{
+    DateTime, Object, ObjectView, Scalar, ScalarCow, TimeZone, Value, ValueCow, ValueView,
+};
+
+use crate::parser::Language;
 
 use super::PartialStore;
 use super::Renderable;
@@ -80,17 +84,48 @@ impl<'r, R: Runtime + ?Sized> Runtime for &'r R {
     }
 }
 
+impl<'r> dyn Runtime + 'r {
+    /// Look up a variable path, like `site.posts[0]["title"]`, as `{{ site.posts[0]["title"] }}`
+    /// would.
+    ///
+    /// For tags and filters that take the name of a variable rather than its value.  Bracketed
+    /// indexes may be integers, quoted keys, or other variables.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use liquid_core::runtime::{Runtime, RuntimeBuilder};
+    /// use liquid_core::ValueView;
+    ///
+    /// let globals = liquid_core::object!({"site": {"posts": [{"title": "Hello"}]}});
+    /// let runtime = RuntimeBuilder::new().set_globals(&globals).build();
+    /// let runtime: &dyn Runtime = &runtime;
+    /// let title = runtime.get_path(r#"site.posts[0]["title"]"#).unwrap();
+    /// assert_eq!(title.to_kstr(), "Hello");
+    /// ```
+    pub fn get_path(&self, path: &str) -> Result<ValueCow<'_>> {
+        let variable = crate::parser::parse_variable_path(path)?;
+        let path = variable.evaluate(self)?;
+        self.get(&path)
+    }
+}
+
 /// Create processing runtime for a template.
 pub struct RuntimeBuilder<'g, 'p> {
     globals: Option<&'g dyn ObjectView>,
+    shared_globals: Option<SharedGlobals>,
     partials: Option<&'p dyn PartialStore>,
 }
 
+/// Read-only globals shared between renders.
+type SharedGlobals = sync::Arc<dyn ObjectView + Send + Sync>;
+
 impl<'c, 'g: 'c, 'p: 'c> RuntimeBuilder<'g, 'p> {
     /// Creates a new, empty rendering runtime.
     pub fn new() -> Self {
         Self {
             globals: None,
+            shared_globals: None,
             partials: None,
         }
     }
@@ -99,14 +134,25 @@ impl<'c, 'g: 'c, 'p: 'c> RuntimeBuilder<'g, 'p> {
     pub fn set_globals<'n>(self, values: &'n dyn ObjectView) -> RuntimeBuilder<'n, 'p> {
         RuntimeBuilder {
             globals: Some(values),
+            shared_globals: self.shared_globals,
             partials: self.partials,
         }
     }
 
+    /// Layer the globals over read-only globals shared between renders, like a large `site`.
+    ///
+    /// The shared globals are neither copied nor modified; globals of the same name, and
+    /// variables assigned while rendering, hide them.
+    pub fn set_shared_globals(mut self, values: SharedGlobals) -> Self {
+        self.shared_globals = Some(values);
+        self
+    }
+
     /// Initialize partial-templates available for including.
     pub fn set_partials<'n>(self, values: &'n dyn PartialStore) -> RuntimeBuilder<'g, 'n> {
         RuntimeBuilder {
             globals: self.globals,
+            shared_globals: self.shared_globals,
             partials: Some(values),
         }
     }
@@ -119,11 +165,18 @@ impl<'c, 'g: 'c, 'p: 'c> RuntimeBuilder<'g, 'p> {
             ..Default::default()
         };
         let runtime = super::IndexFrame::new(runtime);
+        let shared_globals = self.shared_globals.unwrap_or_else(null_shared_globals);
+        let runtime = super::StackFrame::new(runtime, shared_globals);
         let runtime = super::StackFrame::new(runtime, self.globals.unwrap_or(&NullObject));
         super::GlobalFrame::new(runtime)
     }
 }
 
+fn null_shared_globals() -> SharedGlobals {
+    static NULL: sync::OnceLock<SharedGlobals> = sync::OnceLock::new();
+    NULL.get_or_init(|| sync::Arc::new(NullObject)).clone()
+}
+
 #[derive(Copy, Clone, Debug)]
 struct NullObject;
 
@@ -309,9 +362,14 @@ impl Default for Registers {
 /// at a given point and unwind the `render` call stack until
 /// it reaches an enclosing `for_loop`. At that point the interrupt
 /// is cleared, and the `for_loop` carries on processing as directed.
+///
+/// An interrupt may target a loop further out (`{% break 2 %}`), in which
+/// case each loop it passes through stops and hands it on to the next.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct InterruptRegister {
     interrupt: Option<Interrupt>,
+    levels: usize,
+    loops: usize,
 }
 
 impl InterruptRegister {
@@ -322,13 +380,65 @@ impl InterruptRegister {
 
     /// Sets the interrupt state. Any previous state is obliterated.
     pub fn set(&mut self, interrupt: Interrupt) {
+        self.set_levels(interrupt, 1);
+    }
+
+    /// Sets the interrupt state, targeting the loop `levels` out from the
+    /// innermost one. Any previous state is obliterated.
+    pub fn set_levels(&mut self, interrupt: Interrupt, levels: usize) {
         self.interrupt.replace(interrupt);
+        self.levels = levels.max(1);
     }
 
     /// Fetches and clears the interrupt state.
     pub fn reset(&mut self) -> Option<Interrupt> {
+        self.levels = 0;
         self.interrupt.take()
     }
+
+    /// How many loops enclose what is being rendered, see [`LoopGuard`].
+    pub fn loops(&self) -> usize {
+        self.loops
+    }
+
+    /// Fetches the interrupt state on behalf of the innermost enclosing loop.
+    ///
+    /// The state is cleared once it reaches the loop it targets.  Until then,
+    /// it stays active for the next loop out and [`Interrupt::Break`] is
+    /// returned so the current one stops.
+    pub fn unwind(&mut self) -> Option<Interrupt> {
+        self.interrupt?;
+        if 1 < self.levels {
+            self.levels -= 1;
+            Some(Interrupt::Break)
+        } else {
+            self.reset()
+        }
+    }
+}
+
+/// A loop being rendered, so interrupts can target it.
+///
+/// The loop is counted by [`InterruptRegister::loops`] until the guard is dropped, however the
+/// loop ends.
+pub struct LoopGuard<'r> {
+    registers: &'r Registers,
+}
+
+impl<'r> LoopGuard<'r> {
+    /// Note that a loop is starting.
+    pub fn enter(runtime: &'r dyn Runtime) -> Self {
+        let registers = runtime.registers();
+        registers.get_mut::<InterruptRegister>().loops += 1;
+        Self { registers }
+    }
+}
+
+impl<'r> Drop for LoopGuard<'r> {
+    fn drop(&mut self) {
+        let mut interrupt = self.registers.get_mut::<InterruptRegister>();
+        interrupt.loops = interrupt.loops.saturating_sub(1);
+    }
 }
 
 /// Block processing interrupt state.
@@ -340,6 +450,101 @@ pub enum Interrupt {
     Break,
 }
 
+/// The moment a render happens, for things like `{{ "now" | date }}`.
+///
+/// The clock is read once, so the whole render sees the same moment, unless it is fixed in
+/// advance with [`ClockRegister::set_now`], e.g. for reproducible output.
+///
+/// Dates are shown in UTC, or their own offset, unless a default time zone is set with
+/// [`ClockRegister::set_time_zone`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClockRegister {
+    now: Option<DateTime>,
+    time_zone: Option<TimeZone>,
+}
+
+impl ClockRegister {
+    /// The current moment.
+    pub fn now(&mut self) -> DateTime {
+        *self.now.get_or_insert_with(DateTime::now)
+    }
+
+    /// Render as if it were `now`.
+    pub fn set_now(&mut self, now: DateTime) {
+        self.now = Some(now);
+    }
+
+    /// The time zone to show dates in, when not otherwise specified.
+    pub fn time_zone(&self) -> Option<&TimeZone> {
+        self.time_zone.as_ref()
+    }
+
+    /// Show dates in `zone` by default.
+    pub fn set_time_zone(&mut self, zone: Option<TimeZone>) {
+        self.time_zone = zone;
+    }
+}
+
+/// Random numbers for filters like `sample`.
+///
+/// Each render is seeded differently, unless a seed is fixed in advance with
+/// [`RandomRegister::set_seed`], e.g. for reproducible output.
+///
+/// Clones share one sequence, so partials rendered in a sandbox draw from the same numbers
+/// as the template that renders them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RandomRegister {
+    state: std::rc::Rc<std::cell::Cell<Option<u64>>>,
+}
+
+impl RandomRegister {
+    /// Produce the same numbers on every render seeded with `seed`.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.state.set(Some(seed));
+    }
+
+    /// The next random number.
+    pub fn next_u64(&mut self) -> u64 {
+        let state = self.state.get().unwrap_or_else(|| {
+            use std::hash::{BuildHasher, Hasher};
+            std::collections::hash_map::RandomState::new()
+                .build_hasher()
+                .finish()
+        });
+        // SplitMix64
+        let state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        self.state.set(Some(state));
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A random number less than `bound`, which must not be zero.
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        ((u128::from(self.next_u64()) * bound as u128) >> 64) as usize
+    }
+}
+
+/// The language the template was parsed with, for filters that compile expressions while
+/// rendering, like `group_by_exp`.
+#[derive(Clone, Default)]
+pub struct LanguageRegister {
+    language: Option<sync::Arc<Language>>,
+}
+
+impl LanguageRegister {
+    /// The language, if known.
+    pub fn language(&self) -> Option<&sync::Arc<Language>> {
+        self.language.as_ref()
+    }
+
+    /// Set the language the template was parsed with.
+    pub fn set_language(&mut self, language: sync::Arc<Language>) {
+        self.language = Some(language);
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 struct NullPartials;
 
@@ -369,6 +574,64 @@ mod test {
     use crate::model::Value;
     use crate::model::ValueViewCmp;
 
+    #[test]
+    fn get_path() {
+        let globals = crate::object!({
+            "site": {
+                "posts": [{"title": "First"}, {"title": "Last", "tags": ["a", "b"]}],
+                "odd key": 1,
+            },
+            "key": "title",
+        });
+        let rt = RuntimeBuilder::new().set_globals(&globals).build();
+        let rt: &dyn Runtime = &rt;
+
+        for (path, expected) in [
+            ("site.posts[0].title", "First"),
+            ("site.posts[-1][\"title\"]", "Last"),
+            ("site.posts[1]['tags'][1]", "b"),
+            ("site.posts[0][key]", "First"),
+            ("site.posts.size", "2"),
+            ("site.posts.last.tags.first", "a"),
+            ("site['odd key']", "1"),
+            (" key ", "title"),
+        ] {
+            assert_eq!(rt.get_path(path).unwrap().to_kstr(), expected, "{}", path);
+        }
+
+        for path in [
+            "site.missing",
+            "site.posts[5]",
+            "site.",
+            "site posts",
+            "{{ key }}",
+            "",
+        ] {
+            assert!(rt.get_path(path).is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn shared_globals() {
+        let site: SharedGlobals = sync::Arc::new(crate::object!({"title": "Site", "posts": 2}));
+        for _ in 0..2 {
+            let page = crate::object!({"title": "Page"});
+            let rt = RuntimeBuilder::new()
+                .set_globals(&page)
+                .set_shared_globals(site.clone())
+                .build();
+            let rt: &dyn Runtime = &rt;
+            assert_eq!(rt.get_path("title").unwrap().to_kstr(), "Page");
+            assert_eq!(rt.get_path("posts").unwrap().to_kstr(), "2");
+            assert!(rt.roots().contains("posts"));
+
+            rt.set_global("posts".into(), Value::scalar(3));
+            assert_eq!(rt.get_path("posts").unwrap().to_kstr(), "3");
+        }
+        assert_eq!(sync::Arc::strong_count(&site), 1);
+        assert_eq!(site.get("posts").unwrap().to_kstr(), "2");
+    }
+
     #[test]
     fn mask_variables() {
         let test_path = [Scalar::new("test")];
@@ -407,4 +670,43 @@ mod test {
             &ValueViewCmp::new(&"some value")
         );
     }
+
+    #[test]
+    fn interrupt_unwind() {
+        let mut interrupt = InterruptRegister::default();
+        assert_eq!(interrupt.unwind(), None);
+
+        interrupt.set_levels(Interrupt::Continue, 3);
+        assert_eq!(interrupt.unwind(), Some(Interrupt::Break));
+        assert!(interrupt.interrupted());
+        assert_eq!(interrupt.unwind(), Some(Interrupt::Break));
+        assert_eq!(interrupt.unwind(), Some(Interrupt::Continue));
+        assert!(!interrupt.interrupted());
+    }
+
+    #[test]
+    fn random_seeded() {
+        let mut first = RandomRegister::default();
+        first.set_seed(42);
+        let mut second = RandomRegister::default();
+        second.set_seed(42);
+        for _ in 0..10 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        for bound in 1..20 {
+            assert!(first.next_below(bound) < bound);
+        }
+    }
+
+    #[test]
+    fn random_clones_share_sequence() {
+        let mut first = RandomRegister::default();
+        first.set_seed(42);
+        let mut second = first.clone();
+        let mut expected = RandomRegister::default();
+        expected.set_seed(42);
+        assert_eq!(first.next_u64(), expected.next_u64());
+        assert_eq!(second.next_u64(), expected.next_u64());
+        assert_eq!(first.next_u64(), expected.next_u64());
+    }
 }
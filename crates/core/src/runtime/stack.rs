@@ -245,21 +245,43 @@ impl<P: super::Runtime> super::Runtime for IndexFrame<P> {
 
 /// A [`StackFrame`] where variables are not recursively searched for,
 /// However, you can still access the parent's partials.
+///
+/// Counters from `increment`/`decrement` are also isolated from the parent.
 pub struct SandboxedStackFrame<P, O> {
     parent: P,
     name: Option<crate::model::KString>,
     data: O,
+    index: std::cell::RefCell<Object>,
     registers: Registers,
 }
 
 impl<P: super::Runtime, O: ObjectView> SandboxedStackFrame<P, O> {
     /// Create a new [`SandboxedStackFrame`] from a parent and some data
     pub fn new(parent: P, data: O) -> Self {
+        let registers = Registers::default();
+        // The sandbox is still part of the same render
+        let clock = {
+            let mut clock = parent.registers().get_mut::<super::ClockRegister>();
+            clock.now();
+            clock.clone()
+        };
+        *registers.get_mut::<super::ClockRegister>() = clock;
+        let language = parent
+            .registers()
+            .get_mut::<super::LanguageRegister>()
+            .clone();
+        *registers.get_mut::<super::LanguageRegister>() = language;
+        let random = parent
+            .registers()
+            .get_mut::<super::RandomRegister>()
+            .clone();
+        *registers.get_mut::<super::RandomRegister>() = random;
         Self {
             parent,
             name: None,
             data,
-            registers: Default::default(),
+            index: Default::default(),
+            registers,
         }
     }
 
@@ -292,20 +314,21 @@ impl<P: super::Runtime, O: ObjectView> super::Runtime for SandboxedStackFrame<P,
         let key = path.first()?;
         let key = key.to_kstr();
         let data = &self.data;
-        data.get(key.as_str())
-            .and_then(|_| crate::model::try_find(data.as_value(), path))
+        if data.contains_key(key.as_str()) {
+            crate::model::try_find(data.as_value(), path)
+        } else {
+            let index = self.index.borrow();
+            crate::model::try_find(index.as_value(), path).map(|v| v.into_owned().into())
+        }
     }
 
     fn get(&self, path: &[ScalarCow<'_>]) -> Result<ValueCow<'_>> {
         let key = path.first().ok_or_else(|| {
             Error::with_msg("Unknown variable").context("requested variable", "nil")
         })?;
-        let key = key.to_kstr();
-        let data = &self.data;
-        data.get(key.as_str())
-            .and_then(|_| crate::model::try_find(data.as_value(), path))
-            .map(|v| v.into_owned().into())
-            .ok_or_else(|| Error::with_msg("Unknown variable").context("requested variable", key))
+        self.try_get(path).ok_or_else(|| {
+            Error::with_msg("Unknown variable").context("requested variable", key.to_kstr())
+        })
     }
 
     fn set_global(
@@ -317,11 +340,12 @@ impl<P: super::Runtime, O: ObjectView> super::Runtime for SandboxedStackFrame<P,
     }
 
     fn set_index(&self, name: crate::model::KString, val: Value) -> Option<Value> {
-        self.parent.set_index(name, val)
+        let mut index = self.index.borrow_mut();
+        index.insert(name, val)
     }
 
     fn get_index<'a>(&'a self, name: &str) -> Option<ValueCow<'a>> {
-        self.parent.get_index(name)
+        self.index.borrow().get(name).map(|v| v.to_value().into())
     }
 
     fn registers(&self) -> &super::Registers {
@@ -395,6 +419,20 @@ mod tests {
         assert!(stack_frame.get(&["c".into()]).is_ok());
     }
 
+    #[test]
+    fn test_opaque_stack_frame_index() {
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_index("a".into(), Value::Scalar(1i64.into()));
+        let opaque_stack_frame = SandboxedStackFrame::new(&runtime, Object::new());
+
+        // Testing that counters are not shared with the parent
+        assert!(opaque_stack_frame.get_index("a").is_none());
+        opaque_stack_frame.set_index("b".into(), Value::Scalar(2i64.into()));
+        assert!(opaque_stack_frame.get_index("b").is_some());
+        assert!(opaque_stack_frame.get(&["b".into()]).is_ok());
+        assert!(runtime.get_index("b").is_none());
+    }
+
     #[test]
     fn test_opaque_stack_frame_roots() {
         let globals = {
@@ -3,6 +3,7 @@ use std::fmt;
 use crate::error::{Error, Result};
 use crate::model::Path;
 use crate::model::Scalar;
+use crate::model::ScalarCow;
 use crate::model::{ValueCow, ValueView};
 
 use super::Expression;
@@ -35,12 +36,7 @@ impl Variable {
         let mut path = Path::with_index(self.variable.as_ref());
         path.reserve(self.indexes.len());
         for expr in &self.indexes {
-            let v = expr.try_evaluate(runtime)?;
-            let s = match v {
-                ValueCow::Owned(v) => v.into_scalar(),
-                ValueCow::Borrowed(v) => v.as_scalar(),
-            }?;
-            path.push(s);
+            path.push(try_evaluate_index(expr, runtime)?);
         }
         Some(path)
     }
@@ -50,23 +46,41 @@ impl Variable {
         let mut path = Path::with_index(self.variable.as_ref());
         path.reserve(self.indexes.len());
         for expr in &self.indexes {
-            let v = expr.evaluate(runtime)?;
-            let s = match v {
-                ValueCow::Owned(v) => v.into_scalar(),
-                ValueCow::Borrowed(v) => v.as_scalar(),
-            }
-            .ok_or_else(|| {
-                let v = expr.evaluate(runtime).expect("lookup already verified");
-                let v = v.source();
-                let msg = format!("Expected scalar, found `{}`", v);
-                Error::with_msg(msg)
-            })?;
-            path.push(s);
+            path.push(evaluate_index(expr, runtime)?);
         }
         Ok(path)
     }
 }
 
+/// Evaluate an index, like the `[i]` of `a[i]`, into a scalar.
+pub(crate) fn try_evaluate_index<'c>(
+    expr: &'c Expression,
+    runtime: &'c dyn Runtime,
+) -> Option<ScalarCow<'c>> {
+    match expr.try_evaluate(runtime)? {
+        ValueCow::Owned(v) => v.into_scalar(),
+        ValueCow::Borrowed(v) => v.as_scalar(),
+    }
+}
+
+/// Evaluate an index, like the `[i]` of `a[i]`, into a scalar.
+pub(crate) fn evaluate_index<'c>(
+    expr: &'c Expression,
+    runtime: &'c dyn Runtime,
+) -> Result<ScalarCow<'c>> {
+    let v = expr.evaluate(runtime)?;
+    match v {
+        ValueCow::Owned(v) => v.into_scalar(),
+        ValueCow::Borrowed(v) => v.as_scalar(),
+    }
+    .ok_or_else(|| {
+        let v = expr.evaluate(runtime).expect("lookup already verified");
+        let v = v.source();
+        let msg = format!("Expected scalar, found `{}`", v);
+        Error::with_msg(msg)
+    })
+}
+
 impl Extend<Scalar> for Variable {
     fn extend<T: IntoIterator<Item = Scalar>>(&mut self, iter: T) {
         let path = iter.into_iter().map(Expression::with_literal);
@@ -250,25 +250,44 @@ pub fn derive_display_filter(item: TokenStream) -> TokenStream {
     filter::display::derive(&input).into()
 }
 
-#[proc_macro_derive(CoreValueView)]
+#[proc_macro_derive(CoreValueView, attributes(object))]
 pub fn derive_core_value_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     value_view::core_derive(&input).into()
 }
 
-#[proc_macro_derive(CoreObjectView)]
+#[proc_macro_derive(CoreObjectView, attributes(object))]
 pub fn derive_core_object_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     object_view::core_derive(&input).into()
 }
 
-#[proc_macro_derive(ValueView)]
+#[proc_macro_derive(ValueView, attributes(object))]
 pub fn derive_value_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     value_view::derive(&input).into()
 }
 
-#[proc_macro_derive(ObjectView)]
+/// Implements `ObjectView` for a struct, exposing each of its fields as a key.
+///
+/// Fields accept the helper attribute `#[object(...)]`:
+/// - `rename = "..."` exposes the field under another key.
+/// - `skip` leaves the field out of the object.
+///
+/// The same attributes are honored by the `ValueView` derive.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(ObjectView, ValueView, Debug)]
+/// struct Product {
+///     #[object(rename = "title")]
+///     name: String,
+///     #[object(skip)]
+///     internal_id: u64,
+/// }
+/// ```
+#[proc_macro_derive(ObjectView, attributes(object))]
 pub fn derive_object_view(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
     object_view::derive(&input).into()
@@ -1,7 +1,10 @@
 use proc_macro2::*;
 use quote::*;
+use syn::spanned::Spanned as _;
 use syn::*;
 
+use crate::helpers::*;
+
 pub fn derive(input: &DeriveInput) -> TokenStream {
     let DeriveInput {
         ident,
@@ -17,6 +20,8 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
         Err(err) => return err.to_compile_error(),
     };
     let num_fields = fields.len();
+    let names: Vec<_> = fields.iter().map(|field| &field.name).collect();
+    let fields: Vec<_> = fields.iter().map(|field| field.ident).collect();
 
     quote! {
         impl #impl_generics ::liquid::ObjectView for #ident #ty_generics #where_clause {
@@ -31,7 +36,7 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
             fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = ::liquid::model::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
                 let mut keys = Vec::with_capacity(#num_fields);
                 #(
-                    keys.push(::liquid::model::KStringCow::from_static(stringify!(#fields)));
+                    keys.push(::liquid::model::KStringCow::from_static(#names));
                 )*
                 Box::new(keys.into_iter())
             }
@@ -48,7 +53,7 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
                 let mut values = Vec::<(::liquid::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid::ValueView)>::with_capacity(#num_fields);
                 #(
                     values.push((
-                        ::liquid::model::KStringCow::from_static(stringify!(#fields)),
+                        ::liquid::model::KStringCow::from_static(#names),
                         &self.#fields,
                     ));
                 )*
@@ -58,7 +63,7 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
             fn contains_key(&self, index: &str) -> bool {
                 match index {
                     #(
-                        stringify!(#fields) => true,
+                        #names => true,
                     )*
                     _ => false,
                 }
@@ -67,7 +72,7 @@ pub fn derive(input: &DeriveInput) -> TokenStream {
             fn get<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<&'liquid_derive_s dyn ::liquid::ValueView> {
                 match index {
                     #(
-                        stringify!(#fields) => Some(&self.#fields),
+                        #names => Some(&self.#fields),
                     )*
                     _ => None,
                 }
@@ -91,6 +96,8 @@ pub fn core_derive(input: &DeriveInput) -> TokenStream {
         Err(err) => return err.to_compile_error(),
     };
     let num_fields = fields.len();
+    let names: Vec<_> = fields.iter().map(|field| &field.name).collect();
+    let fields: Vec<_> = fields.iter().map(|field| field.ident).collect();
 
     quote! {
         impl #impl_generics ::liquid_core::ObjectView for #ident #ty_generics #where_clause {
@@ -105,7 +112,7 @@ pub fn core_derive(input: &DeriveInput) -> TokenStream {
             fn keys<'liquid_derive_k>(&'liquid_derive_k self) -> Box<dyn Iterator<Item = ::liquid_core::model::KStringCow<'liquid_derive_k>> + 'liquid_derive_k> {
                 let mut keys = Vec::with_capacity(#num_fields);
                 #(
-                    keys.push(::liquid_core::model::KStringCow::from_static(stringify!(#fields)));
+                    keys.push(::liquid_core::model::KStringCow::from_static(#names));
                 )*
                 Box::new(keys.into_iter())
             }
@@ -122,7 +129,7 @@ pub fn core_derive(input: &DeriveInput) -> TokenStream {
                 let mut values = Vec::<(::liquid_core::model::KStringCow<'liquid_derive_k>, &'liquid_derive_k dyn ::liquid_core::ValueView)>::with_capacity(#num_fields);
                 #(
                     values.push((
-                        ::liquid_core::model::KStringCow::from_static(stringify!(#fields)),
+                        ::liquid_core::model::KStringCow::from_static(#names),
                         &self.#fields,
                     ));
                 )*
@@ -132,7 +139,7 @@ pub fn core_derive(input: &DeriveInput) -> TokenStream {
             fn contains_key(&self, index: &str) -> bool {
                 match index {
                     #(
-                        stringify!(#fields) => true,
+                        #names => true,
                     )*
                     _ => false,
                 }
@@ -141,7 +148,7 @@ pub fn core_derive(input: &DeriveInput) -> TokenStream {
             fn get<'liquid_derive_s>(&'liquid_derive_s self, index: &str) -> Option<&'liquid_derive_s dyn ::liquid_core::ValueView> {
                 match index {
                     #(
-                        stringify!(#fields) => Some(&self.#fields),
+                        #names => Some(&self.#fields),
                     )*
                     _ => None,
                 }
@@ -150,7 +157,46 @@ pub fn core_derive(input: &DeriveInput) -> TokenStream {
     }
 }
 
-pub(crate) fn get_fields(data: &Data) -> Result<Vec<&Ident>> {
+/// A struct field exposed as a key of the object.
+pub(crate) struct ObjectField<'a> {
+    pub(crate) ident: &'a Ident,
+    /// The key, which is the field's name unless renamed with `#[object(rename = "...")]`.
+    pub(crate) name: String,
+}
+
+/// Parses the `#[object(...)]` attributes of a field, returning `None` if it is skipped.
+fn parse_field(field: &Field) -> Result<Option<ObjectField<'_>>> {
+    let ident = field.ident.as_ref().expect("Fields are named.");
+    let mut rename = AssignOnce::Unset;
+    let mut skip = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("object"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                assign_str_value(&mut rename, attr, "rename", &meta)?;
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+            } else {
+                return Err(Error::new(
+                    attr.span(),
+                    format!("unknown `{}` object attribute", meta.path.to_token_stream()),
+                ));
+            }
+            Ok(())
+        })?;
+    }
+
+    if skip {
+        return Ok(None);
+    }
+    let name = rename.into_option().unwrap_or_else(|| ident.to_string());
+    Ok(Some(ObjectField { ident, name }))
+}
+
+pub(crate) fn get_fields(data: &Data) -> Result<Vec<ObjectField<'_>>> {
     let fields = match data {
         Data::Struct(data) => &data.fields,
         Data::Enum(data) => {
@@ -183,9 +229,9 @@ pub(crate) fn get_fields(data: &Data) -> Result<Vec<&Ident>> {
         }
     };
 
-    Ok(fields
-        .named
-        .iter()
-        .map(|field| field.ident.as_ref().expect("Fields are named."))
-        .collect())
+    let mut object_fields = Vec::with_capacity(fields.named.len());
+    for field in &fields.named {
+        object_fields.extend(parse_field(field)?);
+    }
+    Ok(object_fields)
 }
@@ -0,0 +1,107 @@
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+
+use crate::{invalid_argument, invalid_input};
+
+// liquid-rust proprietary
+
+#[derive(Debug, FilterParameters)]
+struct InGroupsOfArgs {
+    #[parameter(
+        description = "The number of elements in each group.",
+        arg_type = "integer"
+    )]
+    size: Expression,
+    #[parameter(
+        description = "Whether to fill out the last group with nils. Defaults to false.",
+        arg_type = "bool",
+        mode = "keyword"
+    )]
+    pad: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "in_groups_of",
+    description = "Splits an array into groups of the given size, like rows of a grid.",
+    parameters(InGroupsOfArgs),
+    parsed(InGroupsOfFilter)
+)]
+pub struct InGroupsOf;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "in_groups_of"]
+struct InGroupsOfFilter {
+    #[parameters]
+    args: InGroupsOfArgs,
+}
+
+impl Filter for InGroupsOfFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        if args.size <= 0 {
+            return Err(invalid_argument("size", "Positive integer expected"));
+        }
+        let size = args.size as usize;
+        let pad = args.pad.unwrap_or(false);
+
+        let array = input
+            .as_array()
+            .ok_or_else(|| invalid_input("Array expected"))?;
+        let elements: Vec<_> = array.values().map(|v| v.to_value()).collect();
+        let groups = elements
+            .chunks(size)
+            .map(|group| {
+                let mut group = group.to_vec();
+                if pad {
+                    group.resize(size, Value::Nil);
+                }
+                Value::Array(group)
+            })
+            .collect();
+        Ok(Value::Array(groups))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_in_groups_of() {
+        assert_eq!(
+            liquid_core::call_filter!(InGroupsOf, liquid_core::value!([1, 2, 3, 4, 5]), 2).unwrap(),
+            liquid_core::value!([[1, 2], [3, 4], [5]])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(InGroupsOf, liquid_core::value!([1, 2, 3, 4]), 2).unwrap(),
+            liquid_core::value!([[1, 2], [3, 4]])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(InGroupsOf, liquid_core::value!([]), 3).unwrap(),
+            liquid_core::value!([])
+        );
+        liquid_core::call_filter!(InGroupsOf, liquid_core::value!([1]), 0).unwrap_err();
+        liquid_core::call_filter!(InGroupsOf, "abc", 2).unwrap_err();
+    }
+
+    #[test]
+    fn unit_in_groups_of_pad() {
+        let input = liquid_core::value!(["a", "b", "c", "d"]);
+        assert_eq!(
+            liquid_core::call_filter!(InGroupsOf, input.clone(), 3; pad = true).unwrap(),
+            liquid_core::value!([["a", "b", "c"], ["d", nil, nil]])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(InGroupsOf, input.clone(), 3; pad = false).unwrap(),
+            liquid_core::value!([["a", "b", "c"], ["d"]])
+        );
+        liquid_core::call_filter!(InGroupsOf, input, 3; padding = true).unwrap_err();
+    }
+}
@@ -0,0 +1,228 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::error::ResultLiquidReplaceExt;
+use liquid_core::model::KString;
+use liquid_core::runtime::LayoutRegister;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+
+// liquid-rust proprietary
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BlockBlock;
+
+impl BlockBlock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BlockReflection for BlockBlock {
+    fn start_tag(&self) -> &str {
+        "block"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endblock"
+    }
+
+    fn description(&self) -> &str {
+        "a named section that a template using this one as a `layout` can override"
+    }
+}
+
+impl ParseBlock for BlockBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let name: KString = arguments
+            .expect_next("Identifier expected")?
+            .expect_identifier()
+            .into_result()?
+            .to_string()
+            .into();
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        let template = Template::new(
+            tokens
+                .parse_all(options)
+                .trace_with(|| format!("{{% block {} %}}", &name).into())?,
+        );
+
+        tokens.assert_empty();
+        Ok(Box::new(Block { name, template }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Block {
+    name: KString,
+    template: Template,
+}
+
+impl Block {
+    fn trace(&self) -> String {
+        format!("{{% block {} %}}", self.name)
+    }
+}
+
+impl Renderable for Block {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let content = runtime
+            .registers()
+            .get_mut::<LayoutRegister>()
+            .block(&self.name)
+            .map(|content| content.to_owned());
+        let content = match content {
+            Some(content) => content,
+            None => {
+                let mut rendered = Vec::new();
+                self.template
+                    .render_to(&mut rendered, runtime)
+                    .trace_with(|| self.trace().into())?;
+                let rendered = String::from_utf8(rendered)
+                    .replace("Failed to render")
+                    .trace_with(|| self.trace().into())?;
+
+                // Only a template waiting on its layout overrides that layout's blocks.
+                let mut layout = runtime.registers().get_mut::<LayoutRegister>();
+                if layout.layout().is_some() {
+                    layout.set_block(self.name.clone(), rendered.clone());
+                }
+                rendered
+            }
+        };
+
+        write!(writer, "{}", content).replace("Failed to render")?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "stdlib"))]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::partials;
+    use liquid_core::partials::PartialCompiler;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    use crate::extra::LayoutTag;
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .blocks
+            .register("block".to_string(), BlockBlock.into());
+        options
+            .tags
+            .register("layout".to_string(), LayoutTag.into());
+        options
+            .tags
+            .register("assign".to_string(), stdlib::AssignTag.into());
+        options
+    }
+
+    fn render(text: &str) -> Result<String> {
+        let options = options();
+        let template = parser::parse(text, &options).map(runtime::Template::new)?;
+
+        let mut source = partials::InMemorySource::new();
+        source.add(
+            "base",
+            "<title>{% block title %}Site{% endblock %}</title>{% block body %}{% block content %}empty{% endblock %}{% endblock %}",
+        );
+        source.add(
+            "page",
+            "{% layout 'base' %}{% block content %}<p>{% block text %}{% endblock %}</p>{% endblock %}",
+        );
+        source.add("loop", "{% layout 'loop' %}");
+        let partials =
+            partials::EagerCompiler::new(source).compile(::std::sync::Arc::new(options))?;
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+
+        let mut output = Vec::new();
+        runtime::render_with_layouts(&template, &mut output, &runtime)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn block_without_layout() {
+        let output = render("{% block title %}Hello{% endblock %}").unwrap();
+        assert_eq!(output, "Hello");
+    }
+
+    #[test]
+    fn layout_defaults() {
+        let output = render("{% layout 'base' %}ignored").unwrap();
+        assert_eq!(output, "<title>Site</title>empty");
+    }
+
+    #[test]
+    fn layout_overrides_blocks() {
+        let output = render(
+            "{% layout 'base' %}{% assign name = 'Home' %}{% block title %}{{ name }}{% endblock %}",
+        )
+        .unwrap();
+        assert_eq!(output, "<title>Home</title>empty");
+    }
+
+    #[test]
+    fn layout_nested_blocks() {
+        let output = render(
+            "{% layout 'base' %}{% block body %}[{% block content %}x{% endblock %}]{% endblock %}",
+        )
+        .unwrap();
+        assert_eq!(output, "<title>Site</title>[x]");
+    }
+
+    #[test]
+    fn layout_chain() {
+        let output =
+            render("{% layout 'page' %}{% block title %}Page{% endblock %}{% block text %}hi{% endblock %}")
+                .unwrap();
+        assert_eq!(output, "<title>Page</title><p>hi</p>");
+    }
+
+    #[test]
+    fn layout_after_whitespace() {
+        let output = render("\n  {% layout 'base' %}\n").unwrap();
+        assert_eq!(output, "<title>Site</title>empty");
+
+        let output = render("text\n").unwrap();
+        assert_eq!(output, "text\n");
+    }
+
+    #[test]
+    fn layout_after_output_is_an_error() {
+        assert!(render("text{% layout 'base' %}").is_err());
+    }
+
+    #[test]
+    fn layout_cycle_is_an_error() {
+        assert!(render("{% layout 'loop' %}").is_err());
+    }
+
+    #[test]
+    fn missing_layout_is_an_error() {
+        assert!(render("{% layout 'nope' %}").is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::sync::Arc;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::error::ResultLiquidReplaceExt;
+use liquid_core::parser::FragmentCache;
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::ValueView;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+
+// liquid-rust proprietary
+
+/// Reuse the output rendered for a key, from the language's
+/// [`FragmentCache`][liquid_core::parser::FragmentCache].
+///
+/// A hit skips rendering the content entirely, so its side effects, like `{% assign %}` or
+/// `{% increment %}`, don't happen either.  Without a cache configured, the content always
+/// renders.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CacheBlock;
+
+impl CacheBlock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BlockReflection for CacheBlock {
+    fn start_tag(&self) -> &str {
+        "cache"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endcache"
+    }
+
+    fn description(&self) -> &str {
+        "reuse the output rendered for the same key, rather than rendering the content again"
+    }
+}
+
+impl ParseBlock for CacheBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let key = arguments
+            .expect_next("Key expected.")?
+            .expect_value()
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        let template = Template::new(
+            tokens
+                .parse_all(options)
+                .trace_with(|| format!("{{% cache {} %}}", key).into())?,
+        );
+
+        tokens.assert_empty();
+        Ok(Box::new(Cache {
+            key,
+            template,
+            cache: options.fragment_cache.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Cache {
+    key: Expression,
+    template: Template,
+    cache: Option<Arc<dyn FragmentCache>>,
+}
+
+impl Cache {
+    fn trace(&self) -> String {
+        format!("{{% cache {} %}}", self.key)
+    }
+}
+
+impl Renderable for Cache {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let key = self
+            .key
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?;
+        // Keys can be long, so the cache stores a digest of them instead.
+        let mut hasher = DefaultHasher::new();
+        key.to_kstr().as_str().hash(&mut hasher);
+        let key = format!("{:016x}", hasher.finish());
+
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => {
+                return self
+                    .template
+                    .render_to(writer, runtime)
+                    .trace_with(|| self.trace().into());
+            }
+        };
+
+        let fragment = match cache.get(&key) {
+            Some(fragment) => fragment,
+            None => {
+                let mut rendered = Vec::new();
+                self.template
+                    .render_to(&mut rendered, runtime)
+                    .trace_with(|| self.trace().into())?;
+                let rendered = String::from_utf8(rendered)
+                    .replace("Failed to render")
+                    .trace_with(|| self.trace().into())?;
+                cache.insert(&key, rendered.clone());
+                rendered
+            }
+        };
+
+        write!(writer, "{}", fragment).replace("Failed to render")?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "stdlib"))]
+mod test {
+    use super::*;
+
+    use liquid_core::model::Value;
+    use liquid_core::parser;
+    use liquid_core::parser::LruFragmentCache;
+    use liquid_core::runtime;
+    use liquid_core::runtime::{Runtime, RuntimeBuilder};
+
+    fn options(cache: Option<Arc<LruFragmentCache>>) -> Arc<Language> {
+        let mut options = Language::default();
+        options
+            .blocks
+            .register("cache".to_string(), CacheBlock.into());
+        options
+            .tags
+            .register("assign".to_string(), crate::stdlib::AssignTag.into());
+        options.fragment_cache = cache.map(|cache| cache as Arc<dyn FragmentCache>);
+        Arc::new(options)
+    }
+
+    fn runtime() -> impl Runtime {
+        RuntimeBuilder::new().build()
+    }
+
+    #[test]
+    fn cache_hit_skips_rendering() {
+        let cache = Arc::new(LruFragmentCache::new(10));
+        let options = options(Some(cache.clone()));
+        let text = "{% cache key %}{{ value }}{% endcache %}";
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = runtime();
+        runtime.set_global("key".into(), Value::scalar("a"));
+        runtime.set_global("value".into(), Value::scalar(1));
+        assert_eq!(template.render(&runtime).unwrap(), "1");
+
+        runtime.set_global("value".into(), Value::scalar(2));
+        assert_eq!(template.render(&runtime).unwrap(), "1");
+
+        runtime.set_global("key".into(), Value::scalar("b"));
+        assert_eq!(template.render(&runtime).unwrap(), "2");
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn cache_hit_skips_side_effects() {
+        let options = options(Some(Arc::new(LruFragmentCache::new(10))));
+        let text = "{% cache 'k' %}{% assign x = 1 %}{% endcache %}[{{ x }}]";
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        for expected in ["[1]", "[unset]"] {
+            let runtime = runtime();
+            runtime.set_global("x".into(), Value::scalar("unset"));
+            assert_eq!(template.render(&runtime).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn cache_shared_across_templates() {
+        let options = options(Some(Arc::new(LruFragmentCache::new(10))));
+        let first = parser::parse("{% cache 'k' %}first{% endcache %}", &options)
+            .map(runtime::Template::new)
+            .unwrap();
+        let second = parser::parse("{% cache 'k' %}second{% endcache %}", &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = runtime();
+        assert_eq!(first.render(&runtime).unwrap(), "first");
+        assert_eq!(second.render(&runtime).unwrap(), "first");
+    }
+
+    #[test]
+    fn without_cache_always_renders() {
+        let options = options(None);
+        let template = parser::parse("{% cache 'k' %}{{ value }}{% endcache %}", &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = runtime();
+        runtime.set_global("value".into(), Value::scalar(1));
+        assert_eq!(template.render(&runtime).unwrap(), "1");
+        runtime.set_global("value".into(), Value::scalar(2));
+        assert_eq!(template.render(&runtime).unwrap(), "2");
+    }
+
+    #[test]
+    fn missing_key_is_an_error() {
+        let template = parser::parse("{% cache %}{% endcache %}", &options(None));
+        assert!(template.is_err());
+    }
+}
@@ -0,0 +1,292 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::{KString, Value, ValueCow};
+use liquid_core::parser::TryMatchToken;
+use liquid_core::runtime::GlobalFrame;
+use liquid_core::runtime::SandboxedStackFrame;
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Runtime;
+use liquid_core::{Error, Result};
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+use super::MacroRegister;
+
+// liquid-rust proprietary
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CallTag;
+
+impl CallTag {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TagReflection for CallTag {
+    fn tag(&self) -> &str {
+        "call"
+    }
+
+    fn description(&self) -> &str {
+        "render a snippet defined with `macro`, in a scope of its own"
+    }
+}
+
+impl ParseTag for CallTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let name: KString = arguments
+            .expect_next("Identifier expected")?
+            .expect_identifier()
+            .into_result()?
+            .to_string()
+            .into();
+
+        // `{% call name value, param: value %}`
+        let mut positional = Vec::new();
+        let mut named: Vec<(KString, Expression)> = Vec::new();
+        let mut token = arguments.next();
+        while let Some(arg) = token.take() {
+            token = arguments.next();
+            let is_named = match token.take().map(|colon| colon.expect_str(":")) {
+                Some(TryMatchToken::Matches(())) => true,
+                Some(TryMatchToken::Fails(next)) => {
+                    token = Some(next);
+                    false
+                }
+                None => false,
+            };
+
+            if is_named {
+                let id = arg.expect_identifier().into_result()?.to_string();
+                let value = arguments
+                    .expect_next("expected value")?
+                    .expect_value()
+                    .into_result()?;
+                named.push((id.into(), value));
+                token = arguments.next();
+            } else {
+                if !named.is_empty() {
+                    return Err(
+                        arguments.raise_error("Positional arguments must come before named ones")
+                    );
+                }
+                positional.push(arg.expect_value().into_result()?);
+            }
+
+            if let Some(comma) = token.take() {
+                comma
+                    .expect_str(",")
+                    .into_result_custom_msg("`,` is needed to separate arguments")?;
+                token = Some(arguments.expect_next("Argument expected")?);
+            }
+        }
+
+        Ok(Box::new(Call {
+            name,
+            positional,
+            named,
+        }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Call {
+    name: KString,
+    positional: Vec<Expression>,
+    named: Vec<(KString, Expression)>,
+}
+
+impl Call {
+    fn trace(&self) -> String {
+        format!("{{% call {} %}}", self.name)
+    }
+}
+
+impl Renderable for Call {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        // Don't hold the register while rendering, the macro may `call` others.
+        let (definition, macros) = {
+            let register = runtime.registers().get_mut::<MacroRegister>();
+            (register.get(&self.name), register.enter_call())
+        };
+        let definition = definition.ok_or_else(|| {
+            Error::with_msg("Unknown macro")
+                .context("macro", self.name.clone())
+                .trace(self.trace())
+        })?;
+        if MacroRegister::MAX_DEPTH < macros.depth() {
+            return Error::with_msg("Too many nested macro calls")
+                .context("maximum depth", MacroRegister::MAX_DEPTH.to_string())
+                .trace(self.trace())
+                .into_err();
+        }
+
+        if definition.params.len() < self.positional.len() {
+            return Error::with_msg("Too many arguments")
+                .context("expected", definition.params.len().to_string())
+                .context("found", self.positional.len().to_string())
+                .trace(self.trace())
+                .into_err();
+        }
+
+        let mut root = std::collections::HashMap::new();
+        for (i, (param, default)) in definition.params.iter().enumerate() {
+            let arg = self.positional.get(i).or_else(|| {
+                self.named
+                    .iter()
+                    .find(|(name, _)| name == param)
+                    .map(|(_, value)| value)
+            });
+            let value = match arg.or(default.as_ref()) {
+                Some(value) => value.evaluate(runtime).trace_with(|| self.trace().into())?,
+                None => ValueCow::Owned(Value::Nil),
+            };
+            root.insert(param.as_ref(), value);
+        }
+        if let Some((name, _)) = self
+            .named
+            .iter()
+            .find(|(name, _)| !definition.params.iter().any(|(param, _)| param == name))
+        {
+            return Error::with_msg("Unknown parameter")
+                .context("parameter", name.clone())
+                .trace(self.trace())
+                .into_err();
+        }
+
+        let scope = GlobalFrame::new(SandboxedStackFrame::new(runtime, &root));
+        // The sandbox has registers of its own, so carry over the macros it may call.  The depth
+        // is restored along with the caller's registers however the macro returns.
+        *scope.registers().get_mut::<MacroRegister>() = macros;
+        definition
+            .template
+            .render_to(writer, &scope)
+            .trace_with(|| self.trace().into())
+    }
+}
+
+#[cfg(all(test, feature = "stdlib"))]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    use crate::extra::MacroBlock;
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options.tags.register("call".to_string(), CallTag.into());
+        options
+            .tags
+            .register("assign".to_string(), stdlib::AssignTag.into());
+        options
+            .blocks
+            .register("macro".to_string(), MacroBlock.into());
+        options
+            .blocks
+            .register("for".to_string(), stdlib::ForBlock.into());
+        options
+    }
+
+    fn render(text: &str) -> Result<String> {
+        let template = parser::parse(text, &options()).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("page".into(), Value::scalar("home"));
+        runtime.set_global(
+            "numbers".into(),
+            Value::Array(vec![Value::scalar(1), Value::scalar(2)]),
+        );
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn call_macro() {
+        let text = concat!(
+            "{% macro link label, url: '/' %}<a href=\"{{ url }}\">{{ label }}</a>{% endmacro %}",
+            "{% call link 'Home' %} ",
+            "{% call link 'About', url: '/about' %} ",
+            "{% call link url: '/x', label: page %}",
+        );
+        assert_eq!(
+            render(text).unwrap(),
+            "<a href=\"/\">Home</a> <a href=\"/about\">About</a> <a href=\"/x\">home</a>"
+        );
+    }
+
+    #[test]
+    fn call_is_isolated() {
+        let text = concat!(
+            "{% macro set %}{% assign leaked = 'yes' %}{{ page }}{% endmacro %}",
+            "{% call set %}{{ leaked }}",
+        );
+        // Neither the caller's variables nor the macro's assigns cross over.
+        assert!(render(text).is_err());
+
+        let text = concat!(
+            "{% macro set %}{% assign leaked = 'yes' %}{% endmacro %}",
+            "{% call set %}{{ leaked }}",
+        );
+        assert!(render(text).is_err());
+    }
+
+    #[test]
+    fn call_nested_macros() {
+        let text = concat!(
+            "{% macro item x %}<li>{{ x }}</li>{% endmacro %}",
+            "{% macro list items %}{% for i in items %}{% call item i %}{% endfor %}{% endmacro %}",
+            "{% call list numbers %}",
+        );
+        assert_eq!(render(text).unwrap(), "<li>1</li><li>2</li>");
+
+        let text = concat!(
+            "{% macro item x %}<li>{{ x }}</li>{% endmacro %}",
+            "{% macro twice x %}{% call item x %}{% call item x %}{% endmacro %}",
+            "{% call twice 1 %}",
+        );
+        assert_eq!(render(text).unwrap(), "<li>1</li><li>1</li>");
+    }
+
+    #[test]
+    fn call_recursion_is_limited() {
+        let text = "{% macro forever %}{% call forever %}{% endmacro %}{% call forever %}";
+        assert!(render(text).is_err());
+    }
+
+    #[test]
+    fn missing_argument_is_nil() {
+        let text = "{% macro show x %}[{{ x }}]{% endmacro %}{% call show %}";
+        assert_eq!(render(text).unwrap(), "[]");
+    }
+
+    #[test]
+    fn call_errors() {
+        for text in [
+            "{% call nope %}",
+            "{% macro show %}{% endmacro %}{% call show 1 %}",
+            "{% macro show x %}{% endmacro %}{% call show y: 1 %}",
+        ] {
+            assert!(render(text).is_err(), "{}", text);
+        }
+        for text in [
+            "{% call show x: 1, 2 %}",
+            "{% call show 1 2 %}",
+            "{% macro show x y %}{% endmacro %}",
+        ] {
+            assert!(parser::parse(text, &options()).is_err(), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::runtime::LayoutRegister;
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::ValueView;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+// liquid-rust proprietary
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LayoutTag;
+
+impl LayoutTag {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TagReflection for LayoutTag {
+    fn tag(&self) -> &'static str {
+        "layout"
+    }
+
+    fn description(&self) -> &'static str {
+        "render a partial in place of this template, overriding its `block`s"
+    }
+}
+
+impl ParseTag for LayoutTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let name = arguments
+            .expect_next("Identifier or literal expected.")?
+            .expect_value()
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(Layout { name }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Layout {
+    name: Expression,
+}
+
+impl Layout {
+    fn trace(&self) -> String {
+        format!("{{% layout {} %}}", self.name)
+    }
+}
+
+impl Renderable for Layout {
+    fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let name = self
+            .name
+            .evaluate(runtime)
+            .trace_with(|| self.trace().into())?
+            .to_kstr()
+            .into_owned();
+        runtime
+            .registers()
+            .get_mut::<LayoutRegister>()
+            .set_layout(name);
+        Ok(())
+    }
+}
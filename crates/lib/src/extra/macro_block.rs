@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Arc;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::KString;
+use liquid_core::parser::TryMatchToken;
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+
+// liquid-rust proprietary
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MacroBlock;
+
+impl MacroBlock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BlockReflection for MacroBlock {
+    fn start_tag(&self) -> &str {
+        "macro"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endmacro"
+    }
+
+    fn description(&self) -> &str {
+        "define a snippet, with parameters, to render with `call`"
+    }
+}
+
+impl ParseBlock for MacroBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let name: KString = arguments
+            .expect_next("Identifier expected")?
+            .expect_identifier()
+            .into_result()?
+            .to_string()
+            .into();
+
+        // `{% macro name param, param: default %}`
+        let mut params = Vec::new();
+        let mut token = arguments.next();
+        while let Some(param) = token.take() {
+            let param: KString = param.expect_identifier().into_result()?.to_string().into();
+
+            let mut default = None;
+            token = arguments.next();
+            if let Some(colon) = token.take() {
+                token = match colon.expect_str(":") {
+                    TryMatchToken::Matches(()) => {
+                        default = Some(
+                            arguments
+                                .expect_next("expected value")?
+                                .expect_value()
+                                .into_result()?,
+                        );
+                        arguments.next()
+                    }
+                    TryMatchToken::Fails(colon) => Some(colon),
+                };
+            }
+            params.push((param, default));
+
+            if let Some(comma) = token.take() {
+                comma
+                    .expect_str(",")
+                    .into_result_custom_msg("`,` is needed to separate parameters")?;
+                token = Some(arguments.expect_next("Parameter expected")?);
+            }
+        }
+
+        let template = Template::new(
+            tokens
+                .parse_all(options)
+                .trace_with(|| format!("{{% macro {} %}}", &name).into())?,
+        );
+
+        tokens.assert_empty();
+        Ok(Box::new(Macro {
+            definition: Arc::new(MacroDefinition {
+                name,
+                params,
+                template,
+            }),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct MacroDefinition {
+    pub(crate) name: KString,
+    pub(crate) params: Vec<(KString, Option<Expression>)>,
+    pub(crate) template: Template,
+}
+
+/// The macros defined so far in this render.
+///
+/// The definitions are shared with the scopes of the macros being called, and only copied if a
+/// macro defines another.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MacroRegister {
+    macros: Arc<HashMap<KString, Arc<MacroDefinition>>>,
+    depth: usize,
+}
+
+impl MacroRegister {
+    /// How many macros may be called within one another, so recursive macros can't overflow the
+    /// stack.
+    pub(crate) const MAX_DEPTH: usize = 100;
+
+    pub(crate) fn get(&self, name: &str) -> Option<Arc<MacroDefinition>> {
+        self.macros.get(name).cloned()
+    }
+
+    /// How many macros are being called within one another.
+    pub(crate) fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The register for the scope of a macro being called from this one.
+    pub(crate) fn enter_call(&self) -> Self {
+        Self {
+            macros: self.macros.clone(),
+            depth: self.depth + 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Macro {
+    definition: Arc<MacroDefinition>,
+}
+
+impl Renderable for Macro {
+    fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let mut register = runtime.registers().get_mut::<MacroRegister>();
+        Arc::make_mut(&mut register.macros)
+            .insert(self.definition.name.clone(), self.definition.clone());
+        Ok(())
+    }
+}
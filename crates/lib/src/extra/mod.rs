@@ -1,3 +1,21 @@
+mod array;
+mod block_block;
+mod cache_block;
+mod call_tag;
 mod date;
+mod layout_tag;
+mod macro_block;
+mod number;
+mod string;
+mod with_block;
 
+pub use self::array::*;
+pub use self::block_block::*;
+pub use self::cache_block::*;
+pub use self::call_tag::*;
 pub use self::date::*;
+pub use self::layout_tag::*;
+pub use self::macro_block::*;
+pub use self::number::*;
+pub use self::string::*;
+pub use self::with_block::*;
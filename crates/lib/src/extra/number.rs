@@ -0,0 +1,310 @@
+use std::sync::Arc;
+
+use liquid_core::parser::FilterArguments;
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterParameters, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+use crate::{invalid_argument, invalid_input, push_grouped_digits};
+
+/// The most decimal places `format_number` writes.
+const MAX_PRECISION: i64 = 20;
+
+// liquid-rust proprietary
+
+/// How `format_number` writes numbers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Written between each group of three digits, like `,`.
+    pub thousands_separator: String,
+    /// Written between the whole and fractional parts, like `.`.
+    pub decimal_separator: String,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::new(",", ".")
+    }
+}
+
+impl NumberFormat {
+    pub fn new(thousands_separator: &str, decimal_separator: &str) -> Self {
+        Self {
+            thousands_separator: thousands_separator.to_owned(),
+            decimal_separator: decimal_separator.to_owned(),
+        }
+    }
+
+    /// The format for a locale, like `en-US` or `de`, if it is known.
+    ///
+    /// The region is only consulted when it changes the format, so `de-AT` is written like `de`.
+    pub fn locale(locale: &str) -> Option<Self> {
+        let locale = locale.replace('_', "-").to_ascii_lowercase();
+        let format = match locale.as_str() {
+            "de-ch" | "it-ch" | "fr-ch" => Self::new("’", "."),
+            "en-za" | "pt-pt" => Self::new("\u{A0}", ","),
+            "es-mx" | "es-us" => Self::new(",", "."),
+            _ => match locale.split('-').next().unwrap_or_default() {
+                "en" | "ja" | "ko" | "zh" | "th" | "he" => Self::new(",", "."),
+                "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr"
+                | "sl" => Self::new(".", ","),
+                "fr" => Self::new("\u{202F}", ","),
+                "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" | "hu" | "bg"
+                | "lt" | "lv" | "et" => Self::new("\u{A0}", ","),
+                _ => return None,
+            },
+        };
+        Some(format)
+    }
+
+    /// Write `number`, already formatted in the usual `-1234.5` way.
+    fn format(&self, number: &str) -> String {
+        let (sign, number) = match number.strip_prefix('-') {
+            Some(number) => ("-", number),
+            None => ("", number),
+        };
+        let (whole, fraction) = match number.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (number, None),
+        };
+
+        let mut formatted = sign.to_owned();
+        push_grouped_digits(&mut formatted, whole, &self.thousands_separator);
+        if let Some(fraction) = fraction {
+            formatted.push_str(&self.decimal_separator);
+            formatted.push_str(fraction);
+        }
+        formatted
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct FormatNumberArgs {
+    #[parameter(
+        description = "The number of decimal places. By default, whole numbers have none and others as many as needed.",
+        arg_type = "integer"
+    )]
+    precision: Option<Expression>,
+    #[parameter(
+        description = "The locale to format the number for, like \"de-DE\", rather than the default.",
+        arg_type = "str",
+        mode = "keyword"
+    )]
+    locale: Option<Expression>,
+}
+
+/// Formats a number with thousands separators, like `{{ 1234.5 | format_number: 2 }}` to
+/// `1,234.50`.
+///
+/// Numbers are formatted with the [`NumberFormat`] given to [`FormatNumber::new`], unless
+/// another locale is asked for, like `{{ 1234.5 | format_number: 2, locale: "de" }}` to
+/// `1.234,50`.
+#[derive(Clone, Default, FilterReflection)]
+#[filter(
+    name = "format_number",
+    description = "Formats a number with thousands separators and an optional number of decimal places.",
+    parameters(FormatNumberArgs),
+    parsed(FormatNumberFilter)
+)]
+pub struct FormatNumber {
+    format: Arc<NumberFormat>,
+}
+
+impl FormatNumber {
+    /// Format numbers like `format`, unless a template asks for a locale.
+    pub fn new(format: NumberFormat) -> Self {
+        Self {
+            format: Arc::new(format),
+        }
+    }
+}
+
+impl ParseFilter for FormatNumber {
+    fn parse(&self, args: FilterArguments<'_>) -> Result<Box<dyn Filter>> {
+        let args = FormatNumberArgs::from_args(args)?;
+        Ok(Box::new(FormatNumberFilter {
+            args,
+            format: self.format.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug, Display_filter)]
+#[name = "format_number"]
+struct FormatNumberFilter {
+    #[parameters]
+    args: FormatNumberArgs,
+    format: Arc<NumberFormat>,
+}
+
+impl Filter for FormatNumberFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
+        let locale_format;
+        let format = match args.locale {
+            Some(locale) => {
+                locale_format = NumberFormat::locale(locale.as_str())
+                    .ok_or_else(|| invalid_argument("locale", "Unknown locale"))?;
+                &locale_format
+            }
+            None => self.format.as_ref(),
+        };
+        let precision = match args.precision {
+            Some(precision) if precision < 0 => {
+                return Err(invalid_argument(
+                    "precision",
+                    "Non-negative integer expected",
+                ))
+            }
+            Some(precision) if MAX_PRECISION < precision => {
+                return Err(invalid_argument(
+                    "precision",
+                    "At most 20 decimal places are supported",
+                ))
+            }
+            precision => precision.map(|precision| precision as usize),
+        };
+
+        let number = input
+            .as_scalar()
+            .ok_or_else(|| invalid_input("Number expected"))?;
+        let number = match (number.to_integer(), precision) {
+            (Some(n), None) => n.to_string(),
+            (Some(n), Some(0)) => n.to_string(),
+            (Some(n), Some(precision)) => format!("{}.{}", n, "0".repeat(precision)),
+            (None, precision) => {
+                let f = number
+                    .to_float()
+                    .filter(|f| f.is_finite())
+                    .ok_or_else(|| invalid_input("Number expected"))?;
+                match precision {
+                    Some(precision) => format!("{:.*}", precision, f),
+                    None => f.to_string(),
+                }
+            }
+        };
+        Ok(Value::scalar(format.format(&number)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use liquid_core::runtime::RuntimeBuilder;
+
+    fn format_number(filter: FormatNumber, input: Value, locale: Option<&'static str>) -> Value {
+        let filter = FormatNumberFilter {
+            args: FormatNumberArgs {
+                precision: Some(Expression::with_literal(2)),
+                locale: locale.map(Expression::with_literal),
+            },
+            format: filter.format,
+        };
+        let runtime = RuntimeBuilder::new().build();
+        filter.evaluate(&input, &runtime).unwrap()
+    }
+
+    #[test]
+    fn unit_format_number() {
+        for (input, expected) in [
+            (liquid_core::value!(1234567), "1,234,567"),
+            (liquid_core::value!(-1234), "-1,234"),
+            (liquid_core::value!(999), "999"),
+            (liquid_core::value!(1234.5), "1,234.5"),
+            (liquid_core::value!("1234.5"), "1,234.5"),
+            (liquid_core::value!(0.25), "0.25"),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(FormatNumber::default(), input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+        assert_eq!(
+            liquid_core::call_filter!(FormatNumber::default(), liquid_core::Value::Nil).unwrap(),
+            liquid_core::Value::Nil
+        );
+        liquid_core::call_filter!(FormatNumber::default(), "abc").unwrap_err();
+        liquid_core::call_filter!(FormatNumber::default(), 1, -1).unwrap_err();
+        liquid_core::call_filter!(FormatNumber::default(), 1, 21).unwrap_err();
+        liquid_core::call_filter!(FormatNumber::default(), 1.5, 9_999_999_999i64).unwrap_err();
+    }
+
+    #[test]
+    fn unit_format_number_precision() {
+        for (input, precision, expected) in [
+            (1234.5, 2, "1,234.50"),
+            (1234.567, 2, "1,234.57"),
+            (1234.5, 0, "1,234"),
+            (-0.5, 3, "-0.500"),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(FormatNumber::default(), input, precision).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+        assert_eq!(
+            liquid_core::call_filter!(FormatNumber::default(), 1000, 1).unwrap(),
+            liquid_core::value!("1,000.0")
+        );
+    }
+
+    #[test]
+    fn unit_format_number_locale() {
+        assert_eq!(
+            format_number(
+                FormatNumber::default(),
+                liquid_core::value!(1234.56),
+                Some("de-DE")
+            ),
+            liquid_core::value!("1.234,56")
+        );
+        assert_eq!(
+            format_number(
+                FormatNumber::default(),
+                liquid_core::value!(1234567.891),
+                Some("fr")
+            ),
+            liquid_core::value!("1\u{202F}234\u{202F}567,89")
+        );
+        assert_eq!(
+            format_number(
+                FormatNumber::default(),
+                liquid_core::value!(1234.5),
+                Some("de_CH")
+            ),
+            liquid_core::value!("1’234.50")
+        );
+        assert_eq!(
+            format_number(
+                FormatNumber::new(NumberFormat::locale("de").unwrap()),
+                liquid_core::value!(1234.5),
+                None
+            ),
+            liquid_core::value!("1.234,50")
+        );
+
+        let filter = FormatNumberFilter {
+            args: FormatNumberArgs {
+                precision: None,
+                locale: Some(Expression::with_literal("xx-YY")),
+            },
+            format: Default::default(),
+        };
+        let runtime = RuntimeBuilder::new().build();
+        filter
+            .evaluate(&liquid_core::value!(1), &runtime)
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,161 @@
+use std::borrow::Cow;
+
+use liquid_core::parser::FilterArguments;
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterParameters, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+use regex::Regex;
+
+use crate::invalid_argument;
+
+// liquid-rust proprietary
+
+#[derive(Debug, FilterParameters)]
+struct SplitRegexArgs {
+    #[parameter(
+        description = "The regular expression matching the separators between elements.",
+        arg_type = "str"
+    )]
+    pattern: Expression,
+}
+
+fn compile(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| invalid_argument("pattern".to_owned(), e.to_string()))
+}
+
+/// Divides a string into an array, splitting on each match of a regular expression, like
+/// `{{ "a, b;c" | split_regex: "[,;]\s*" }}`.
+///
+/// A literal pattern is compiled once, when the template is parsed; one from a variable is
+/// compiled each time the filter is rendered.
+#[derive(Clone, FilterReflection)]
+#[filter(
+    name = "split_regex",
+    description = "Divides an input string into an array using a regular expression as the separator.",
+    parameters(SplitRegexArgs),
+    parsed(SplitRegexFilter)
+)]
+pub struct SplitRegex;
+
+impl ParseFilter for SplitRegex {
+    fn parse(&self, args: FilterArguments<'_>) -> Result<Box<dyn Filter>> {
+        let args = SplitRegexArgs::from_args(args)?;
+        let regex = match &args.pattern {
+            Expression::Literal(pattern) => Some(compile(pattern.to_kstr().as_str())?),
+            _ => None,
+        };
+        Ok(Box::new(SplitRegexFilter { args, regex }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug, Display_filter)]
+#[name = "split_regex"]
+struct SplitRegexFilter {
+    #[parameters]
+    args: SplitRegexArgs,
+    regex: Option<Regex>,
+}
+
+impl Filter for SplitRegexFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let regex = match &self.regex {
+            Some(regex) => Cow::Borrowed(regex),
+            None => {
+                let args = self.args.evaluate(runtime)?;
+                Cow::Owned(compile(args.pattern.as_str())?)
+            }
+        };
+
+        let input = input.to_kstr();
+        let mut elements: Vec<_> = regex.split(input.as_str()).collect();
+        // Like Ruby's `String#split`, trailing empty elements are dropped.
+        while elements.last().is_some_and(|e| e.is_empty()) {
+            elements.pop();
+        }
+        Ok(Value::Array(
+            elements
+                .into_iter()
+                .map(|e| Value::scalar(e.to_owned()))
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+    use liquid_core::{Language, Object, Renderable};
+
+    #[test]
+    fn unit_split_regex() {
+        assert_eq!(
+            liquid_core::call_filter!(SplitRegex, "a, b;c  d", r"[,;\s]+").unwrap(),
+            liquid_core::value!(["a", "b", "c", "d"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(SplitRegex, "a1b22c", r"\d").unwrap(),
+            liquid_core::value!(["a", "b", "", "c"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(SplitRegex, "a.b.", r"\.").unwrap(),
+            liquid_core::value!(["a", "b"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(SplitRegex, "", ",").unwrap(),
+            liquid_core::value!([])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(SplitRegex, "abc", ",").unwrap(),
+            liquid_core::value!(["abc"])
+        );
+    }
+
+    #[test]
+    fn unit_split_regex_invalid_pattern() {
+        liquid_core::call_filter!(SplitRegex, "abc", "(").unwrap_err();
+        liquid_core::call_filter!(SplitRegex, "abc").unwrap_err();
+    }
+
+    fn render(text: &str, globals: Object) -> Result<String> {
+        let mut options = Language::default();
+        options
+            .filters
+            .register("split_regex".to_owned(), Box::new(SplitRegex));
+        let template = parser::parse(text, &options).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new().set_globals(&globals).build();
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn split_regex_variable_pattern() {
+        let mut globals = Object::new();
+        globals.insert("pattern".into(), Value::scalar(r"\W+"));
+        assert_eq!(
+            render(r#"{{ "one, two! three" | split_regex: pattern }}"#, globals).unwrap(),
+            "onetwothree"
+        );
+
+        let mut globals = Object::new();
+        globals.insert("pattern".into(), Value::scalar("["));
+        render(r#"{{ "abc" | split_regex: pattern }}"#, globals).unwrap_err();
+    }
+
+    #[test]
+    fn split_regex_literal_pattern_checked_on_parse() {
+        let mut options = Language::default();
+        options
+            .filters
+            .register("split_regex".to_owned(), Box::new(SplitRegex));
+        parser::parse(r#"{{ "abc" | split_regex: "(" }}"#, &options).unwrap_err();
+    }
+}
@@ -0,0 +1,198 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::{KString, KStringRef, ValueView};
+use liquid_core::runtime::StackFrame;
+use liquid_core::Expression;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+
+// liquid-rust proprietary
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct WithBlock;
+
+impl WithBlock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BlockReflection for WithBlock {
+    fn start_tag(&self) -> &str {
+        "with"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endwith"
+    }
+
+    fn description(&self) -> &str {
+        "alias values for the content of the block"
+    }
+}
+
+impl ParseBlock for WithBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        // `{% with value as name, value as name %}`
+        let mut bindings = Vec::new();
+        loop {
+            let value = arguments
+                .expect_next("Value expected.")?
+                .expect_value()
+                .into_result()?;
+            arguments
+                .expect_next("\"as\" expected.")?
+                .expect_str("as")
+                .into_result_custom_msg("\"as\" expected.")?;
+            let name: KString = arguments
+                .expect_next("Identifier expected.")?
+                .expect_identifier()
+                .into_result()?
+                .to_string()
+                .into();
+            bindings.push((name, value));
+
+            match arguments.next() {
+                Some(comma) => comma
+                    .expect_str(",")
+                    .into_result_custom_msg("`,` is needed to separate bindings")?,
+                None => break,
+            }
+        }
+
+        let template = Template::new(tokens.parse_all(options)?);
+
+        tokens.assert_empty();
+        Ok(Box::new(With { bindings, template }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct With {
+    bindings: Vec<(KString, Expression)>,
+    template: Template,
+}
+
+impl With {
+    fn trace(&self) -> String {
+        let bindings = self
+            .bindings
+            .iter()
+            .map(|(name, value)| format!("{} as {}", value, name));
+        format!("{{% with {} %}}", itertools::join(bindings, ", "))
+    }
+}
+
+impl Renderable for With {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let mut root = std::collections::HashMap::<KStringRef<'_>, &dyn ValueView>::new();
+        let values = self
+            .bindings
+            .iter()
+            .map(|(_, value)| value.evaluate(runtime))
+            .collect::<Result<Vec<_>>>()
+            .trace_with(|| self.trace().into())?;
+        for ((name, _), value) in self.bindings.iter().zip(values.iter()) {
+            root.insert(name.as_ref(), value.as_view());
+        }
+
+        let scope = StackFrame::new(runtime, &root);
+        self.template
+            .render_to(writer, &scope)
+            .trace_with(|| self.trace().into())
+    }
+}
+
+#[cfg(all(test, feature = "stdlib"))]
+mod test {
+    use super::*;
+
+    use liquid_core::model::Value;
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+    use liquid_core::Object;
+
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .blocks
+            .register("with".to_string(), WithBlock.into());
+        options
+            .blocks
+            .register("for".to_string(), stdlib::ForBlock.into());
+        options
+            .tags
+            .register("break".to_string(), stdlib::BreakTag.into());
+        options
+    }
+
+    fn render(text: &str) -> Result<String> {
+        let template = parser::parse(text, &options()).map(runtime::Template::new)?;
+
+        let runtime = RuntimeBuilder::new().build();
+        let mut settings = Object::new();
+        settings.insert("theme".into(), Value::scalar("dark"));
+        let mut account = Object::new();
+        account.insert("settings".into(), Value::Object(settings));
+        let mut user = Object::new();
+        user.insert("account".into(), Value::Object(account));
+        user.insert("name".into(), Value::scalar("Ann"));
+        runtime.set_global("user".into(), Value::Object(user));
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn with_alias() {
+        let text = "{% with user.account.settings as s %}{{ s.theme }}{% endwith %}";
+        assert_eq!(render(text).unwrap(), "dark");
+
+        let text = "{% with user.name as n, 'x' as x %}{{ n }}{{ x }}{% endwith %}";
+        assert_eq!(render(text).unwrap(), "Annx");
+    }
+
+    #[test]
+    fn with_is_scoped() {
+        let text = "{% with user.name as n %}{% endwith %}{{ n }}";
+        assert!(render(text).is_err());
+
+        let text = "{% with user.name as user %}{{ user }}{% endwith %}{{ user.name }}";
+        assert_eq!(render(text).unwrap(), "AnnAnn");
+    }
+
+    #[test]
+    fn with_passes_interrupts() {
+        let text =
+            "{% for i in (1..3) %}{{ i }}{% with i as j %}{% break %}{% endwith %}{% endfor %}";
+        assert_eq!(render(text).unwrap(), "1");
+    }
+
+    #[test]
+    fn with_errors() {
+        for text in [
+            "{% with %}{% endwith %}",
+            "{% with user %}{% endwith %}",
+            "{% with user as %}{% endwith %}",
+            "{% with user as u user.name as n %}{% endwith %}",
+            "{% with user as u, %}{% endwith %}",
+        ] {
+            assert!(parser::parse(text, &options()).is_err(), "{}", text);
+        }
+    }
+}
@@ -1,14 +1,19 @@
-use std::fmt::Write;
-
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::{KString, Object};
+use liquid_core::parser;
+use liquid_core::runtime::{LanguageRegister, RandomRegister, StackFrame};
 use liquid_core::Expression;
-use liquid_core::Result;
 use liquid_core::Runtime;
 use liquid_core::{
     Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
 };
+use liquid_core::{Error, Result};
 use liquid_core::{Value, ValueView};
 
-use crate::invalid_input;
+use crate::stdlib::{
+    element_scope, elements, expect_variable_name, parse_exp_condition, satisfies,
+};
+use crate::{invalid_argument, invalid_input};
 
 #[derive(Debug, FilterParameters)]
 struct PushArgs {
@@ -47,25 +52,50 @@ impl Filter for PushFilter {
     }
 }
 
+/// The number of elements `pop` and `shift` should remove.
+fn expect_count(count: Option<i64>) -> Result<usize> {
+    match count {
+        None => Ok(1),
+        Some(count) if 0 <= count => Ok(count as usize),
+        Some(_) => Err(invalid_argument("count", "Non-negative integer expected")),
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct PopArgs {
+    #[parameter(
+        description = "The number of elements to remove. Defaults to 1.",
+        arg_type = "integer"
+    )]
+    count: Option<Expression>,
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "pop",
-    description = "Removes the last element of an array.",
+    description = "Removes the last element, or last few elements, of an array.",
+    parameters(PopArgs),
     parsed(PopFilter)
 )]
 pub struct Pop;
 
-#[derive(Debug, Default, Display_filter)]
+#[derive(Debug, FromFilterParameters, Display_filter)]
 #[name = "pop"]
-struct PopFilter;
+struct PopFilter {
+    #[parameters]
+    args: PopArgs,
+}
 
 impl Filter for PopFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let count = expect_count(args.count)?;
         let mut array = input
             .to_value()
             .into_array()
             .ok_or_else(|| invalid_input("Array expected"))?;
-        array.pop();
+        array.truncate(array.len().saturating_sub(count));
 
         Ok(Value::Array(array))
     }
@@ -73,14 +103,14 @@ impl Filter for PopFilter {
 
 #[derive(Debug, FilterParameters)]
 struct UnshiftArgs {
-    #[parameter(description = "The element to append to the array.")]
+    #[parameter(description = "The element to prepend to the array.")]
     element: Expression,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "unshift",
-    description = "Appends the given element to the start of an array.",
+    description = "Prepends the given element to the start of an array.",
     parameters(UnshiftArgs),
     parsed(UnshiftFilter)
 )]
@@ -108,28 +138,41 @@ impl Filter for UnshiftFilter {
     }
 }
 
+#[derive(Debug, FilterParameters)]
+struct ShiftArgs {
+    #[parameter(
+        description = "The number of elements to remove. Defaults to 1.",
+        arg_type = "integer"
+    )]
+    count: Option<Expression>,
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "shift",
-    description = "Removes the first element of an array.",
+    description = "Removes the first element, or first few elements, of an array.",
+    parameters(ShiftArgs),
     parsed(ShiftFilter)
 )]
 pub struct Shift;
 
-#[derive(Debug, Default, Display_filter)]
+#[derive(Debug, FromFilterParameters, Display_filter)]
 #[name = "shift"]
-struct ShiftFilter;
+struct ShiftFilter {
+    #[parameters]
+    args: ShiftArgs,
+}
 
 impl Filter for ShiftFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let count = expect_count(args.count)?;
         let mut array = input
             .to_value()
             .into_array()
             .ok_or_else(|| invalid_input("Array expected"))?;
-
-        if !array.is_empty() {
-            array.remove(0);
-        }
+        array.drain(..count.min(array.len()));
 
         Ok(Value::Array(array))
     }
@@ -166,28 +209,272 @@ impl Filter for ArrayToSentenceStringFilter {
 
         let connector = args.connector.unwrap_or_else(|| "and".into());
 
-        let mut array = input
+        let array = input
             .as_array()
-            .ok_or_else(|| invalid_input("Array expected"))?
-            .values();
-
-        let mut sentence = array
-            .next()
-            .map(|v| v.to_kstr().into_string())
-            .unwrap_or_else(|| "".to_string());
-
-        let mut iter = array.peekable();
-        while let Some(value) = iter.next() {
-            if iter.peek().is_some() {
-                write!(sentence, ", {}", value.render())
-                    .expect("It should be safe to write to a string.");
-            } else {
-                write!(sentence, ", {} {}", connector, value.render())
-                    .expect("It should be safe to write to a string.");
+            .ok_or_else(|| invalid_input("Array expected"))?;
+
+        let elements: Vec<_> = array.values().map(|v| v.render().to_string()).collect();
+        // Like Jekyll, a pair is joined without a comma: "a and b".
+        let sentence = match elements.as_slice() {
+            [] => "".to_owned(),
+            [only] => only.clone(),
+            [first, second] => format!("{} {} {}", first, connector, second),
+            [init @ .., last] => format!("{}, {} {}", init.join(", "), connector, last),
+        };
+
+        Ok(Value::scalar(sentence))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct SampleArgs {
+    #[parameter(
+        description = "The number of elements to pick. When given, the result is an array.",
+        arg_type = "integer"
+    )]
+    count: Option<Expression>,
+}
+
+/// Picks random elements of an array.
+///
+/// The picks can be made reproducible by seeding the
+/// [`RandomRegister`][liquid_core::runtime::RandomRegister].
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "sample",
+    description = "Picks a random element of an array, or an array of `count` random elements.",
+    parameters(SampleArgs),
+    parsed(SampleFilter)
+)]
+pub struct Sample;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "sample"]
+struct SampleFilter {
+    #[parameters]
+    args: SampleArgs,
+}
+
+impl Filter for SampleFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let count = match args.count {
+            Some(count) if count < 0 => {
+                return Err(invalid_argument("count", "Non-negative integer expected"))
             }
+            count => count.map(|count| count as usize),
+        };
+        // Like Jekyll, anything but an array is left as-is.
+        let mut array = match input.to_value().into_array() {
+            Some(array) => array,
+            None => return Ok(input.to_value()),
+        };
+
+        // A partial Fisher-Yates shuffle, so each element is picked at most once.
+        let picks = count.unwrap_or(1).min(array.len());
+        let mut random = runtime.registers().get_mut::<RandomRegister>();
+        for i in 0..picks {
+            let j = i + random.next_below(array.len() - i);
+            array.swap(i, j);
         }
+        array.truncate(picks);
 
-        Ok(Value::scalar(sentence))
+        match count {
+            Some(_) => Ok(Value::Array(array)),
+            None => Ok(array.pop().unwrap_or(Value::Nil)),
+        }
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct GroupByArgs {
+    #[parameter(
+        description = "The property to group the elements by.",
+        arg_type = "str"
+    )]
+    property: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "group_by",
+    description = "Group the elements of an array by a property, into objects with the `name` of the group, its `items` and their `size`.",
+    parameters(GroupByArgs),
+    parsed(GroupByFilter)
+)]
+pub struct GroupBy;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "group_by"]
+struct GroupByFilter {
+    #[parameters]
+    args: GroupByArgs,
+}
+
+impl Filter for GroupByFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let array = input
+            .as_array()
+            .ok_or_else(|| invalid_input("Array expected"))?;
+        let groups = group(array.values(), |element| {
+            Ok(element
+                .as_object()
+                .and_then(|object| object.resolve(&args.property))
+                .map(|value| value.to_kstr().into_owned())
+                .unwrap_or_default())
+        })?;
+
+        Ok(Value::Array(groups))
+    }
+}
+
+/// Group `elements` by their `key`, in order of first appearance, like Jekyll.
+fn group<'v>(
+    elements: impl Iterator<Item = &'v dyn ValueView>,
+    mut key: impl FnMut(&dyn ValueView) -> Result<KString>,
+) -> Result<Vec<Value>> {
+    let mut groups: Vec<(KString, Vec<Value>)> = Vec::new();
+    for element in elements {
+        let name = key(element)?;
+        match groups.iter_mut().find(|(group, _)| *group == name) {
+            Some((_, items)) => items.push(element.to_value()),
+            None => groups.push((name, vec![element.to_value()])),
+        }
+    }
+
+    let groups = groups
+        .into_iter()
+        .map(|(name, items)| {
+            let mut group = Object::new();
+            group.insert("name".into(), Value::scalar(name));
+            group.insert("size".into(), Value::scalar(items.len() as i64));
+            group.insert("items".into(), Value::Array(items));
+            Value::Object(group)
+        })
+        .collect();
+    Ok(groups)
+}
+
+#[derive(Debug, FilterParameters)]
+struct GroupByExpArgs {
+    #[parameter(
+        description = "The name each element is known by in the expression.",
+        arg_type = "str"
+    )]
+    variable: Expression,
+
+    #[parameter(
+        description = "The expression to group the elements by, like `post.date | date: '%Y'`.",
+        arg_type = "str"
+    )]
+    expression: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "group_by_exp",
+    description = "Group the elements of an array by an expression, into objects with the `name` of the group, its `items` and their `size`.",
+    parameters(GroupByExpArgs),
+    parsed(GroupByExpFilter)
+)]
+pub struct GroupByExp;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "group_by_exp"]
+struct GroupByExpFilter {
+    #[parameters]
+    args: GroupByExpArgs,
+}
+
+impl Filter for GroupByExpFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let variable = expect_variable_name(args.variable.as_str())?;
+        // The expression is only known now, so compile it with the template's filters.
+        let language = runtime
+            .registers()
+            .get_mut::<LanguageRegister>()
+            .language()
+            .cloned()
+            .ok_or_else(|| {
+                Error::with_msg("Unknown template language").context(
+                    "cause",
+                    "the expression is compiled with the template's filters",
+                )
+            })?;
+        let expression = parser::parse_expression(args.expression.as_str(), &language)
+            .context_key("expression")
+            .value_with(|| args.expression.to_string().into())?;
+
+        let array = input
+            .as_array()
+            .ok_or_else(|| invalid_input("Array expected"))?;
+        let groups = group(array.values(), |element| {
+            let scope = element_scope(variable, element);
+            let frame = StackFrame::new(runtime, &scope);
+            let name = expression.evaluate(&frame)?;
+            Ok(name.to_kstr().into_owned())
+        })?;
+
+        Ok(Value::Array(groups))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct WhereExpArgs {
+    #[parameter(
+        description = "The name each element is known by in the expression.",
+        arg_type = "str"
+    )]
+    variable: Expression,
+
+    #[parameter(
+        description = "The condition elements must meet, like in `{% if %}`.",
+        arg_type = "str"
+    )]
+    condition: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "where_exp",
+    description = "Filter the elements of an array to those meeting a condition, like `where_exp: \"item\", \"item.price > 100\"`.",
+    parameters(WhereExpArgs),
+    parsed(WhereExpFilter)
+)]
+pub struct WhereExp;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "where_exp"]
+struct WhereExpFilter {
+    #[parameters]
+    args: WhereExpArgs,
+}
+
+impl Filter for WhereExpFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let variable = expect_variable_name(args.variable.as_str())?;
+        let condition = parse_exp_condition(args.condition.as_str())?;
+
+        let elements = match elements(input) {
+            Some(elements) => elements,
+            None => return Ok(input.to_value()),
+        };
+
+        let mut matches = Vec::new();
+        for element in elements {
+            if satisfies(&condition, variable, element, runtime)? {
+                matches.push(element.to_value());
+            }
+        }
+
+        Ok(Value::Array(matches))
     }
 }
 
@@ -195,6 +482,10 @@ impl Filter for ArrayToSentenceStringFilter {
 mod tests {
     use super::*;
 
+    use crate::stdlib;
+
+    use liquid_core::{Language, Renderable, Template};
+
     #[test]
     fn unit_push() {
         let input = liquid_core::value!(["Seattle", "Tacoma"]);
@@ -219,6 +510,18 @@ mod tests {
         assert_eq!(unit_result, desired_result);
     }
 
+    #[test]
+    fn unit_pop_count() {
+        let input = liquid_core::value!(["Seattle", "Tacoma", "Spokane"]);
+        let unit_result = liquid_core::call_filter!(Pop, input.clone(), 2).unwrap();
+        assert_eq!(unit_result, liquid_core::value!(["Seattle"]));
+        let unit_result = liquid_core::call_filter!(Pop, input.clone(), 5).unwrap();
+        assert_eq!(unit_result, liquid_core::value!([]));
+        let unit_result = liquid_core::call_filter!(Pop, input.clone(), 0).unwrap();
+        assert_eq!(unit_result, input);
+        liquid_core::call_filter!(Pop, input, -1).unwrap_err();
+    }
+
     #[test]
     fn unit_unshift() {
         let input = liquid_core::value!(["Seattle", "Tacoma"]);
@@ -243,6 +546,105 @@ mod tests {
         assert_eq!(unit_result, desired_result);
     }
 
+    #[test]
+    fn unit_shift_count() {
+        let input = liquid_core::value!(["Seattle", "Tacoma", "Spokane"]);
+        let unit_result = liquid_core::call_filter!(Shift, input.clone(), 2).unwrap();
+        assert_eq!(unit_result, liquid_core::value!(["Spokane"]));
+        let unit_result = liquid_core::call_filter!(Shift, input.clone(), 5).unwrap();
+        assert_eq!(unit_result, liquid_core::value!([]));
+        liquid_core::call_filter!(Shift, input, -1).unwrap_err();
+    }
+
+    #[test]
+    fn unit_array_filters_copy_input() {
+        let mut language = Language::empty();
+        language.filters.register("push".to_owned(), Box::new(Push));
+        language.filters.register("pop".to_owned(), Box::new(Pop));
+        language
+            .filters
+            .register("join".to_owned(), Box::new(stdlib::Join));
+        let template = parser::parse(
+            "{{ cities | push: 'Spokane' | join: ',' }};{{ cities | pop | join: ',' }};{{ cities | join: ',' }}",
+            &language,
+        )
+        .map(Template::new)
+        .unwrap();
+
+        let runtime = liquid_core::runtime::RuntimeBuilder::new().build();
+        runtime.set_global("cities".into(), liquid_core::value!(["Seattle", "Tacoma"]));
+        assert_eq!(
+            template.render(&runtime).unwrap(),
+            "Seattle,Tacoma,Spokane;Seattle;Seattle,Tacoma"
+        );
+    }
+
+    #[test]
+    fn unit_sample() {
+        let input = liquid_core::value!([1, 2, 3, 4, 5]);
+        let unit_result = liquid_core::call_filter!(Sample, input.clone()).unwrap();
+        assert!(input
+            .as_array()
+            .unwrap()
+            .values()
+            .any(|v| v.to_value() == unit_result));
+
+        let unit_result = liquid_core::call_filter!(Sample, input.clone(), 3).unwrap();
+        let mut picks: Vec<_> = unit_result
+            .as_array()
+            .unwrap()
+            .values()
+            .map(|v| v.as_scalar().unwrap().to_integer().unwrap())
+            .collect();
+        assert_eq!(picks.len(), 3);
+        picks.sort_unstable();
+        picks.dedup();
+        assert_eq!(picks.len(), 3);
+        assert!(picks.iter().all(|n| (1..=5).contains(n)));
+
+        let unit_result = liquid_core::call_filter!(Sample, input, 10).unwrap();
+        assert_eq!(unit_result.as_array().unwrap().size(), 5);
+    }
+
+    #[test]
+    fn unit_sample_edge_cases() {
+        assert_eq!(
+            liquid_core::call_filter!(Sample, liquid_core::value!([])).unwrap(),
+            liquid_core::Value::Nil
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sample, liquid_core::value!([1]), 0).unwrap(),
+            liquid_core::value!([])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sample, "abc").unwrap(),
+            liquid_core::value!("abc")
+        );
+        liquid_core::call_filter!(Sample, liquid_core::value!([1]), -1).unwrap_err();
+    }
+
+    #[test]
+    fn unit_sample_seeded() {
+        use liquid_core::runtime::RuntimeBuilder;
+
+        let input = liquid_core::value!(["a", "b", "c", "d", "e", "f", "g", "h"]);
+        let filter = SampleFilter {
+            args: SampleArgs {
+                count: Some(Expression::with_literal(4)),
+            },
+        };
+        let sample = |seed| {
+            let runtime = RuntimeBuilder::new().build();
+            runtime
+                .registers()
+                .get_mut::<RandomRegister>()
+                .set_seed(seed);
+            filter.evaluate(&input, &runtime).unwrap()
+        };
+        assert_eq!(sample(7), sample(7));
+        assert_ne!(sample(7), sample(8));
+    }
+
     #[test]
     fn unit_array_to_sentence_string() {
         let input = liquid_core::value!(["foo", "bar", "baz"]);
@@ -255,7 +657,7 @@ mod tests {
     fn unit_array_to_sentence_string_two_elements() {
         let input = liquid_core::value!(["foo", "bar"]);
         let unit_result = liquid_core::call_filter!(ArrayToSentenceString, input).unwrap();
-        let desired_result = "foo, and bar";
+        let desired_result = "foo and bar";
         assert_eq!(unit_result, desired_result);
     }
 
@@ -282,4 +684,176 @@ mod tests {
         let desired_result = "foo, bar, or baz";
         assert_eq!(unit_result, desired_result);
     }
+
+    #[test]
+    fn unit_array_to_sentence_string_mixed_elements() {
+        let input = liquid_core::value!([1, 2.5, true, nil, "x"]);
+        let unit_result = liquid_core::call_filter!(ArrayToSentenceString, input, "&").unwrap();
+        let desired_result = "1, 2.5, true, , & x";
+        assert_eq!(unit_result, desired_result);
+
+        let input = liquid_core::value!(["foo", "bar"]);
+        let unit_result = liquid_core::call_filter!(ArrayToSentenceString, input, "or").unwrap();
+        let desired_result = "foo or bar";
+        assert_eq!(unit_result, desired_result);
+    }
+
+    #[test]
+    fn unit_where_exp() {
+        let input = liquid_core::value!([
+            { "title": "a", "price": 50, "tags": ["sale"] },
+            { "title": "b", "price": 150, "tags": ["sale"] },
+            { "title": "c", "price": 250, "tags": [] },
+        ]);
+        let unit_result =
+            liquid_core::call_filter!(WhereExp, input, "item", "item.price > 100").unwrap();
+        let desired_result = liquid_core::value!([
+            { "title": "b", "price": 150, "tags": ["sale"] },
+            { "title": "c", "price": 250, "tags": [] },
+        ]);
+        assert_eq!(unit_result, desired_result);
+
+        let unit_result = liquid_core::call_filter!(
+            WhereExp,
+            input,
+            "p",
+            "p.price > 100 and p.tags contains 'sale'"
+        )
+        .unwrap();
+        let desired_result =
+            liquid_core::value!([{ "title": "b", "price": 150, "tags": ["sale"] }]);
+        assert_eq!(unit_result, desired_result);
+    }
+
+    #[test]
+    fn unit_where_exp_scalars() {
+        let input = liquid_core::value!([1, 2, 3, 4]);
+        let unit_result = liquid_core::call_filter!(WhereExp, input, "n", "n >= 3").unwrap();
+        assert_eq!(unit_result, liquid_core::value!([3, 4]));
+
+        let unit_result = liquid_core::call_filter!(WhereExp, 5, "n", "n >= 3").unwrap();
+        assert_eq!(unit_result, liquid_core::value!(5));
+    }
+
+    #[test]
+    fn unit_where_exp_invalid() {
+        let input = liquid_core::value!([1, 2]);
+        liquid_core::call_filter!(WhereExp, input, "n.x", "n").unwrap_err();
+        liquid_core::call_filter!(WhereExp, input, "n", "n >").unwrap_err();
+        liquid_core::call_filter!(WhereExp, input, "n", "n %}{% if n").unwrap_err();
+        liquid_core::call_filter!(WhereExp, input, "n", "n %}true{% endif").unwrap_err();
+        liquid_core::call_filter!(WhereExp, input, "n", "n }}{{ n").unwrap_err();
+        liquid_core::call_filter!(WhereExp, input, "n", "").unwrap_err();
+    }
+
+    #[test]
+    fn unit_group_by() {
+        let input = liquid_core::value!([
+            { "title": "a", "category": "news" },
+            { "title": "b", "category": "blog" },
+            { "title": "c", "category": "news" },
+            { "title": "d" },
+        ]);
+        let unit_result = liquid_core::call_filter!(GroupBy, input, "category").unwrap();
+        let desired_result = liquid_core::value!([
+            {
+                "name": "news",
+                "items": [
+                    { "title": "a", "category": "news" },
+                    { "title": "c", "category": "news" },
+                ],
+                "size": 2,
+            },
+            { "name": "blog", "items": [{ "title": "b", "category": "blog" }], "size": 1 },
+            { "name": "", "items": [{ "title": "d" }], "size": 1 },
+        ]);
+        assert_eq!(unit_result, desired_result);
+    }
+
+    #[test]
+    fn unit_group_by_numbers() {
+        let input = liquid_core::value!([{ "year": 2020 }, { "year": 2021 }, { "year": 2020 }]);
+        let unit_result = liquid_core::call_filter!(GroupBy, input, "year").unwrap();
+        let desired_result = liquid_core::value!([
+            { "name": "2020", "items": [{ "year": 2020 }, { "year": 2020 }], "size": 2 },
+            { "name": "2021", "items": [{ "year": 2021 }], "size": 1 },
+        ]);
+        assert_eq!(unit_result, desired_result);
+
+        liquid_core::call_filter!(GroupBy, 5, "year").unwrap_err();
+    }
+
+    #[test]
+    fn unit_group_by_exp() {
+        use liquid_core::runtime::RuntimeBuilder;
+
+        let input = liquid_core::value!([
+            { "title": "a", "date": "2020-01-05" },
+            { "title": "b", "date": "2021-03-01" },
+            { "title": "c", "date": "2020-12-31" },
+        ]);
+        let mut language = Language::empty();
+        language
+            .filters
+            .register("date".to_owned(), Box::new(stdlib::Date));
+        let runtime = RuntimeBuilder::new().build();
+        runtime
+            .registers()
+            .get_mut::<LanguageRegister>()
+            .set_language(std::sync::Arc::new(language));
+
+        let filter = GroupByExpFilter {
+            args: GroupByExpArgs {
+                variable: Expression::with_literal("post"),
+                expression: Expression::with_literal("post.date | date: '%Y'"),
+            },
+        };
+        let desired_result = liquid_core::value!([
+            {
+                "name": "2020",
+                "items": [
+                    { "title": "a", "date": "2020-01-05" },
+                    { "title": "c", "date": "2020-12-31" },
+                ],
+                "size": 2,
+            },
+            { "name": "2021", "items": [{ "title": "b", "date": "2021-03-01" }], "size": 1 },
+        ]);
+        assert_eq!(filter.evaluate(&input, &runtime).unwrap(), desired_result);
+    }
+
+    #[test]
+    fn unit_group_by_exp_without_filters() {
+        use liquid_core::runtime::RuntimeBuilder;
+
+        let input = liquid_core::value!([1, 2, 3, 4]);
+        let filter = |expression: &str| GroupByExpFilter {
+            args: GroupByExpArgs {
+                variable: Expression::with_literal("n"),
+                expression: Expression::with_literal(expression.to_owned()),
+            },
+        };
+
+        // The expression can't be compiled without the template's language
+        let runtime = RuntimeBuilder::new().build();
+        filter("n % 2").evaluate(&input, &runtime).unwrap_err();
+
+        runtime
+            .registers()
+            .get_mut::<LanguageRegister>()
+            .set_language(std::sync::Arc::new(Language::empty()));
+        let desired_result = liquid_core::value!([
+            { "name": "1", "items": [1, 3], "size": 2 },
+            { "name": "0", "items": [2, 4], "size": 2 },
+        ]);
+        assert_eq!(
+            filter("n % 2").evaluate(&input, &runtime).unwrap(),
+            desired_result
+        );
+        filter("n | date: '%Y'")
+            .evaluate(&input, &runtime)
+            .unwrap_err();
+
+        liquid_core::call_filter!(GroupByExp, input, "n.x", "n").unwrap_err();
+    }
 }
@@ -0,0 +1,169 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::error::ResultLiquidReplaceExt;
+use liquid_core::parser::SyntaxHighlighter;
+use liquid_core::parser::TryMatchToken;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{BlockReflection, ParseBlock, TagBlock, TagTokenIter};
+
+use crate::stdlib::escape_html;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct HighlightBlock;
+
+impl HighlightBlock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BlockReflection for HighlightBlock {
+    fn start_tag(&self) -> &str {
+        "highlight"
+    }
+
+    fn end_tag(&self) -> &str {
+        "endhighlight"
+    }
+
+    fn description(&self) -> &str {
+        "render the raw content as code in the given language, using the configured highlighter"
+    }
+}
+
+impl ParseBlock for HighlightBlock {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        mut tokens: TagBlock<'_, '_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let language = arguments.expect_next("Language expected.")?;
+        let language = match language.expect_identifier() {
+            TryMatchToken::Matches(language) => language.to_owned(),
+            TryMatchToken::Fails(language) => language.as_str().to_owned(),
+        };
+        let is_valid = |c: char| c.is_ascii_alphanumeric() || "+#._-".contains(c);
+        if !language.chars().all(is_valid) {
+            return Err(arguments.raise_error("Invalid language"));
+        }
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        let code = tokens.escape_liquid(false)?.to_owned();
+
+        tokens.assert_empty();
+        Ok(Box::new(Highlight {
+            language,
+            code,
+            highlighter: options.highlighter.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Highlight {
+    language: String,
+    code: String,
+    highlighter: Option<Arc<dyn SyntaxHighlighter>>,
+}
+
+impl Renderable for Highlight {
+    fn render_to(&self, writer: &mut dyn Write, _runtime: &dyn Runtime) -> Result<()> {
+        match self.highlighter {
+            Some(ref highlighter) => {
+                let html = highlighter
+                    .highlight(&self.code, &self.language)
+                    .trace_with(|| format!("{{% highlight {} %}}", self.language).into())?;
+                write!(writer, "{}", html).replace("Failed to render")?;
+            }
+            None => {
+                write!(
+                    writer,
+                    "<pre><code class=\"language-{}\">{}</code></pre>",
+                    self.language,
+                    escape_html(self.code.trim_matches('\n'), false)
+                )
+                .replace("Failed to render")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+    use liquid_core::Error;
+
+    #[derive(Debug)]
+    struct Upper;
+
+    impl SyntaxHighlighter for Upper {
+        fn highlight(&self, code: &str, language: &str) -> Result<String> {
+            if language == "nope" {
+                return Err(Error::with_msg("Unknown language"));
+            }
+            Ok(format!("{}:{}", language, code.to_uppercase()))
+        }
+    }
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .blocks
+            .register("highlight".to_string(), HighlightBlock.into());
+        options
+    }
+
+    fn render(text: &str, options: &Language) -> Result<String> {
+        let template = parser::parse(text, options).map(runtime::Template::new)?;
+        let runtime = RuntimeBuilder::new().build();
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn highlight_default() {
+        let text = "{% highlight ruby %}\nputs \"<{{ a }}>\" if a && b\n{% endhighlight %}";
+        assert_eq!(
+            render(text, &options()).unwrap(),
+            "<pre><code class=\"language-ruby\">puts &quot;&lt;{{ a }}&gt;&quot; if a &amp;&amp; b</code></pre>"
+        );
+    }
+
+    #[test]
+    fn highlight_with_highlighter() {
+        let mut options = options();
+        options.highlighter = Some(Arc::new(Upper));
+        let text = "{% highlight rust %}let {{ x }};{% endhighlight %}";
+        assert_eq!(render(text, &options).unwrap(), "rust:LET {{ X }};");
+
+        let text = "{% highlight nope %}x{% endhighlight %}";
+        assert!(render(text, &options).is_err());
+    }
+
+    #[test]
+    fn highlight_requires_language() {
+        for text in [
+            "{% highlight %}x{% endhighlight %}",
+            "{% highlight ruby python %}x{% endhighlight %}",
+            "{% highlight 'ruby' %}x{% endhighlight %}",
+        ] {
+            assert!(parser::parse(text, &options()).is_err(), "{}", text);
+        }
+    }
+}
@@ -33,42 +33,43 @@ impl TagReflection for IncludeTag {
 impl ParseTag for IncludeTag {
     fn parse(
         &self,
-        mut arguments: TagTokenIter<'_>,
+        arguments: TagTokenIter<'_>,
         _options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        let name = arguments.expect_next("Identifier or literal expected.")?;
-
-        // This may accept strange inputs such as `{% include 0 %}` or `{% include filterchain | filter:0 %}`.
-        // Those inputs would fail anyway by there being not a path with those names so they are not a big concern.
-        let name = match name.expect_identifier() {
-            // Using `to_kstr()` on literals ensures `Strings` will have their quotes trimmed.
-            TryMatchToken::Matches(name) => name.to_kstr().to_string(),
-            TryMatchToken::Fails(name) => name.as_str().to_string(),
-        };
+        parse_include(arguments, false)
+    }
 
-        let partial = Expression::with_literal(name);
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
 
-        let mut vars: Vec<(KString, Expression)> = Vec::new();
-        while let Ok(next) = arguments.expect_next("") {
-            let id = next.expect_identifier().into_result()?.to_string();
+#[derive(Copy, Clone, Debug, Default)]
+pub struct IncludeRelativeTag;
 
-            arguments
-                .expect_next("\"=\" expected.")?
-                .expect_str("=")
-                .into_result_custom_msg("expected \"=\" to be used for the assignment")?;
-
-            vars.push((
-                id.into(),
-                arguments
-                    .expect_next("expected value")?
-                    .expect_value()
-                    .into_result()?,
-            ));
-        }
+impl IncludeRelativeTag {
+    pub fn new() -> Self {
+        Self
+    }
+}
 
-        arguments.expect_nothing()?;
+impl TagReflection for IncludeRelativeTag {
+    fn tag(&self) -> &'static str {
+        "include_relative"
+    }
+
+    fn description(&self) -> &'static str {
+        "include a template found relative to the current one, rather than the partials' root"
+    }
+}
 
-        Ok(Box::new(Include { partial, vars }))
+impl ParseTag for IncludeRelativeTag {
+    fn parse(
+        &self,
+        arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        parse_include(arguments, true)
     }
 
     fn reflection(&self) -> &dyn TagReflection {
@@ -76,15 +77,92 @@ impl ParseTag for IncludeTag {
     }
 }
 
+fn parse_include(mut arguments: TagTokenIter<'_>, relative: bool) -> Result<Box<dyn Renderable>> {
+    let name = arguments.expect_next("Identifier or literal expected.")?;
+
+    // This may accept strange inputs such as `{% include 0 %}` or `{% include filterchain | filter:0 %}`.
+    // Those inputs would fail anyway by there being not a path with those names so they are not a big concern.
+    let name = match name.expect_identifier() {
+        // Using `to_kstr()` on literals ensures `Strings` will have their quotes trimmed.
+        TryMatchToken::Matches(name) => name.to_kstr().to_string(),
+        TryMatchToken::Fails(name) => name.as_str().to_string(),
+    };
+
+    let partial = Expression::with_literal(name);
+
+    let mut vars: Vec<(KString, Expression)> = Vec::new();
+    while let Ok(next) = arguments.expect_next("") {
+        let id = next.expect_identifier().into_result()?.to_string();
+
+        arguments
+            .expect_next("\"=\" expected.")?
+            .expect_str("=")
+            .into_result_custom_msg("expected \"=\" to be used for the assignment")?;
+
+        vars.push((
+            id.into(),
+            arguments
+                .expect_next("expected value")?
+                .expect_value()
+                .into_result()?,
+        ));
+    }
+
+    arguments.expect_nothing()?;
+
+    Ok(Box::new(Include {
+        partial,
+        vars,
+        relative,
+    }))
+}
+
+/// Resolve `name` against the directory of the template `from`, both being paths within the
+/// partials.
+fn resolve_relative(from: Option<&str>, name: &str) -> Result<String> {
+    let is_separator = |c: char| c == '/' || c == '\\';
+    if name.starts_with(is_separator) {
+        return Error::with_msg("Relative includes can't be absolute paths")
+            .context("partial", name.to_owned())
+            .into_err();
+    }
+    let dir = from
+        .and_then(|from| from.rsplit_once(is_separator))
+        .map(|(dir, _)| dir);
+    let mut path: Vec<&str> = dir
+        .into_iter()
+        .flat_map(|dir| dir.split(is_separator))
+        .filter(|s| !s.is_empty())
+        .collect();
+    for segment in name.split(is_separator) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                return Error::with_msg("Relative includes can't leave the including directory")
+                    .context("partial", name.to_owned())
+                    .into_err();
+            }
+            segment => path.push(segment),
+        }
+    }
+    Ok(path.join("/"))
+}
+
 #[derive(Debug)]
 struct Include {
     partial: Expression,
     vars: Vec<(KString, Expression)>,
+    relative: bool,
 }
 
 impl Renderable for Include {
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
         let name = self.partial.evaluate(runtime)?.render().to_string();
+        let name = if self.relative {
+            resolve_relative(runtime.name().as_deref(), &name)?
+        } else {
+            name
+        };
 
         {
             let mut pass_through = std::collections::HashMap::<
@@ -105,7 +183,8 @@ impl Renderable for Include {
                 pass_through.insert("include".into(), &helper_vars);
             }
 
-            let scope = StackFrame::new(runtime, &pass_through);
+            // Name the frame so includes within the partial are relative to it.
+            let scope = StackFrame::new(runtime, &pass_through).with_name(name.clone());
             let partial = scope
                 .partials()
                 .get(&name)
@@ -155,6 +234,9 @@ mod test {
                 "example.txt" => Some(r#"{{'whooo' | size}}{%comment%}What happens{%endcomment%} {%if num < numTwo%}wat{%else%}wot{%endif%} {%if num > numTwo%}wat{%else%}wot{%endif%}"#.into()),
                 "example_var.txt" => Some(r#"{{include.example_var}}"#.into()),
                 "example_multi_var.txt" => Some(r#"{{include.example_var}} {{include.example}}"#.into()),
+                "docs/index.md" => Some(r#"{% include_relative intro.md %}"#.into()),
+                "docs/intro.md" => Some(r#"intro {% include_relative parts/a.md x=0 %}"#.into()),
+                "docs/parts/a.md" => Some(r#"a{{ include.x }}"#.into()),
                 _ => None
             }
         }
@@ -165,6 +247,9 @@ mod test {
         options
             .tags
             .register("include".to_string(), IncludeTag.into());
+        options
+            .tags
+            .register("include_relative".to_string(), IncludeRelativeTag.into());
         options
             .blocks
             .register("comment".to_string(), stdlib::CommentBlock.into());
@@ -277,4 +362,57 @@ mod test {
         let output = template.render(&runtime);
         assert!(output.is_err());
     }
+
+    #[test]
+    fn include_relative() {
+        let text = "{% include docs/index.md %} {% include_relative parts/a.md x=1 %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let globals = liquid_core::Object::new();
+        let runtime = StackFrame::new(&runtime, &globals).with_name("docs/page.md");
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "intro a0 a1");
+    }
+
+    #[test]
+    fn include_relative_without_name() {
+        let text = "{% include_relative docs/intro.md %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "intro a0");
+    }
+
+    #[test]
+    fn include_relative_parent_dir() {
+        assert_eq!(
+            resolve_relative(Some("a/b/c.md"), "./d/e.md").unwrap(),
+            "a/b/d/e.md"
+        );
+        assert_eq!(resolve_relative(Some("c.md"), "e.md").unwrap(), "e.md");
+        assert!(resolve_relative(Some("a/b/c.md"), "../e.md").is_err());
+        assert!(resolve_relative(Some("a/b/c.md"), "/e.md").is_err());
+        assert_eq!(
+            resolve_relative(Some("a\\b\\c.md"), "d\\e.md").unwrap(),
+            "a/b/d/e.md"
+        );
+    }
 }
@@ -0,0 +1,124 @@
+#[cfg(feature = "markdown")]
+use liquid_core::parser::MarkdownRenderer;
+use liquid_core::runtime::LanguageRegister;
+#[cfg(not(feature = "markdown"))]
+use liquid_core::Error;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+/// Renders a string as Markdown, like `{{ "Hello **world**" | markdownify }}`.
+///
+/// The HTML comes from the [`MarkdownRenderer`][liquid_core::parser::MarkdownRenderer] set on the
+/// template's [`Language`][liquid_core::Language].  When there is none, the `markdown` feature
+/// falls back to [`CommonMark`]; without it, rendering fails.
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "markdownify",
+    description = "Converts a Markdown-formatted string into HTML, using the configured renderer.",
+    parsed(MarkdownifyFilter)
+)]
+pub struct Markdownify;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "markdownify"]
+struct MarkdownifyFilter;
+
+impl Filter for MarkdownifyFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let renderer = runtime
+            .registers()
+            .get_mut::<LanguageRegister>()
+            .language()
+            .and_then(|language| language.markdown.clone());
+
+        let markdown = input.to_kstr();
+        let html = match renderer {
+            Some(renderer) => renderer.render(markdown.as_str())?,
+            #[cfg(feature = "markdown")]
+            None => CommonMark.render(markdown.as_str())?,
+            #[cfg(not(feature = "markdown"))]
+            None => return Err(Error::with_msg("No Markdown renderer configured")),
+        };
+        Ok(Value::scalar(html))
+    }
+}
+
+/// Renders [CommonMark](https://commonmark.org) with `pulldown-cmark`.
+#[cfg(feature = "markdown")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CommonMark;
+
+#[cfg(feature = "markdown")]
+impl MarkdownRenderer for CommonMark {
+    fn render(&self, markdown: &str) -> Result<String> {
+        let parser = pulldown_cmark::Parser::new(markdown);
+        let mut html = String::with_capacity(markdown.len() * 3 / 2);
+        pulldown_cmark::html::push_html(&mut html, parser);
+        Ok(html)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use liquid_core::parser::MarkdownRenderer;
+    use liquid_core::runtime::RuntimeBuilder;
+    use liquid_core::{Error, Language};
+
+    #[derive(Debug)]
+    struct Emphasis;
+
+    impl MarkdownRenderer for Emphasis {
+        fn render(&self, markdown: &str) -> Result<String> {
+            if markdown.contains('<') {
+                return Err(Error::with_msg("Raw HTML not allowed"));
+            }
+            Ok(format!("<p>{}</p>\n", markdown.replace("**", "*")))
+        }
+    }
+
+    #[test]
+    fn unit_markdownify() {
+        let mut language = Language::empty();
+        language.markdown = Some(Arc::new(Emphasis));
+        let runtime = RuntimeBuilder::new().build();
+        runtime
+            .registers()
+            .get_mut::<LanguageRegister>()
+            .set_language(Arc::new(language));
+
+        assert_eq!(
+            MarkdownifyFilter
+                .evaluate(&Value::scalar("Hello **world**"), &runtime)
+                .unwrap(),
+            liquid_core::value!("<p>Hello *world*</p>\n")
+        );
+        assert_eq!(
+            MarkdownifyFilter.evaluate(&Value::Nil, &runtime).unwrap(),
+            liquid_core::value!("<p></p>\n")
+        );
+        MarkdownifyFilter
+            .evaluate(&Value::scalar("<b>hi</b>"), &runtime)
+            .unwrap_err();
+    }
+
+    #[cfg(not(feature = "markdown"))]
+    #[test]
+    fn unit_markdownify_without_renderer() {
+        liquid_core::call_filter!(Markdownify, "Hello **world**").unwrap_err();
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn unit_markdownify_common_mark() {
+        assert_eq!(
+            liquid_core::call_filter!(Markdownify, "Hello **world**").unwrap(),
+            liquid_core::value!("<p>Hello <strong>world</strong></p>\n")
+        );
+    }
+}
@@ -1,7 +1,17 @@
 mod array;
+mod highlight_block;
 mod include_tag;
+mod markdownify;
 mod slugify;
+mod string;
+mod url;
+mod xml;
 
 pub use self::array::*;
+pub use self::highlight_block::*;
 pub use self::include_tag::*;
+pub use self::markdownify::*;
 pub use self::slugify::*;
+pub use self::string::*;
+pub use self::url::*;
+pub use self::xml::*;
@@ -25,17 +25,19 @@ impl SlugifyMode {
             "pretty" => SlugifyMode::Pretty,
             "ascii" => SlugifyMode::Ascii,
             "latin" => SlugifyMode::Latin,
-            _ => SlugifyMode::Def,
+            "default" => SlugifyMode::Def,
+            // Like Jekyll, an unknown mode leaves the string as-is, apart from its case.
+            _ => SlugifyMode::No,
         }
     }
 }
 
 static SLUG_INVALID_CHARS_DEFAULT: once_cell::sync::Lazy<Regex> =
-    once_cell::sync::Lazy::new(|| Regex::new(r"([^0-9\p{Alphabetic}]+)").unwrap());
+    once_cell::sync::Lazy::new(|| Regex::new(r"([^\p{M}\p{L}\p{Nd}]+)").unwrap());
 static SLUG_INVALID_CHARS_RAW: once_cell::sync::Lazy<Regex> =
     once_cell::sync::Lazy::new(|| Regex::new(r"([\s]+)").unwrap());
 static SLUG_INVALID_CHARS_PRETTY: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
-    Regex::new(r"([^\p{Alphabetic}0-9\._\~!\$&'\(\)\+,;=@]+)").unwrap()
+    Regex::new(r"([^\p{M}\p{L}\p{Nd}\._\~!\$&'\(\)\+,;=@]+)").unwrap()
 });
 static SLUG_INVALID_CHARS_ASCII: once_cell::sync::Lazy<Regex> =
     once_cell::sync::Lazy::new(|| Regex::new(r"([^a-zA-Z0-9]+)").unwrap());
@@ -69,20 +71,28 @@ impl Filter for SlugifyFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
         let s = input.to_kstr();
         let mode = args
             .mode
             .map(|mode| SlugifyMode::new(mode.as_str()))
             .unwrap_or(SlugifyMode::Def);
 
+        if mode == SlugifyMode::No {
+            return Ok(Value::scalar(s.to_lowercase()));
+        }
+
         let s = if mode == SlugifyMode::Latin {
-            deunicode::deunicode_with_tofu(s.trim(), "-")
+            deunicode::deunicode_with_tofu(&s, "-")
         } else {
-            s.trim().to_string()
+            s.into_string()
         };
 
         let result = match mode {
-            SlugifyMode::No => s,
+            SlugifyMode::No => unreachable!("handled above"),
             SlugifyMode::Def => SLUG_INVALID_CHARS_DEFAULT.replace_all(&s, "-").to_string(),
             SlugifyMode::Raw => SLUG_INVALID_CHARS_RAW.replace_all(&s, "-").to_string(),
             SlugifyMode::Pretty => SLUG_INVALID_CHARS_PRETTY.replace_all(&s, "-").to_string(),
@@ -91,7 +101,10 @@ impl Filter for SlugifyFilter {
             }
         };
 
-        Ok(Value::scalar(result.trim_matches('-').to_lowercase()))
+        // Like Jekyll, only a single hyphen is removed from either end.
+        let result = result.strip_prefix('-').unwrap_or(&result);
+        let result = result.strip_suffix('-').unwrap_or(result);
+        Ok(Value::scalar(result.to_lowercase()))
     }
 }
 
@@ -131,6 +144,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_slugify_pretty() {
+        assert_eq!(
+            liquid_core::call_filter!(Slugify, "  The _config.yml file?!", "pretty").unwrap(),
+            liquid_core::value!("the-_config.yml-file-!")
+        );
+    }
+
+    #[test]
+    fn test_slugify_marks_and_digits() {
+        // Combining marks and non-ASCII digits are kept, like Jekyll.
+        assert_eq!(
+            liquid_core::call_filter!(Slugify, "Cafe\u{301} \u{663}\u{664} Ⅻ").unwrap(),
+            liquid_core::value!("cafe\u{301}-\u{663}\u{664}")
+        );
+    }
+
+    #[test]
+    fn test_slugify_trims_hyphens() {
+        assert_eq!(
+            liquid_core::call_filter!(Slugify, " --Hello, World!-- ").unwrap(),
+            liquid_core::value!("hello-world")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slugify, "--a b--", "raw").unwrap(),
+            liquid_core::value!("-a-b-")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slugify, " a b ", "raw").unwrap(),
+            liquid_core::value!("a-b")
+        );
+    }
+
+    #[test]
+    fn test_slugify_unknown_mode() {
+        assert_eq!(
+            liquid_core::call_filter!(Slugify, " The _config.yml File ", "bogus").unwrap(),
+            liquid_core::value!(" the _config.yml file ")
+        );
+    }
+
     #[test]
     fn test_slugify_none() {
         assert_eq!(
@@ -0,0 +1,71 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+/// Ruby's `\s`, which leaves out Unicode spaces like U+00A0.
+fn is_whitespace(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\x0B' | '\x0C' | '\r' | ' ')
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "normalize_whitespace",
+    description = "Replaces any run of whitespace, including newlines, with a single space and strips leading and trailing whitespace.",
+    parsed(NormalizeWhitespaceFilter)
+)]
+pub struct NormalizeWhitespace;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "normalize_whitespace"]
+struct NormalizeWhitespaceFilter;
+
+impl Filter for NormalizeWhitespaceFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        // Like Jekyll, nil normalizes to an empty string and zero-width spaces are dropped.
+        let s = input.to_kstr();
+
+        let mut result = String::with_capacity(s.len());
+        let mut in_whitespace = false;
+        for c in s.chars() {
+            if is_whitespace(c) {
+                if !in_whitespace {
+                    result.push(' ');
+                }
+                in_whitespace = true;
+            } else {
+                if c != '\u{200B}' {
+                    result.push(c);
+                }
+                in_whitespace = false;
+            }
+        }
+        let trimmed = result.trim_matches(|c| c == '\0' || is_whitespace(c));
+        Ok(Value::scalar(trimmed.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_normalize_whitespace() {
+        assert_eq!(
+            liquid_core::call_filter!(NormalizeWhitespace, "  a\tb\n\n c \r\n\x0Bd\x0C  ").unwrap(),
+            liquid_core::value!("a b c d")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(NormalizeWhitespace, "one\u{200B}two\u{A0}three").unwrap(),
+            liquid_core::value!("onetwo\u{A0}three")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(NormalizeWhitespace, "\u{200B} a \u{200B} b").unwrap(),
+            liquid_core::value!("a  b")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(NormalizeWhitespace, liquid_core::Value::Nil).unwrap(),
+            liquid_core::value!("")
+        );
+    }
+}
@@ -0,0 +1,469 @@
+use std::fmt;
+use std::sync::Arc;
+
+use liquid_core::model::Scalar;
+use liquid_core::parser::FilterArguments;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+use crate::no_arguments;
+
+// Everything but RFC 3986's reserved and unreserved characters, like Addressable's
+// `normalize_component`.  `%` is handled separately, to keep existing escapes.
+const URI: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b':')
+    .remove(b'/')
+    .remove(b'?')
+    .remove(b'#')
+    .remove(b'[')
+    .remove(b']')
+    .remove(b'@')
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b',')
+    .remove(b';')
+    .remove(b'=');
+
+// Ruby's `CGI.escape`: spaces are left for `+`.
+const CGI: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b' ');
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "uri_escape",
+    description = "Percent-encodes any characters not allowed in a URI, leaving reserved characters and existing escapes as-is.",
+    parsed(UriEscapeFilter)
+)]
+pub struct UriEscape;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "uri_escape"]
+struct UriEscapeFilter;
+
+impl Filter for UriEscapeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
+        let s = input.to_kstr();
+
+        let mut result = String::with_capacity(s.len());
+        for (i, part) in s.as_str().split('%').enumerate() {
+            let part = if i == 0 {
+                part
+            } else {
+                match part
+                    .get(..2)
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                {
+                    Some(hex) => {
+                        result.push('%');
+                        result.push_str(&hex.to_ascii_uppercase());
+                        &part[2..]
+                    }
+                    None => {
+                        result.push_str("%25");
+                        part
+                    }
+                }
+            };
+            result.extend(percent_encoding::utf8_percent_encode(part, URI));
+        }
+        Ok(Value::scalar(result))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "cgi_escape",
+    description = "Form-encodes a string for use in a query string, replacing spaces with `+`.",
+    parsed(CgiEscapeFilter)
+)]
+pub struct CgiEscape;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "cgi_escape"]
+struct CgiEscapeFilter;
+
+impl Filter for CgiEscapeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
+        let s = input.to_kstr();
+
+        let result = percent_encoding::utf8_percent_encode(s.as_str(), CGI)
+            .collect::<String>()
+            .replace(' ', "+");
+        Ok(Value::scalar(result))
+    }
+}
+
+/// Where a site is served from, for `relative_url` and `absolute_url`.
+///
+/// Anything left unset is read from the `site` global, like Jekyll's `site.url` and
+/// `site.baseurl`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SiteUrl {
+    /// The scheme and host, like `https://example.com`.
+    pub url: Option<String>,
+    /// The path the site is served under, like `/blog`.
+    pub baseurl: Option<String>,
+}
+
+impl SiteUrl {
+    fn url(&self, runtime: &dyn Runtime) -> Option<String> {
+        self.url
+            .clone()
+            .or_else(|| site_global(runtime, "url"))
+            .filter(|url| !url.is_empty())
+    }
+
+    fn baseurl(&self, runtime: &dyn Runtime) -> Option<String> {
+        self.baseurl
+            .clone()
+            .or_else(|| site_global(runtime, "baseurl"))
+    }
+
+    fn relative_url(&self, input: &str, runtime: &dyn Runtime) -> String {
+        let mut url = String::new();
+        if let Some(baseurl) = self.baseurl(runtime) {
+            let baseurl = baseurl.trim_end_matches('/');
+            if !baseurl.is_empty() {
+                if !baseurl.starts_with('/') {
+                    url.push('/');
+                }
+                url.push_str(baseurl);
+            }
+        }
+        if !input.starts_with('/') {
+            url.push('/');
+        }
+        url.push_str(input);
+        url
+    }
+}
+
+fn site_global(runtime: &dyn Runtime, name: &'static str) -> Option<String> {
+    runtime
+        .try_get(&[Scalar::new("site"), Scalar::new(name)])
+        .filter(|value| !value.is_nil())
+        .map(|value| value.to_kstr().into_string())
+}
+
+/// The URL of a page, or anything else with a `url`, or else the input itself.
+fn input_url(input: &dyn ValueView) -> Option<String> {
+    let url = match input.as_object() {
+        Some(object) => object.get("url")?,
+        None => input,
+    };
+    if url.is_nil() {
+        return None;
+    }
+    Some(url.to_kstr().into_string())
+}
+
+/// Whether `url` has a scheme, like `https:` or `mailto:`.
+fn is_absolute(url: &str) -> bool {
+    match url.split_once(':') {
+        Some((scheme, _)) => {
+            let mut chars = scheme.chars();
+            chars.next().map(|c| c.is_ascii_alphabetic()) == Some(true)
+                && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        None => false,
+    }
+}
+
+/// Prefixes a path with the site's `baseurl`, like `{{ "/assets/style.css" | relative_url }}` to
+/// `/blog/assets/style.css`.
+#[derive(Clone, Default, FilterReflection)]
+#[filter(
+    name = "relative_url",
+    description = "Prefixes a path with the site's base URL.",
+    parsed(RelativeUrlFilter)
+)]
+pub struct RelativeUrl {
+    site: Arc<SiteUrl>,
+}
+
+impl RelativeUrl {
+    /// Prefix paths with `site`'s base URL, reading the `site` global for anything it leaves unset.
+    pub fn new(site: SiteUrl) -> Self {
+        Self {
+            site: Arc::new(site),
+        }
+    }
+}
+
+impl ParseFilter for RelativeUrl {
+    fn parse(&self, args: FilterArguments<'_>) -> Result<Box<dyn Filter>> {
+        no_arguments(args)?;
+        Ok(Box::new(RelativeUrlFilter {
+            site: self.site.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct RelativeUrlFilter {
+    site: Arc<SiteUrl>,
+}
+
+impl fmt::Display for RelativeUrlFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "relative_url")
+    }
+}
+
+impl Filter for RelativeUrlFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let input = match input_url(input) {
+            Some(input) => input,
+            None => return Ok(Value::Nil),
+        };
+        if is_absolute(&input) {
+            return Ok(Value::scalar(input));
+        }
+        Ok(Value::scalar(self.site.relative_url(&input, runtime)))
+    }
+}
+
+/// Prefixes a path with the site's `url` and `baseurl`, like
+/// `{{ "/about/" | absolute_url }}` to `https://example.com/blog/about/`.
+#[derive(Clone, Default, FilterReflection)]
+#[filter(
+    name = "absolute_url",
+    description = "Prefixes a path with the site's URL and base URL.",
+    parsed(AbsoluteUrlFilter)
+)]
+pub struct AbsoluteUrl {
+    site: Arc<SiteUrl>,
+}
+
+impl AbsoluteUrl {
+    /// Prefix paths with `site`'s URL, reading the `site` global for anything it leaves unset.
+    pub fn new(site: SiteUrl) -> Self {
+        Self {
+            site: Arc::new(site),
+        }
+    }
+}
+
+impl ParseFilter for AbsoluteUrl {
+    fn parse(&self, args: FilterArguments<'_>) -> Result<Box<dyn Filter>> {
+        no_arguments(args)?;
+        Ok(Box::new(AbsoluteUrlFilter {
+            site: self.site.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct AbsoluteUrlFilter {
+    site: Arc<SiteUrl>,
+}
+
+impl fmt::Display for AbsoluteUrlFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "absolute_url")
+    }
+}
+
+impl Filter for AbsoluteUrlFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let input = match input_url(input) {
+            Some(input) => input,
+            None => return Ok(Value::Nil),
+        };
+        if is_absolute(&input) {
+            return Ok(Value::scalar(input));
+        }
+        let path = self.site.relative_url(&input, runtime);
+        // Like Jekyll, without a `url` this is the same as `relative_url`.
+        let url = match self.site.url(runtime) {
+            Some(url) => format!("{}{}", url.trim_end_matches('/'), path),
+            None => path,
+        };
+        Ok(Value::scalar(url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_uri_escape() {
+        // Example from https://jekyllrb.com/docs/liquid/filters/
+        assert_eq!(
+            liquid_core::call_filter!(UriEscape, "foo, bar \\baz?").unwrap(),
+            liquid_core::value!("foo,%20bar%20%5Cbaz?")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(UriEscape, "/my file/ünïcode.html?a=1&b=[2]#top").unwrap(),
+            liquid_core::value!("/my%20file/%C3%BCn%C3%AFcode.html?a=1&b=[2]#top")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(UriEscape, "").unwrap(),
+            liquid_core::value!("")
+        );
+    }
+
+    #[test]
+    fn unit_uri_escape_existing_escapes() {
+        assert_eq!(
+            liquid_core::call_filter!(UriEscape, "a%20b%2fc").unwrap(),
+            liquid_core::value!("a%20b%2Fc")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(UriEscape, "100% %zz %2").unwrap(),
+            liquid_core::value!("100%25%20%25zz%20%252")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(UriEscape, "%é").unwrap(),
+            liquid_core::value!("%25%C3%A9")
+        );
+    }
+
+    #[test]
+    fn unit_cgi_escape() {
+        // Example from https://jekyllrb.com/docs/liquid/filters/
+        assert_eq!(
+            liquid_core::call_filter!(CgiEscape, "foo, bar; baz?").unwrap(),
+            liquid_core::value!("foo%2C+bar%3B+baz%3F")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(CgiEscape, "a~b*c/d é%").unwrap(),
+            liquid_core::value!("a~b%2Ac%2Fd+%C3%A9%25")
+        );
+    }
+
+    fn site(url: Option<&str>, baseurl: Option<&str>) -> SiteUrl {
+        SiteUrl {
+            url: url.map(str::to_owned),
+            baseurl: baseurl.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn unit_relative_url() {
+        let blog = RelativeUrl::new(site(None, Some("/blog")));
+        for (input, expected) in [
+            ("/assets/style.css", "/blog/assets/style.css"),
+            ("assets/style.css", "/blog/assets/style.css"),
+            ("", "/blog/"),
+            ("https://example.org/x", "https://example.org/x"),
+            ("mailto:me@example.org", "mailto:me@example.org"),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(blog.clone(), input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+        for baseurl in ["blog", "/blog/", "blog/"] {
+            assert_eq!(
+                liquid_core::call_filter!(RelativeUrl::new(site(None, Some(baseurl))), "/a")
+                    .unwrap(),
+                liquid_core::value!("/blog/a")
+            );
+        }
+        for baseurl in [Some(""), Some("/"), None] {
+            assert_eq!(
+                liquid_core::call_filter!(RelativeUrl::new(site(None, baseurl)), "a").unwrap(),
+                liquid_core::value!("/a")
+            );
+        }
+        assert_eq!(
+            liquid_core::call_filter!(blog.clone(), liquid_core::value!({ "url": "/about/" }))
+                .unwrap(),
+            liquid_core::value!("/blog/about/")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(blog.clone(), liquid_core::Value::Nil).unwrap(),
+            liquid_core::Value::Nil
+        );
+        liquid_core::call_filter!(blog, "a", "b").unwrap_err();
+    }
+
+    #[test]
+    fn unit_absolute_url() {
+        let site = AbsoluteUrl::new(site(Some("https://example.com/"), Some("/blog")));
+        for (input, expected) in [
+            ("/about/", "https://example.com/blog/about/"),
+            ("about", "https://example.com/blog/about"),
+            ("http://example.org", "http://example.org"),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(site.clone(), input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+        assert_eq!(
+            liquid_core::call_filter!(AbsoluteUrl::default(), "/about/").unwrap(),
+            liquid_core::value!("/about/")
+        );
+    }
+
+    #[test]
+    fn unit_url_filters_site_global() {
+        use liquid_core::runtime::RuntimeBuilder;
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global(
+            "site".into(),
+            liquid_core::value!({ "url": "https://example.com", "baseurl": "/docs" }),
+        );
+        let input = liquid_core::value!("/intro/");
+
+        let relative = RelativeUrlFilter {
+            site: Default::default(),
+        };
+        assert_eq!(
+            relative.evaluate(&input, &runtime).unwrap(),
+            liquid_core::value!("/docs/intro/")
+        );
+        let absolute = AbsoluteUrlFilter {
+            site: Default::default(),
+        };
+        assert_eq!(
+            absolute.evaluate(&input, &runtime).unwrap(),
+            liquid_core::value!("https://example.com/docs/intro/")
+        );
+
+        // Configured values win over the global.
+        let absolute = AbsoluteUrlFilter {
+            site: Arc::new(site(Some("https://example.org"), None)),
+        };
+        assert_eq!(
+            absolute.evaluate(&input, &runtime).unwrap(),
+            liquid_core::value!("https://example.org/docs/intro/")
+        );
+    }
+}
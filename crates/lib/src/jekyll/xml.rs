@@ -0,0 +1,72 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+use crate::stdlib::escape_html;
+
+/// Whether `c` may appear in an XML 1.0 document.
+fn is_xml_char(c: char) -> bool {
+    matches!(c,
+        '\t' | '\n' | '\r'
+        | '\u{20}'..='\u{D7FF}'
+        | '\u{E000}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{10FFFF}')
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "xml_escape",
+    description = "Escapes a string for use in XML, dropping any characters XML does not allow.",
+    parsed(XmlEscapeFilter)
+)]
+pub struct XmlEscape;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "xml_escape"]
+struct XmlEscapeFilter;
+
+impl Filter for XmlEscapeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        // Like Jekyll, nil escapes to an empty string.
+        let s: String = input
+            .to_kstr()
+            .chars()
+            .filter(|c| is_xml_char(*c))
+            .collect();
+        Ok(Value::scalar(escape_html(&s, false)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_xml_escape() {
+        assert_eq!(
+            liquid_core::call_filter!(XmlEscape, "<p>Tom & \"Jerry's\"</p>").unwrap(),
+            liquid_core::value!("&lt;p&gt;Tom &amp; &quot;Jerry&#39;s&quot;&lt;/p&gt;")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(XmlEscape, "&amp; héllo").unwrap(),
+            liquid_core::value!("&amp;amp; héllo")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(XmlEscape, liquid_core::Value::Nil).unwrap(),
+            liquid_core::value!("")
+        );
+    }
+
+    #[test]
+    fn unit_xml_escape_invalid_characters() {
+        assert_eq!(
+            liquid_core::call_filter!(
+                XmlEscape,
+                "a\u{0}b\u{8}c\u{B}\u{1F}d\te\r\nf\u{FFFE}\u{FFFF}"
+            )
+            .unwrap(),
+            liquid_core::value!("abcd\te\r\nf")
+        );
+    }
+}
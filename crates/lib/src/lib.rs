@@ -19,6 +19,44 @@ where
     Error::with_msg("Invalid input").context("cause", cause)
 }
 
+/// Append the digits of `whole`, with `separator` between each group of three.
+#[cfg(any(feature = "extra", feature = "shopify"))]
+pub(crate) fn push_grouped_digits(formatted: &mut String, whole: &str, separator: &str) {
+    for (i, digit) in whole.chars().enumerate() {
+        if 0 < i && (whole.len() - i) % 3 == 0 {
+            formatted.push_str(separator);
+        }
+        formatted.push(digit);
+    }
+}
+
+/// Reject the positional arguments given to a filter that takes none.
+#[cfg(any(feature = "jekyll", feature = "shopify"))]
+pub(crate) fn no_positional_arguments(
+    args: &mut liquid_core::parser::FilterArguments<'_>,
+) -> liquid_core::Result<()> {
+    if args.positional.next().is_some() {
+        return Err(Error::with_msg("Invalid number of positional arguments")
+            .context("cause", "expected at most 0 positional arguments"));
+    }
+    Ok(())
+}
+
+/// Reject any arguments given to a filter that takes none.
+#[cfg(any(feature = "jekyll", feature = "shopify"))]
+pub(crate) fn no_arguments(
+    mut args: liquid_core::parser::FilterArguments<'_>,
+) -> liquid_core::Result<()> {
+    no_positional_arguments(&mut args)?;
+    if let Some(arg) = args.keyword.next() {
+        return Err(Error::with_msg(format!(
+            "Unexpected named argument `{}`",
+            arg.0
+        )));
+    }
+    Ok(())
+}
+
 pub(crate) fn invalid_argument<S>(argument: S, cause: S) -> Error
 where
     S: Into<liquid_core::model::KString>,
@@ -0,0 +1,208 @@
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+use crate::invalid_input;
+
+// shopify-specific
+
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+
+/// URL-safe Base64, padded when encoding but with padding optional when decoding.
+const URL_SAFE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Encode the raw data of bytes, or else the text of `input`.
+fn encode_filter(input: &dyn ValueView, engine: &GeneralPurpose) -> Value {
+    let scalar = input.as_scalar();
+    let encoded = match scalar.as_ref().and_then(|s| s.as_bytes()) {
+        Some(bytes) => engine.encode(bytes),
+        None => engine.encode(input.to_kstr().as_bytes()),
+    };
+    Value::scalar(encoded)
+}
+
+fn decode_filter(input: &dyn ValueView, engine: &GeneralPurpose) -> Result<Value> {
+    let input = input.to_kstr();
+    let decoded = engine
+        .decode(input.as_str())
+        .map_err(|_| invalid_input("Invalid base64"))?;
+    let decoded = String::from_utf8(decoded).map_err(|_| invalid_input("Malformed UTF-8"))?;
+    Ok(Value::scalar(decoded))
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "base64_encode",
+    description = "Encodes a string in Base64.",
+    parsed(Base64EncodeFilter)
+)]
+pub struct Base64Encode;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "base64_encode"]
+struct Base64EncodeFilter;
+
+impl Filter for Base64EncodeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        Ok(encode_filter(input, &STANDARD))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "base64_decode",
+    description = "Decodes a string encoded in Base64.",
+    parsed(Base64DecodeFilter)
+)]
+pub struct Base64Decode;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "base64_decode"]
+struct Base64DecodeFilter;
+
+impl Filter for Base64DecodeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        decode_filter(input, &STANDARD)
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "base64_url_safe_encode",
+    description = "Encodes a string in URL-safe Base64, using `-` and `_` rather than `+` and `/`.",
+    parsed(Base64UrlSafeEncodeFilter)
+)]
+pub struct Base64UrlSafeEncode;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "base64_url_safe_encode"]
+struct Base64UrlSafeEncodeFilter;
+
+impl Filter for Base64UrlSafeEncodeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        Ok(encode_filter(input, &URL_SAFE))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "base64_url_safe_decode",
+    description = "Decodes a string encoded in URL-safe Base64, with or without padding.",
+    parsed(Base64UrlSafeDecodeFilter)
+)]
+pub struct Base64UrlSafeDecode;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "base64_url_safe_decode"]
+struct Base64UrlSafeDecodeFilter;
+
+impl Filter for Base64UrlSafeDecodeFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        decode_filter(input, &URL_SAFE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_base64_encode() {
+        assert_eq!(
+            liquid_core::call_filter!(Base64Encode, "one two three").unwrap(),
+            liquid_core::value!("b25lIHR3byB0aHJlZQ==")
+        );
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(Base64Encode, input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn unit_base64_encode_bytes() {
+        let bytes = Value::scalar(liquid_core::model::Bytes::from(vec![0xff, 0xfe]));
+        assert_eq!(
+            liquid_core::call_filter!(Base64Encode, bytes.clone()).unwrap(),
+            liquid_core::value!("//4=")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Base64UrlSafeEncode, bytes).unwrap(),
+            liquid_core::value!("__4=")
+        );
+    }
+
+    #[test]
+    fn unit_base64_decode() {
+        assert_eq!(
+            liquid_core::call_filter!(Base64Decode, "b25lIHR3byB0aHJlZQ==").unwrap(),
+            liquid_core::value!("one two three")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Base64Decode, "").unwrap(),
+            liquid_core::value!("")
+        );
+        for input in [
+            "invalidbase64",
+            "Zg",
+            "Zg=",
+            "Zh==",
+            "Z===",
+            "Zm9v_w==",
+            "/w==",
+        ] {
+            liquid_core::call_filter!(Base64Decode, input).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn unit_base64_url_safe_encode() {
+        assert_eq!(
+            liquid_core::call_filter!(
+                Base64UrlSafeEncode,
+                "abcdefghijklmnopqrstuvwxyz ABCDEFGHIJKLMNOPQRSTUVWXYZ 1234567890 !@#$%^&*()-=_+/?.:;[]{}\\|"
+            )
+            .unwrap(),
+            liquid_core::value!("YWJjZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXogQUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVogMTIzNDU2Nzg5MCAhQCMkJV4mKigpLT1fKy8_Ljo7W117fVx8")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Base64UrlSafeEncode, "??>").unwrap(),
+            liquid_core::value!("Pz8-")
+        );
+    }
+
+    #[test]
+    fn unit_base64_url_safe_decode() {
+        assert_eq!(
+            liquid_core::call_filter!(
+                Base64UrlSafeDecode,
+                "YWJjZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXogQUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVogMTIzNDU2Nzg5MCAhQCMkJV4mKigpLT1fKy8_Ljo7W117fVx8"
+            )
+            .unwrap(),
+            liquid_core::value!("abcdefghijklmnopqrstuvwxyz ABCDEFGHIJKLMNOPQRSTUVWXYZ 1234567890 !@#$%^&*()-=_+/?.:;[]{}\\|")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Base64UrlSafeDecode, "Zm8").unwrap(),
+            liquid_core::value!("fo")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Base64UrlSafeDecode, "Zm8=").unwrap(),
+            liquid_core::value!("fo")
+        );
+        liquid_core::call_filter!(Base64UrlSafeDecode, "Pz8+").unwrap_err();
+    }
+}
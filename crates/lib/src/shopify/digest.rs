@@ -0,0 +1,250 @@
+use std::fmt::Write;
+
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use md5::Md5 as Md5Digest;
+use sha1::{Digest, Sha1 as Sha1Digest};
+use sha2::Sha256 as Sha256Digest;
+
+// shopify-specific
+
+fn hex_digest<D: Digest>(input: &[u8]) -> String {
+    to_hex(&D::digest(input))
+}
+
+fn hex_hmac<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> String {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(message);
+    to_hex(&mac.finalize().into_bytes())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(hex, "{:02x}", b).expect("writing to a `String` cannot fail");
+    }
+    hex
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "md5",
+    description = "Converts a string into an MD5 hash, as hexadecimal.",
+    parsed(Md5Filter)
+)]
+pub struct Md5;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "md5"]
+struct Md5Filter;
+
+impl Filter for Md5Filter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let input = input.to_kstr();
+        Ok(Value::scalar(hex_digest::<Md5Digest>(input.as_bytes())))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "sha1",
+    description = "Converts a string into a SHA-1 hash, as hexadecimal.",
+    parsed(Sha1Filter)
+)]
+pub struct Sha1;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "sha1"]
+struct Sha1Filter;
+
+impl Filter for Sha1Filter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let input = input.to_kstr();
+        Ok(Value::scalar(hex_digest::<Sha1Digest>(input.as_bytes())))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "sha256",
+    description = "Converts a string into a SHA-256 hash, as hexadecimal.",
+    parsed(Sha256Filter)
+)]
+pub struct Sha256;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "sha256"]
+struct Sha256Filter;
+
+impl Filter for Sha256Filter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let input = input.to_kstr();
+        Ok(Value::scalar(hex_digest::<Sha256Digest>(input.as_bytes())))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct HmacArgs {
+    #[parameter(description = "The secret key to sign with.", arg_type = "str")]
+    key: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "hmac_sha1",
+    description = "Converts a string into a SHA-1 HMAC, signed with the given key, as hexadecimal.",
+    parameters(HmacArgs),
+    parsed(HmacSha1Filter)
+)]
+pub struct HmacSha1;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "hmac_sha1"]
+struct HmacSha1Filter {
+    #[parameters]
+    args: HmacArgs,
+}
+
+impl Filter for HmacSha1Filter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input = input.to_kstr();
+        Ok(Value::scalar(hex_hmac::<Hmac<Sha1Digest>>(
+            args.key.as_bytes(),
+            input.as_bytes(),
+        )))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "hmac_sha256",
+    description = "Converts a string into a SHA-256 HMAC, signed with the given key, as hexadecimal.",
+    parameters(HmacArgs),
+    parsed(HmacSha256Filter)
+)]
+pub struct HmacSha256;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "hmac_sha256"]
+struct HmacSha256Filter {
+    #[parameters]
+    args: HmacArgs,
+}
+
+impl Filter for HmacSha256Filter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input = input.to_kstr();
+        Ok(Value::scalar(hex_hmac::<Hmac<Sha256Digest>>(
+            args.key.as_bytes(),
+            input.as_bytes(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_md5() {
+        // Test vectors from RFC 1321
+        for (input, expected) in [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(Md5, input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn unit_sha1() {
+        for (input, expected) in [
+            ("", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            ("abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            ),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(Sha1, input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn unit_sha256() {
+        for (input, expected) in [
+            (
+                "",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                "abc",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            ),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(Sha256, input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn unit_hmac_sha1() {
+        // Test vector from RFC 2202
+        assert_eq!(
+            liquid_core::call_filter!(HmacSha1, "what do ya want for nothing?", "Jefe").unwrap(),
+            liquid_core::value!("effcdf6ae5eb2fa2d27416d5f184df9c259a7c79")
+        );
+    }
+
+    #[test]
+    fn unit_hmac_sha256() {
+        // Test vectors from RFC 4231
+        assert_eq!(
+            liquid_core::call_filter!(HmacSha256, "what do ya want for nothing?", "Jefe").unwrap(),
+            liquid_core::value!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+        // A key longer than a block is hashed first.
+        assert_eq!(
+            hex_hmac::<Hmac<Sha256Digest>>(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn unit_hmac_missing_key() {
+        liquid_core::call_filter!(HmacSha256, "message").unwrap_err();
+    }
+}
@@ -0,0 +1,81 @@
+use liquid_core::model::{to_json, to_json_pretty};
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{
+    Display_filter, Filter, FilterParameters, FilterReflection, FromFilterParameters, ParseFilter,
+};
+use liquid_core::{Value, ValueView};
+
+// shopify-specific
+
+#[derive(Debug, FilterParameters)]
+struct JsonArgs {
+    #[parameter(
+        description = "Whether to indent the JSON. Defaults to false.",
+        arg_type = "bool"
+    )]
+    pretty: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "json",
+    description = "Serializes the input as JSON, safe to embed in a `<script>` block.",
+    parameters(JsonArgs),
+    parsed(JsonFilter)
+)]
+pub struct Json;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "json"]
+struct JsonFilter {
+    #[parameters]
+    args: JsonArgs,
+}
+
+impl Filter for JsonFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let json = if args.pretty.unwrap_or(false) {
+            to_json_pretty(input)
+        } else {
+            to_json(input)
+        };
+        Ok(Value::scalar(json))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_json() {
+        assert_eq!(
+            liquid_core::call_filter!(Json, liquid_core::value!({"a": [1, "b", nil]})).unwrap(),
+            liquid_core::value!(r#"{"a":[1,"b",null]}"#)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Json, "</script>").unwrap(),
+            liquid_core::value!(r#""\u003c/script\u003e""#)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Json, liquid_core::value!(nil)).unwrap(),
+            liquid_core::value!("null")
+        );
+    }
+
+    #[test]
+    fn unit_json_pretty() {
+        assert_eq!(
+            liquid_core::call_filter!(Json, liquid_core::value!({"a": [1]}), true).unwrap(),
+            liquid_core::value!("{\n  \"a\": [\n    1\n  ]\n}")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Json, liquid_core::value!([1]), false).unwrap(),
+            liquid_core::value!("[1]")
+        );
+    }
+}
@@ -1,3 +1,17 @@
+#[cfg(feature = "base64")]
+mod base64;
+#[cfg(feature = "digest")]
+mod digest;
+mod json;
+mod money;
 mod pluralize;
+mod translate;
 
+#[cfg(feature = "base64")]
+pub use self::base64::*;
+#[cfg(feature = "digest")]
+pub use self::digest::*;
+pub use self::json::*;
+pub use self::money::*;
 pub use self::pluralize::*;
+pub use self::translate::*;
@@ -0,0 +1,317 @@
+use std::fmt;
+use std::sync::Arc;
+
+use liquid_core::parser::FilterArguments;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+use crate::{invalid_input, no_arguments, push_grouped_digits};
+
+// shopify-specific
+
+/// How the `money` filters display an amount.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoneyFormat {
+    /// Written before the amount, like `$`.
+    pub symbol: String,
+    /// Written after the amount by `money_with_currency`, like `USD`.
+    pub currency: String,
+    /// Written between each group of three digits, like `,`.
+    pub thousands_separator: String,
+    /// Written between the whole and fractional parts, like `.`.
+    pub decimal_separator: String,
+    /// The number of digits in the fractional part, e.g. 2 for amounts in cents.
+    pub decimal_places: u32,
+}
+
+impl Default for MoneyFormat {
+    fn default() -> Self {
+        Self {
+            symbol: "$".to_owned(),
+            currency: "USD".to_owned(),
+            thousands_separator: ",".to_owned(),
+            decimal_separator: ".".to_owned(),
+            decimal_places: 2,
+        }
+    }
+}
+
+impl MoneyFormat {
+    /// Format an amount, given in the currency's smallest unit, like cents.
+    fn format(&self, amount: i64, trailing_zeros: bool) -> String {
+        // Any amount fits in the fractional part once there are more than 38 decimal places.
+        let scale = 10u128.checked_pow(self.decimal_places).unwrap_or(u128::MAX);
+        let whole = (u128::from(amount.unsigned_abs()) / scale).to_string();
+        let fraction = u128::from(amount.unsigned_abs()) % scale;
+
+        let mut formatted = String::new();
+        if amount < 0 {
+            formatted.push('-');
+        }
+        formatted.push_str(&self.symbol);
+        push_grouped_digits(&mut formatted, &whole, &self.thousands_separator);
+        if 0 < self.decimal_places && (trailing_zeros || fraction != 0) {
+            formatted.push_str(&self.decimal_separator);
+            formatted.push_str(&format!(
+                "{:0width$}",
+                fraction,
+                width = self.decimal_places as usize
+            ));
+        }
+        formatted
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Style {
+    Plain,
+    WithCurrency,
+    WithoutTrailingZeros,
+}
+
+impl Style {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Plain => "money",
+            Self::WithCurrency => "money_with_currency",
+            Self::WithoutTrailingZeros => "money_without_trailing_zeros",
+        }
+    }
+}
+
+fn parse_money(
+    args: FilterArguments<'_>,
+    format: &Arc<MoneyFormat>,
+    style: Style,
+) -> Result<Box<dyn Filter>> {
+    no_arguments(args)?;
+    Ok(Box::new(MoneyFilter {
+        format: format.clone(),
+        style,
+    }))
+}
+
+/// Formats an amount in cents, like `{{ 145 | money }}` to `$1.45`.
+///
+/// The symbol, separators and number of decimal places come from the [`MoneyFormat`] given to
+/// [`Money::new`].
+#[derive(Clone, Default, FilterReflection)]
+#[filter(
+    name = "money",
+    description = "Formats an amount in cents with the currency symbol.",
+    parsed(MoneyFilter)
+)]
+pub struct Money {
+    format: Arc<MoneyFormat>,
+}
+
+impl Money {
+    /// Format amounts like `format`, instead of in US dollars.
+    pub fn new(format: MoneyFormat) -> Self {
+        Self {
+            format: Arc::new(format),
+        }
+    }
+}
+
+impl ParseFilter for Money {
+    fn parse(&self, args: FilterArguments<'_>) -> Result<Box<dyn Filter>> {
+        parse_money(args, &self.format, Style::Plain)
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+/// Formats an amount in cents with the currency, like `{{ 145 | money_with_currency }}` to
+/// `$1.45 USD`.
+#[derive(Clone, Default, FilterReflection)]
+#[filter(
+    name = "money_with_currency",
+    description = "Formats an amount in cents with the currency symbol and code.",
+    parsed(MoneyFilter)
+)]
+pub struct MoneyWithCurrency {
+    format: Arc<MoneyFormat>,
+}
+
+impl MoneyWithCurrency {
+    /// Format amounts like `format`, instead of in US dollars.
+    pub fn new(format: MoneyFormat) -> Self {
+        Self {
+            format: Arc::new(format),
+        }
+    }
+}
+
+impl ParseFilter for MoneyWithCurrency {
+    fn parse(&self, args: FilterArguments<'_>) -> Result<Box<dyn Filter>> {
+        parse_money(args, &self.format, Style::WithCurrency)
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+/// Formats an amount in cents, leaving off the fractional part when it is zero, like
+/// `{{ 2000 | money_without_trailing_zeros }}` to `$20`.
+#[derive(Clone, Default, FilterReflection)]
+#[filter(
+    name = "money_without_trailing_zeros",
+    description = "Formats an amount in cents with the currency symbol, leaving off a zero fractional part.",
+    parsed(MoneyFilter)
+)]
+pub struct MoneyWithoutTrailingZeros {
+    format: Arc<MoneyFormat>,
+}
+
+impl MoneyWithoutTrailingZeros {
+    /// Format amounts like `format`, instead of in US dollars.
+    pub fn new(format: MoneyFormat) -> Self {
+        Self {
+            format: Arc::new(format),
+        }
+    }
+}
+
+impl ParseFilter for MoneyWithoutTrailingZeros {
+    fn parse(&self, args: FilterArguments<'_>) -> Result<Box<dyn Filter>> {
+        parse_money(args, &self.format, Style::WithoutTrailingZeros)
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct MoneyFilter {
+    format: Arc<MoneyFormat>,
+    style: Style,
+}
+
+impl fmt::Display for MoneyFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.style.name())
+    }
+}
+
+impl Filter for MoneyFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        if input.is_nil() {
+            return Ok(Value::Nil);
+        }
+
+        let amount = input.as_scalar();
+        let amount = match amount.as_ref().and_then(|s| s.to_integer()) {
+            Some(amount) => amount,
+            None => amount
+                .and_then(|s| s.to_float())
+                .filter(|f| f.is_finite() && f.abs() < i64::MAX as f64)
+                .map(|f| f.round() as i64)
+                .ok_or_else(|| invalid_input("Number expected"))?,
+        };
+
+        let mut formatted = self
+            .format
+            .format(amount, self.style != Style::WithoutTrailingZeros);
+        if self.style == Style::WithCurrency {
+            formatted.push(' ');
+            formatted.push_str(&self.format.currency);
+        }
+        Ok(Value::scalar(formatted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_money() {
+        for (input, expected) in [
+            (145, "$1.45"),
+            (5, "$0.05"),
+            (0, "$0.00"),
+            (100000, "$1,000.00"),
+            (123456789, "$1,234,567.89"),
+            (-145, "-$1.45"),
+        ] {
+            assert_eq!(
+                liquid_core::call_filter!(Money::default(), input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+        assert_eq!(
+            liquid_core::call_filter!(Money::default(), 144.6f64).unwrap(),
+            liquid_core::value!("$1.45")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Money::default(), "145").unwrap(),
+            liquid_core::value!("$1.45")
+        );
+        liquid_core::call_filter!(Money::default(), "abc").unwrap_err();
+        liquid_core::call_filter!(Money::default(), 145, 2).unwrap_err();
+    }
+
+    #[test]
+    fn unit_money_with_currency() {
+        assert_eq!(
+            liquid_core::call_filter!(MoneyWithCurrency::default(), 145).unwrap(),
+            liquid_core::value!("$1.45 USD")
+        );
+    }
+
+    #[test]
+    fn unit_money_without_trailing_zeros() {
+        for (input, expected) in [(2000, "$20"), (145, "$1.45"), (150, "$1.50"), (0, "$0")] {
+            assert_eq!(
+                liquid_core::call_filter!(MoneyWithoutTrailingZeros::default(), input).unwrap(),
+                liquid_core::value!(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn unit_money_format() {
+        let euro = MoneyFormat {
+            symbol: "€".to_owned(),
+            currency: "EUR".to_owned(),
+            thousands_separator: ".".to_owned(),
+            decimal_separator: ",".to_owned(),
+            decimal_places: 2,
+        };
+        assert_eq!(
+            liquid_core::call_filter!(Money::new(euro.clone()), 123456789).unwrap(),
+            liquid_core::value!("€1.234.567,89")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(MoneyWithCurrency::new(euro), 100).unwrap(),
+            liquid_core::value!("€1,00 EUR")
+        );
+
+        let yen = MoneyFormat {
+            symbol: "¥".to_owned(),
+            currency: "JPY".to_owned(),
+            decimal_places: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            liquid_core::call_filter!(Money::new(yen), 1500).unwrap(),
+            liquid_core::value!("¥1,500")
+        );
+
+        let tiny = MoneyFormat {
+            decimal_places: 40,
+            ..Default::default()
+        };
+        assert_eq!(
+            liquid_core::call_filter!(Money::new(tiny), 15).unwrap(),
+            liquid_core::value!("$0.0000000000000000000000000000000000000015")
+        );
+    }
+}
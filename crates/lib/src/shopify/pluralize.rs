@@ -40,10 +40,10 @@ impl Filter for PluralizeFilter {
 
         let n = input
             .as_scalar()
-            .and_then(|s| s.to_integer())
-            .ok_or_else(|| invalid_input("Whole number expected"))?;
+            .and_then(|s| s.to_float())
+            .ok_or_else(|| invalid_input("Number expected"))?;
 
-        if (n as isize) == 1 {
+        if n == 1.0 {
             Ok(args.singular.to_value())
         } else {
             Ok(args.plural.to_value())
@@ -77,4 +77,26 @@ mod tests {
             liquid_core::value!("many")
         );
     }
+
+    #[test]
+    fn unit_pluralize_fractional() {
+        assert_eq!(
+            liquid_core::call_filter!(Pluralize, 1f64, "one", "many").unwrap(),
+            liquid_core::value!("one")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Pluralize, 1.5f64, "one", "many").unwrap(),
+            liquid_core::value!("many")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Pluralize, "1", "one", "many").unwrap(),
+            liquid_core::value!("one")
+        );
+    }
+
+    #[test]
+    fn unit_pluralize_not_a_number() {
+        liquid_core::call_filter!(Pluralize, "one", "one", "many").unwrap_err();
+        liquid_core::call_filter!(Pluralize, 1i64, "one").unwrap_err();
+    }
 }
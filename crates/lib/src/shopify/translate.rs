@@ -0,0 +1,242 @@
+use std::fmt;
+use std::sync::Arc;
+
+use liquid_core::model::{KString, Object, ObjectView};
+use liquid_core::parser::FilterArguments;
+use liquid_core::Expression;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+use crate::{invalid_argument, invalid_input, no_positional_arguments};
+
+// shopify-specific
+
+/// Looks up a translation, like `{{ "cart.items" | t: count: 2 }}`.
+///
+/// Translations come from the table given to [`Translate::new`], keyed by dotted paths into its
+/// nested objects.  Placeholders like `%{name}` are filled in from the keyword arguments.  With a
+/// `count`, a translation with `zero`, `one` and `other` forms picks one to match.
+#[derive(Clone, Default, FilterReflection)]
+#[filter(
+    name = "t",
+    description = "Looks up a translation by key, filling in `%{name}` placeholders from the keyword arguments.",
+    parsed(TranslateFilter)
+)]
+pub struct Translate {
+    translations: Arc<Object>,
+}
+
+impl Translate {
+    /// Look up translations in `translations`, nested objects of strings.
+    pub fn new(translations: Object) -> Self {
+        Self {
+            translations: Arc::new(translations),
+        }
+    }
+}
+
+impl ParseFilter for Translate {
+    fn parse(&self, mut args: FilterArguments<'_>) -> Result<Box<dyn Filter>> {
+        no_positional_arguments(&mut args)?;
+        let interpolations = args
+            .keyword
+            .map(|(name, value)| (KString::from_ref(name), value))
+            .collect();
+
+        Ok(Box::new(TranslateFilter {
+            translations: self.translations.clone(),
+            interpolations,
+        }))
+    }
+
+    fn reflection(&self) -> &dyn FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct TranslateFilter {
+    translations: Arc<Object>,
+    interpolations: Vec<(KString, Expression)>,
+}
+
+impl fmt::Display for TranslateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "t")?;
+        for (i, (name, value)) in self.interpolations.iter().enumerate() {
+            let separator = if i == 0 { ":" } else { "," };
+            write!(f, "{} {}: {}", separator, name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Filter for TranslateFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let key = input.to_kstr();
+
+        let mut translation: Option<&dyn ValueView> = Some(self.translations.as_ref());
+        for part in key.split('.') {
+            translation = translation
+                .and_then(|t| t.as_object())
+                .and_then(|t| t.get(part));
+        }
+        let translation = match translation {
+            Some(translation) => translation,
+            // Like Shopify, a missing translation is shown rather than failing the render.
+            None => return Ok(Value::scalar(format!("translation missing: {}", key))),
+        };
+
+        let mut values = Object::new();
+        for (name, value) in &self.interpolations {
+            values.insert(name.clone(), value.evaluate(runtime)?.into_owned());
+        }
+
+        let translation = match translation.as_object() {
+            Some(forms) => plural_form(forms, values.get("count").map(|c| c.as_view()))?,
+            None => translation,
+        };
+        let translation = translation
+            .as_scalar()
+            .ok_or_else(|| invalid_input("Translation expected"))?;
+
+        interpolate(translation.to_kstr().as_str(), &values).map(Value::scalar)
+    }
+}
+
+/// Pick the `zero`, `one` or `other` form of a translation for `count`.
+fn plural_form<'t>(
+    forms: &'t dyn ObjectView,
+    count: Option<&dyn ValueView>,
+) -> Result<&'t dyn ValueView> {
+    let count = count
+        .and_then(|c| c.as_scalar())
+        .and_then(|c| c.to_float())
+        .ok_or_else(|| invalid_argument("count", "Number expected for a pluralized translation"))?;
+    let form = if count == 0.0 && forms.contains_key("zero") {
+        "zero"
+    } else if count == 1.0 {
+        "one"
+    } else {
+        "other"
+    };
+    forms
+        .get(form)
+        .ok_or_else(|| invalid_input(format!("Translation has no `{}` form", form)))
+}
+
+fn interpolate(translation: &str, values: &Object) -> Result<String> {
+    let mut result = String::with_capacity(translation.len());
+    let mut rest = translation;
+    while let Some(start) = rest.find("%{") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let name = &rest[start + 2..end];
+        let value = values.get(name).ok_or_else(|| {
+            invalid_argument(
+                KString::from_ref(name),
+                KString::from_static("Missing interpolation argument"),
+            )
+        })?;
+        result.push_str(&rest[..start]);
+        result.push_str(value.to_kstr().as_str());
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime::RuntimeBuilder;
+    use liquid_core::{Language, Renderable, Template};
+
+    fn translate() -> Translate {
+        let translations = liquid_core::object!({
+            "cart": {
+                "title": "Your cart",
+                "greeting": "Hello %{name}, you have %{count} items",
+                "items": {
+                    "zero": "No items",
+                    "one": "%{count} item",
+                    "other": "%{count} items",
+                },
+            },
+            "count": 5,
+        });
+        Translate::new(translations)
+    }
+
+    fn render(text: &str) -> Result<String> {
+        let mut language = Language::empty();
+        language
+            .filters
+            .register("t".to_owned(), Box::new(translate()));
+        let template = parser::parse(text, &language).map(Template::new)?;
+        let runtime = RuntimeBuilder::new().build();
+        template.render(&runtime)
+    }
+
+    #[test]
+    fn unit_translate() {
+        assert_eq!(
+            liquid_core::call_filter!(translate(), "cart.title").unwrap(),
+            liquid_core::value!("Your cart")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(translate(), "count").unwrap(),
+            liquid_core::value!("5")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(translate(), "cart.missing").unwrap(),
+            liquid_core::value!("translation missing: cart.missing")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(translate(), "cart.title.more").unwrap(),
+            liquid_core::value!("translation missing: cart.title.more")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(translate(), "cart.items"; count = 2).unwrap(),
+            liquid_core::value!("2 items")
+        );
+        liquid_core::call_filter!(translate(), "cart").unwrap_err();
+        liquid_core::call_filter!(translate(), "cart.title", "extra").unwrap_err();
+    }
+
+    #[test]
+    fn unit_translate_interpolation() {
+        assert_eq!(
+            render(r#"{{ "cart.greeting" | t: name: "Ann", count: 3 }}"#).unwrap(),
+            "Hello Ann, you have 3 items"
+        );
+        render(r#"{{ "cart.greeting" | t: name: "Ann" }}"#).unwrap_err();
+        assert_eq!(
+            render(r#"{{ "cart.title" | t: name: "unused" }}"#).unwrap(),
+            "Your cart"
+        );
+    }
+
+    #[test]
+    fn unit_translate_pluralization() {
+        assert_eq!(
+            render(r#"{{ "cart.items" | t: count: 0 }}"#).unwrap(),
+            "No items"
+        );
+        assert_eq!(
+            render(r#"{{ "cart.items" | t: count: 1 }}"#).unwrap(),
+            "1 item"
+        );
+        assert_eq!(
+            render(r#"{{ "cart.items" | t: count: 7 }}"#).unwrap(),
+            "7 items"
+        );
+        render(r#"{{ "cart.items" | t }}"#).unwrap_err();
+    }
+}
@@ -1,7 +1,10 @@
 use std::io::Write;
 
 use liquid_core::error::ResultLiquidExt;
-use liquid_core::model::Value;
+use liquid_core::model::{Scalar, Value, ValueCow, ValueView};
+use liquid_core::parser::{FilterChain, TryMatchToken};
+use liquid_core::Error;
+use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
 use liquid_core::Result;
@@ -39,12 +42,35 @@ impl ParseBlock for CaptureBlock {
         mut tokens: TagBlock<'_, '_>,
         options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        let id = arguments
-            .expect_next("Identifier expected")?
-            .expect_identifier()
-            .into_result()?
-            .to_string()
-            .into();
+        let mut token = arguments.expect_next("Identifier expected")?;
+        let mut append = false;
+        if token.as_str() == "append" {
+            if let Some(next) = arguments.next() {
+                append = true;
+                token = next;
+            }
+        }
+
+        // `{% capture x | strip %}` filters the captured text before it is assigned.
+        let (id, filters) = match token.expect_identifier() {
+            TryMatchToken::Matches(id) => (id.to_string().into(), None),
+            TryMatchToken::Fails(token) => {
+                let filters = token.expect_filter_chain(options).into_result()?;
+                let id = match filters.entry() {
+                    Expression::Variable(variable) => variable.to_string(),
+                    Expression::Literal(_)
+                    | Expression::Range(..)
+                    | Expression::Array(_)
+                    | Expression::Object(_)
+                    | Expression::Indexed(..) => String::new(),
+                };
+                if id.is_empty() || id.contains('[') {
+                    return Err(Error::with_msg("Identifier expected")
+                        .context("found", filters.entry().to_string()));
+                }
+                (id.into(), Some(filters))
+            }
+        };
 
         // no more arguments should be supplied, trying to supply them is an error
         arguments.expect_nothing()?;
@@ -56,7 +82,12 @@ impl ParseBlock for CaptureBlock {
         );
 
         tokens.assert_empty();
-        Ok(Box::new(Capture { id, template }))
+        Ok(Box::new(Capture {
+            id,
+            filters,
+            append,
+            template,
+        }))
     }
 
     fn reflection(&self) -> &dyn BlockReflection {
@@ -67,12 +98,18 @@ impl ParseBlock for CaptureBlock {
 #[derive(Debug)]
 struct Capture {
     id: liquid_core::model::KString,
+    filters: Option<FilterChain>,
+    append: bool,
     template: Template,
 }
 
 impl Capture {
     fn trace(&self) -> String {
-        format!("{{% capture {} %}}", self.id)
+        let append = if self.append { "append " } else { "" };
+        match self.filters {
+            Some(ref filters) => format!("{{% capture {}{} %}}", append, filters),
+            None => format!("{{% capture {}{} %}}", append, self.id),
+        }
     }
 }
 
@@ -84,7 +121,21 @@ impl Renderable for Capture {
             .trace_with(|| self.trace().into())?;
 
         let output = String::from_utf8(captured).expect("render only writes UTF-8");
-        runtime.set_global(self.id.clone(), Value::scalar(output));
+        let mut output = Value::scalar(output);
+        if let Some(ref filters) = self.filters {
+            output = filters
+                .filter(ValueCow::Owned(output), runtime)
+                .trace_with(|| self.trace().into())?
+                .into_owned();
+        }
+        if self.append {
+            let path = [Scalar::new(self.id.clone())];
+            if let Some(existing) = runtime.try_get(&path) {
+                let appended = format!("{}{}", existing.to_kstr(), output.to_kstr());
+                output = Value::scalar(appended);
+            }
+        }
+        runtime.set_global(self.id.clone(), output);
         Ok(())
     }
 }
@@ -93,7 +144,6 @@ impl Renderable for Capture {
 mod test {
     use super::*;
 
-    use liquid_core::model::Scalar;
     use liquid_core::parser;
     use liquid_core::runtime;
     use liquid_core::runtime::RuntimeBuilder;
@@ -104,6 +154,12 @@ mod test {
             .blocks
             .register("capture".to_string(), CaptureBlock.into());
         options
+            .filters
+            .register("strip".to_string(), Box::new(crate::stdlib::Strip));
+        options
+            .filters
+            .register("upcase".to_string(), Box::new(crate::stdlib::Upcase));
+        options
     }
 
     #[test]
@@ -125,11 +181,70 @@ mod test {
         let output = template.render(&rt).unwrap();
         assert_eq!(
             rt.get(&[Scalar::new("attribute_name")]).unwrap(),
-            "potato-42-color"
+            "potato-42.0-color"
         );
         assert_eq!(output, "");
     }
 
+    #[test]
+    fn test_capture_filters() {
+        let text = "{% capture title | strip | upcase %}  hello {{ name }}  {% endcapture %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let rt = RuntimeBuilder::new().build();
+        rt.set_global("name".into(), Value::scalar("world"));
+
+        let output = template.render(&rt).unwrap();
+        assert_eq!(rt.get(&[Scalar::new("title")]).unwrap(), "HELLO WORLD");
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn test_capture_append() {
+        let text = concat!(
+            "{% capture append list %}{{ item }}{% endcapture %}",
+            "{% capture append list | upcase %}, b{% endcapture %}",
+            "{% capture append %}c{% endcapture %}",
+        );
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let rt = RuntimeBuilder::new().build();
+        rt.set_global("list".into(), Value::scalar("a"));
+        rt.set_global("item".into(), Value::scalar(1));
+
+        template.render(&rt).unwrap();
+        assert_eq!(rt.get(&[Scalar::new("list")]).unwrap(), "a1, B");
+        // With nothing following it, `append` is the name of the variable.
+        assert_eq!(rt.get(&[Scalar::new("append")]).unwrap(), "c");
+    }
+
+    #[test]
+    fn test_capture_append_undefined() {
+        let text = "{% capture append list %}a{% endcapture %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let rt = RuntimeBuilder::new().build();
+        template.render(&rt).unwrap();
+        assert_eq!(rt.get(&[Scalar::new("list")]).unwrap(), "a");
+    }
+
+    #[test]
+    fn filtered_literal_is_an_error() {
+        let text = "{% capture 'foo' | upcase %}{% endcapture %}";
+        let template = parser::parse(text, &options()).map(runtime::Template::new);
+        assert!(template.is_err());
+
+        let text = "{% capture foo.bar | upcase %}{% endcapture %}";
+        let template = parser::parse(text, &options()).map(runtime::Template::new);
+        assert!(template.is_err());
+    }
+
     #[test]
     fn trailing_tokens_are_an_error() {
         let text = concat!(
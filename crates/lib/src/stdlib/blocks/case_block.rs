@@ -2,7 +2,6 @@ use std::io::Write;
 
 use liquid_core::error::ResultLiquidExt;
 use liquid_core::model::{ValueView, ValueViewCmp};
-use liquid_core::parser::BlockElement;
 use liquid_core::parser::TryMatchToken;
 use liquid_core::Expression;
 use liquid_core::Language;
@@ -52,37 +51,29 @@ impl ParseBlock for CaseBlock {
 
         let mut cases = Vec::new();
         let mut else_block = None;
-        let mut current_block = Vec::new();
-        let mut current_condition = None;
-
-        while let Some(element) = tokens.next()? {
-            match element {
-                BlockElement::Tag(mut tag) => match tag.name() {
-                    "when" => {
-                        if let Some(condition) = current_condition {
-                            cases.push(CaseOption::new(condition, Template::new(current_block)));
-                        }
-                        current_block = Vec::new();
-                        current_condition = Some(parse_condition(tag.tokens())?);
-                    }
-                    "else" => {
-                        // no more arguments should be supplied, trying to supply them is an error
-                        tag.tokens().expect_nothing()?;
-                        else_block = Some(tokens.parse_all(options)?);
-                        break;
-                    }
-                    _ => current_block.push(tag.parse(&mut tokens, options)?),
-                },
-                element => current_block.push(element.parse(&mut tokens, options)?),
+        // Anything before the first `when` is never rendered.
+        for branch in tokens.parse_branches(&["when", "else"], options)? {
+            let (tag, template) = branch.into_parts();
+            let mut tag = match tag {
+                Some(tag) => tag,
+                None => continue,
+            };
+            if else_block.is_some() {
+                return Err(tag.tokens().raise_error("Unexpected tag after {% else %}"));
+            }
+            match tag.name() {
+                "when" => {
+                    let condition = parse_condition(tag.tokens())?;
+                    cases.push(CaseOption::new(condition, template));
+                }
+                _ => {
+                    // no more arguments should be supplied, trying to supply them is an error
+                    tag.tokens().expect_nothing()?;
+                    else_block = Some(template);
+                }
             }
         }
 
-        if let Some(condition) = current_condition {
-            cases.push(CaseOption::new(condition, Template::new(current_block)));
-        }
-
-        let else_block = else_block.map(Template::new);
-
         tokens.assert_empty();
         Ok(Box::new(Case {
             target,
@@ -259,6 +250,59 @@ mod test {
         assert_eq!(template.render(&runtime).unwrap(), "");
     }
 
+    #[test]
+    fn test_value_lists() {
+        let text = concat!(
+            "{% case x %}",
+            "{% when 'a', 'b' %}",
+            "comma",
+            "{% when 'c' or 'd', 'e' %}",
+            "mixed",
+            "{% else %}",
+            "otherwise",
+            "{% endcase %}"
+        );
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        for (x, expected) in [
+            ("a", "comma"),
+            ("b", "comma"),
+            ("c", "mixed"),
+            ("d", "mixed"),
+            ("e", "mixed"),
+            ("f", "otherwise"),
+        ] {
+            runtime.set_global("x".into(), Value::scalar(x));
+            assert_eq!(template.render(&runtime).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn incomplete_value_list_is_an_error() {
+        let options = options();
+        for text in [
+            "{% case x %}{% when 'a', %}a{% endcase %}",
+            "{% case x %}{% when 'a' or %}a{% endcase %}",
+            "{% case x %}{% when 'a' 'b' %}a{% endcase %}",
+            "{% case x %}{% when %}a{% endcase %}",
+        ] {
+            let template = parser::parse(text, &options).map(runtime::Template::new);
+            assert!(template.is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn when_after_else_is_an_error() {
+        let text = "{% case x %}{% when 1 %}one{% else %}else{% when 2 %}two{% endcase %}";
+        let options = options();
+        let template = parser::parse(text, &options).map(runtime::Template::new);
+        assert!(template.is_err());
+    }
+
     #[test]
     fn multiple_else_blocks_is_an_error() {
         let text = concat!(
@@ -1,11 +1,11 @@
 use std::fmt;
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
-use liquid_core::model::{Object, ObjectView, Value, ValueCow, ValueView};
-use liquid_core::parser::BlockElement;
+use liquid_core::model::{self, ArrayView, ObjectView, Value, ValueCow, ValueView};
 use liquid_core::parser::TryMatchToken;
-use liquid_core::runtime::{Interrupt, InterruptRegister};
+use liquid_core::runtime::{Interrupt, InterruptRegister, LoopGuard};
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -55,10 +55,10 @@ impl ParseBlock for ForBlock {
             .into_result_custom_msg("\"in\" expected.")?;
 
         let range = arguments.expect_next("Array or range expected.")?;
-        let range = match range.expect_value() {
-            TryMatchToken::Matches(array) => RangeExpression::Array(array),
-            TryMatchToken::Fails(range) => match range.expect_range() {
-                TryMatchToken::Matches((start, stop)) => RangeExpression::Counted(start, stop),
+        let range = match range.expect_range() {
+            TryMatchToken::Matches((start, stop)) => RangeExpression::Counted(start, stop),
+            TryMatchToken::Fails(range) => match range.expect_value() {
+                TryMatchToken::Matches(array) => RangeExpression::Array(array),
                 TryMatchToken::Fails(range) => return range.raise_error().into_err(),
             },
         };
@@ -71,7 +71,7 @@ impl ParseBlock for ForBlock {
         while let Some(token) = arguments.next() {
             match token.as_str() {
                 "limit" => limit = Some(parse_attr(&mut arguments)?),
-                "offset" => offset = Some(parse_attr(&mut arguments)?),
+                "offset" => offset = Some(parse_offset(&mut arguments)?),
                 "reversed" => reversed = true,
                 _ => {
                     return token
@@ -84,30 +84,33 @@ impl ParseBlock for ForBlock {
         // no more arguments should be supplied, trying to supply them is an error
         arguments.expect_nothing()?;
 
-        let mut item_template = Vec::new();
+        // `{% else %}` is rendered instead of the items when there are none
+        let mut branches = tokens.parse_branches(&["else"], options)?.into_iter();
+        let (_, item_template) = branches
+            .next()
+            .expect("always a leading branch")
+            .into_parts();
         let mut else_template = None;
-
-        while let Some(element) = tokens.next()? {
-            match element {
-                BlockElement::Tag(mut tag) => match tag.name() {
-                    "else" => {
-                        // no more arguments should be supplied, trying to supply them is an error
-                        tag.tokens().expect_nothing()?;
-                        else_template = Some(tokens.parse_all(options)?);
-                        break;
-                    }
-                    _ => item_template.push(tag.parse(&mut tokens, options)?),
-                },
-                element => item_template.push(element.parse(&mut tokens, options)?),
+        for branch in branches {
+            let (tag, template) = branch.into_parts();
+            let mut tag = tag.expect("only the leading branch has no tag");
+            if else_template.is_some() {
+                return Err(tag.tokens().raise_error("Unexpected tag after {% else %}"));
             }
+            // no more arguments should be supplied, trying to supply them is an error
+            tag.tokens().expect_nothing()?;
+            else_template = Some(template);
         }
 
-        let item_template = Template::new(item_template);
-        let else_template = else_template.map(Template::new);
-
         tokens.assert_empty();
+        if let Some(Offset::Continue) = offset {
+            CONTINUED_LOOPS.store(true, Ordering::Relaxed);
+        }
+        // Loops are resumed by `offset: continue` when over the same variable and collection.
+        let offsets_key = format!("{}-{}", var_name, range).into();
         Ok(Box::new(For {
             var_name: liquid_core::model::KString::from_ref(var_name),
+            offsets_key,
             range,
             item_template,
             else_template,
@@ -125,11 +128,12 @@ impl ParseBlock for ForBlock {
 #[derive(Debug)]
 struct For {
     var_name: liquid_core::model::KString,
+    offsets_key: liquid_core::model::KString,
     range: RangeExpression,
     item_template: Template,
     else_template: Option<Template>,
     limit: Option<Expression>,
-    offset: Option<Expression>,
+    offset: Option<Offset>,
     reversed: bool,
 }
 
@@ -149,7 +153,7 @@ fn trace_for_tag(
     var_name: &str,
     range: &RangeExpression,
     limit: &Option<Expression>,
-    offset: &Option<Expression>,
+    offset: &Option<Offset>,
     reversed: bool,
 ) -> String {
     let mut parameters = vec![];
@@ -178,8 +182,28 @@ impl Renderable for For {
             .trace_with(|| self.trace().into())?;
         let array = range.evaluate()?;
         let limit = evaluate_attr(&self.limit, runtime)?;
-        let offset = evaluate_attr(&self.offset, runtime)?.unwrap_or(0);
+        let offset = match self.offset {
+            Some(Offset::Continue) => runtime
+                .registers()
+                .get_mut::<ForOffsetRegister>()
+                .offsets
+                .get(&self.offsets_key)
+                .copied()
+                .unwrap_or(0),
+            Some(Offset::Expression(ref offset)) => evaluate_whole_number(offset, runtime)?,
+            None => 0,
+        };
         let array = iter_array(array, limit, offset, self.reversed);
+        if CONTINUED_LOOPS.load(Ordering::Relaxed) {
+            let mut register = runtime.registers().get_mut::<ForOffsetRegister>();
+            let end = offset + array.len();
+            match register.offsets.get_mut(&self.offsets_key) {
+                Some(offset) => *offset = end,
+                None => {
+                    register.offsets.insert(self.offsets_key.clone(), end);
+                }
+            }
+        }
 
         match array.len() {
             0 => {
@@ -193,6 +217,7 @@ impl Renderable for For {
             range_len => {
                 let parentloop = runtime.try_get(&[liquid_core::model::Scalar::new("forloop")]);
                 let parentloop_ref = parentloop.as_ref().map(|v| v.as_view());
+                let _loop = LoopGuard::enter(runtime);
                 for (i, v) in array.into_iter().enumerate() {
                     let forloop = ForloopObject::new(i, range_len).parentloop(parentloop_ref);
                     let mut root = std::collections::HashMap::<
@@ -214,7 +239,7 @@ impl Renderable for For {
                     // clearing the interrupt and carrying on as normal. A
                     // `break` requires some special handling, though.
                     let current_interrupt =
-                        scope.registers().get_mut::<InterruptRegister>().reset();
+                        scope.registers().get_mut::<InterruptRegister>().unwind();
                     if let Some(Interrupt::Break) = current_interrupt {
                         break;
                     }
@@ -302,10 +327,10 @@ impl ParseBlock for TableRowBlock {
             .into_result_custom_msg("\"in\" expected.")?;
 
         let range = arguments.expect_next("Array or range expected.")?;
-        let range = match range.expect_value() {
-            TryMatchToken::Matches(array) => RangeExpression::Array(array),
-            TryMatchToken::Fails(range) => match range.expect_range() {
-                TryMatchToken::Matches((start, stop)) => RangeExpression::Counted(start, stop),
+        let range = match range.expect_range() {
+            TryMatchToken::Matches((start, stop)) => RangeExpression::Counted(start, stop),
+            TryMatchToken::Fails(range) => match range.expect_value() {
+                TryMatchToken::Matches(array) => RangeExpression::Array(array),
                 TryMatchToken::Fails(range) => return range.raise_error().into_err(),
             },
         };
@@ -389,7 +414,7 @@ fn trace_tablerow_tag(
         parameters.push(format!("offset:{}", offset));
     }
     format!(
-        "{{% for {} in {} {} %}}",
+        "{{% tablerow {} in {} {} %}}",
         var_name,
         range,
         itertools::join(parameters.iter(), ", ")
@@ -408,13 +433,13 @@ impl Renderable for TableRow {
         let offset = evaluate_attr(&self.offset, runtime)?.unwrap_or(0);
         let array = iter_array(array, limit, offset, false);
 
-        let mut helper_vars = Object::new();
-
         let range_len = array.len();
-        helper_vars.insert("length".into(), Value::scalar(range_len as i64));
+        let cols = cols.unwrap_or(range_len);
 
+        // Output mirrors Ruby Liquid: the first row is always opened, even for empty ranges.
+        writeln!(writer, "<tr class=\"row1\">").replace("Failed to render")?;
+        let _loop = LoopGuard::enter(runtime);
         for (i, v) in array.into_iter().enumerate() {
-            let cols = cols.unwrap_or(range_len);
             let col_index = i % cols;
             let row_index = i / cols;
 
@@ -423,13 +448,11 @@ impl Renderable for TableRow {
                 liquid_core::model::KStringRef<'_>,
                 &dyn ValueView,
             >::new();
+            root.insert("tablerowloop".into(), &tablerow);
+            // `tablerow` is kept for templates written before `tablerowloop` was supported
             root.insert("tablerow".into(), &tablerow);
             root.insert(self.var_name.as_ref(), &v);
 
-            if tablerow.col_first {
-                write!(writer, "<tr class=\"row{}\">", row_index + 1)
-                    .replace("Failed to render")?;
-            }
             write!(writer, "<td class=\"col{}\">", col_index + 1).replace("Failed to render")?;
 
             let scope = StackFrame::new(runtime, &root);
@@ -440,18 +463,29 @@ impl Renderable for TableRow {
                 .value_with(|| format!("{}", i + 1).into())?;
 
             write!(writer, "</td>").replace("Failed to render")?;
-            if tablerow.col_last {
-                write!(writer, "</tr>").replace("Failed to render")?;
+
+            // Like `for`, a `break` ends the table early.
+            let current_interrupt = scope.registers().get_mut::<InterruptRegister>().unwind();
+            if let Some(Interrupt::Break) = current_interrupt {
+                break;
+            }
+
+            if tablerow.col_last && !tablerow.last {
+                write!(writer, "</tr>\n<tr class=\"row{}\">", row_index + 2)
+                    .replace("Failed to render")?;
             }
         }
+        writeln!(writer, "</tr>").replace("Failed to render")?;
 
         Ok(())
     }
 }
 
+/// The `tablerowloop` object: the position of the current cell in the table.
 #[derive(Debug, Clone, ValueView, ObjectView)]
-struct TableRowObject {
+pub struct TableRowObject {
     length: i64,
+    row: i64,
     index0: i64,
     index: i64,
     rindex0: i64,
@@ -465,7 +499,8 @@ struct TableRowObject {
 }
 
 impl TableRowObject {
-    fn new(i: usize, len: usize, col: usize, cols: usize) -> Self {
+    /// State for item `i` of `len`, in column `col` of `cols`.
+    pub fn new(i: usize, len: usize, col: usize, cols: usize) -> Self {
         let i = i as i64;
         let len = len as i64;
         let col = col as i64;
@@ -476,6 +511,7 @@ impl TableRowObject {
         let col_last = col == (cols - 1) || last;
         Self {
             length: len,
+            row: i / cols + 1,
             index0: i,
             index: i + 1,
             rindex0: len - i - 1,
@@ -490,6 +526,47 @@ impl TableRowObject {
     }
 }
 
+/// Where a `for` loop starts in its collection.
+#[derive(Clone, Debug)]
+enum Offset {
+    Expression(Expression),
+    /// Resume after the last loop over the same collection.
+    Continue,
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Offset::Expression(offset) => write!(f, "{}", offset),
+            Offset::Continue => write!(f, "continue"),
+        }
+    }
+}
+
+/// Where each loop, by variable and collection, stopped.
+#[derive(Clone, Debug, Default)]
+struct ForOffsetRegister {
+    offsets: std::collections::HashMap<liquid_core::model::KString, usize>,
+}
+
+/// Whether any loop parsed so far uses `offset: continue`.  Until one does, no loop can be
+/// continued, so loops needn't record where they stopped.
+static CONTINUED_LOOPS: AtomicBool = AtomicBool::new(false);
+
+/// Extracts an offset, an integer value, an identifier or `continue`, from the token stream
+fn parse_offset(arguments: &mut TagTokenIter<'_>) -> Result<Offset> {
+    arguments
+        .expect_next("\":\" expected.")?
+        .expect_str(":")
+        .into_result_custom_msg("\":\" expected.")?;
+
+    let offset = arguments.expect_next("Value expected.")?;
+    match offset.expect_str("continue") {
+        TryMatchToken::Matches(()) => Ok(Offset::Continue),
+        TryMatchToken::Fails(offset) => offset.expect_value().into_result().map(Offset::Expression),
+    }
+}
+
 /// Extracts an integer value or an identifier from the token stream
 fn parse_attr(arguments: &mut TagTokenIter<'_>) -> Result<Expression> {
     arguments
@@ -505,18 +582,19 @@ fn parse_attr(arguments: &mut TagTokenIter<'_>) -> Result<Expression> {
 
 /// Evaluates an attribute, returning Ok(None) if input is also None.
 fn evaluate_attr(attr: &Option<Expression>, runtime: &dyn Runtime) -> Result<Option<usize>> {
-    match attr {
-        Some(attr) => {
-            let value = attr.evaluate(runtime)?;
-            let value = value
-                .as_scalar()
-                .and_then(|s| s.to_integer())
-                .ok_or_else(|| unexpected_value_error("whole number", Some(value.type_name())))?
-                as usize;
-            Ok(Some(value))
-        }
-        None => Ok(None),
-    }
+    attr.as_ref()
+        .map(|attr| evaluate_whole_number(attr, runtime))
+        .transpose()
+}
+
+fn evaluate_whole_number(attr: &Expression, runtime: &dyn Runtime) -> Result<usize> {
+    let value = attr.evaluate(runtime)?;
+    let value = value
+        .as_scalar()
+        .and_then(|s| s.to_integer())
+        .ok_or_else(|| unexpected_value_error("whole number", Some(value.type_name())))?
+        as usize;
+    Ok(value)
 }
 
 #[derive(Clone, Debug)]
@@ -560,17 +638,84 @@ pub enum Range<'r> {
 }
 
 impl<'r> Range<'r> {
-    pub fn evaluate(&self) -> Result<Vec<ValueCow<'_>>> {
-        let range = match self {
-            Range::Array(array) => get_array(array.as_view())?,
+    pub fn evaluate(&self) -> Result<LoopItems<'_>> {
+        let items = match self {
+            Range::Array(array) => match as_range(array.as_view()) {
+                Some(range) => LoopItems::integers(range.start(), range.end()),
+                None => LoopItems::Values(get_array(array.as_view())?),
+            },
 
             Range::Counted(start, stop) => {
-                let range = (*start)..=(*stop);
-                range.map(|x| Value::scalar(x).into()).collect()
+                let range = model::Range::new(*start, *stop);
+                if model::Range::MAX_SIZE < range.size() {
+                    return Error::with_msg("Range is too long")
+                        .context("range", range.to_string())
+                        .context("maximum size", model::Range::MAX_SIZE.to_string())
+                        .into_err();
+                }
+                LoopItems::integers(*start, *stop)
             }
         };
 
-        Ok(range)
+        Ok(items)
+    }
+}
+
+/// What a loop iterates over, with the integers of ranges computed as they're iterated.
+#[derive(Clone, Debug)]
+pub enum LoopItems<'r> {
+    Values(Vec<ValueCow<'r>>),
+    Integers {
+        start: i64,
+        len: usize,
+        reversed: bool,
+    },
+}
+
+impl<'r> LoopItems<'r> {
+    fn integers(start: i64, end: i64) -> Self {
+        let len = i128::from(end) - i128::from(start) + 1;
+        Self::Integers {
+            start,
+            len: len.clamp(0, usize::MAX as i128) as usize,
+            reversed: false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Values(values) => values.len(),
+            Self::Integers { len, .. } => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn into_iter(self) -> Box<dyn Iterator<Item = ValueCow<'r>> + 'r> {
+        match self {
+            Self::Values(values) => Box::new(values.into_iter()),
+            Self::Integers {
+                start,
+                len,
+                reversed,
+            } => Box::new((0..len).map(move |i| {
+                let i = if reversed { len - 1 - i } else { i };
+                Value::scalar(start + i as i64).into()
+            })),
+        }
+    }
+}
+
+/// The range a value holds, so its integers needn't all be allocated to loop over them.
+fn as_range(value: &dyn ValueView) -> Option<model::Range> {
+    if value.type_name() != "range" {
+        return None;
+    }
+    match value.to_value() {
+        Value::Range(range) => Some(range),
+        _ => None,
     }
 }
 
@@ -608,23 +753,33 @@ fn int_argument(arg: &Expression, runtime: &dyn Runtime, arg_name: &str) -> Resu
 }
 
 fn iter_array(
-    mut range: Vec<ValueCow<'_>>,
+    items: LoopItems<'_>,
     limit: Option<usize>,
     offset: usize,
     reversed: bool,
-) -> Vec<ValueCow<'_>> {
-    let offset = ::std::cmp::min(offset, range.len());
+) -> LoopItems<'_> {
+    let offset = ::std::cmp::min(offset, items.len());
     let limit = limit
-        .map(|l| ::std::cmp::min(l, range.len()))
-        .unwrap_or_else(|| range.len() - offset);
-    range.drain(0..offset);
-    range.resize(limit, Value::Nil.into());
+        .map(|l| ::std::cmp::min(l, items.len() - offset))
+        .unwrap_or_else(|| items.len() - offset);
 
-    if reversed {
-        range.reverse();
-    };
+    match items {
+        LoopItems::Values(mut range) => {
+            range.drain(0..offset);
+            range.resize(limit, Value::Nil.into());
+
+            if reversed {
+                range.reverse();
+            };
 
-    range
+            LoopItems::Values(range)
+        }
+        LoopItems::Integers { start, .. } => LoopItems::Integers {
+            start: start + offset as i64,
+            len: limit,
+            reversed,
+        },
+    }
 }
 
 /// Format an error for an unexpected value.
@@ -681,7 +836,28 @@ mod test {
             ]),
         );
         let output = template.render(&runtime).unwrap();
-        assert_eq!(output, "test 22 test 23 test 24 test wat ");
+        assert_eq!(output, "test 22.0 test 23.0 test 24.0 test wat ");
+    }
+
+    #[test]
+    fn loop_over_object() {
+        let text = concat!(
+            "{% for pair in settings %}{{ pair[0] }}={{ pair[1] }};{% endfor %} ",
+            "{% for pair in settings limit:1 %}{{ pair.first }}={{ pair.last }}{% endfor %}",
+        );
+
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let mut settings = liquid_core::Object::new();
+        settings.insert("size".into(), Value::scalar(2));
+        settings.insert("color".into(), Value::scalar("red"));
+        runtime.set_global("settings".into(), Value::Object(settings));
+        let output = template.render(&runtime).unwrap();
+        // Objects iterate in insertion order
+        assert_eq!(output, "size=2;color=red; size=2");
     }
 
     #[test]
@@ -725,6 +901,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn loop_over_long_range() {
+        let text = "{% for x in (1..1000001) %}{{x}}{% endfor %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        template.render(&runtime).unwrap_err();
+
+        let text = "{% for x in r limit:2 offset:3 reversed %}{{x}},{% endfor %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global(
+            "r".into(),
+            Value::Range(liquid_core::model::Range::new(1, i64::MAX)),
+        );
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "5,4,");
+    }
+
     #[test]
     fn nested_forloops() {
         // test that nest nested for loops work, and that the
@@ -845,6 +1045,44 @@ mod test {
         assert_eq!(output, "5 6 ");
     }
 
+    #[test]
+    fn limit_past_the_end() {
+        let text = concat!(
+            "{% for i in (1..5) offset:3 limit:5 %}",
+            "{{ i }} ",
+            "{% endfor %}"
+        );
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "4 5 ");
+    }
+
+    #[test]
+    fn offset_continue() {
+        let text = concat!(
+            "{% for i in array limit:2 %}{{ i }}{% endfor %} ",
+            "{% for i in array offset:continue limit:3 %}{{ i }}{% endfor %} ",
+            "{% for j in array offset:continue limit:1 %}{{ j }}{% endfor %} ",
+            "{% for i in array offset:continue %}{{ i }}{% endfor %} ",
+            "{% for i in array offset:continue %}{{ i }}{% else %}done{% endfor %}",
+        );
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global(
+            "array".into(),
+            Value::Array((1..=7).map(Value::scalar).collect()),
+        );
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "12 345 1 67 done");
+    }
+
     #[test]
     fn reversed_loop() {
         let text = concat!(
@@ -1041,7 +1279,7 @@ mod test {
             ]),
         );
         let output = template.render(&runtime).unwrap();
-        assert_eq!(output, "<tr class=\"row1\"><td class=\"col1\">test 22 </td><td class=\"col2\">test 23 </td><td class=\"col3\">test 24 </td><td class=\"col4\">test wat </td></tr>");
+        assert_eq!(output, "<tr class=\"row1\">\n<td class=\"col1\">test 22.0 </td><td class=\"col2\">test 23.0 </td><td class=\"col3\">test 24.0 </td><td class=\"col4\">test wat </td></tr>\n");
     }
 
     #[test]
@@ -1069,7 +1307,7 @@ mod test {
         let output = template.render(&runtime).unwrap();
         assert_eq!(
                 output,
-                "<tr class=\"row1\"><td class=\"col1\">test 42 </td><td class=\"col2\">test 43 </td></tr><tr class=\"row2\"><td class=\"col1\">test 44 </td><td class=\"col2\">test 45 </td></tr><tr class=\"row3\"><td class=\"col1\">test 46 </td></tr>"
+                "<tr class=\"row1\">\n<td class=\"col1\">test 42 </td><td class=\"col2\">test 43 </td></tr>\n<tr class=\"row2\"><td class=\"col1\">test 44 </td><td class=\"col2\">test 45 </td></tr>\n<tr class=\"row3\"><td class=\"col1\">test 46 </td></tr>\n"
             );
     }
 
@@ -1089,7 +1327,7 @@ mod test {
 
         let runtime = RuntimeBuilder::new().build();
         let output = template.render(&runtime).unwrap();
-        assert_eq!(output, "<tr class=\"row1\"><td class=\"col1\">6 </td><td class=\"col2\">7 </td><td class=\"col3\">8 </td></tr><tr class=\"row2\"><td class=\"col1\">9 </td></tr>");
+        assert_eq!(output, "<tr class=\"row1\">\n<td class=\"col1\">6 </td><td class=\"col2\">7 </td><td class=\"col3\">8 </td></tr>\n<tr class=\"row2\"><td class=\"col1\">9 </td></tr>\n");
     }
 
     #[test]
@@ -1120,14 +1358,85 @@ mod test {
         assert_eq!(
                 output,
                 concat!(
-    "<tr class=\"row1\"><td class=\"col1\">length: 4, index: 1, index0: 0, rindex: 4, rindex0: 3, col: 1, col0: 0, value: 100, first: true, last: false, col_first: true, col_last: false</td>",
-    "<td class=\"col2\">length: 4, index: 2, index0: 1, rindex: 3, rindex0: 2, col: 2, col0: 1, value: 101, first: false, last: false, col_first: false, col_last: true</td></tr>",
+    "<tr class=\"row1\">\n<td class=\"col1\">length: 4, index: 1, index0: 0, rindex: 4, rindex0: 3, col: 1, col0: 0, value: 100, first: true, last: false, col_first: true, col_last: false</td>",
+    "<td class=\"col2\">length: 4, index: 2, index0: 1, rindex: 3, rindex0: 2, col: 2, col0: 1, value: 101, first: false, last: false, col_first: false, col_last: true</td></tr>\n",
     "<tr class=\"row2\"><td class=\"col1\">length: 4, index: 3, index0: 2, rindex: 2, rindex0: 1, col: 1, col0: 0, value: 102, first: false, last: false, col_first: true, col_last: false</td>",
-    "<td class=\"col2\">length: 4, index: 4, index0: 3, rindex: 1, rindex0: 0, col: 2, col0: 1, value: 103, first: false, last: true, col_first: false, col_last: true</td></tr>",
+    "<td class=\"col2\">length: 4, index: 4, index0: 3, rindex: 1, rindex0: 0, col: 2, col0: 1, value: 103, first: false, last: true, col_first: false, col_last: true</td></tr>\n",
     )
             );
     }
 
+    #[test]
+    fn tablerowloop_variables() {
+        let text = concat!(
+            "{% tablerow v in (1..3) cols:2 %}",
+            "{{tablerowloop.row}}.{{tablerowloop.col}}",
+            "{% endtablerow %}",
+        );
+
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(
+            output,
+            "<tr class=\"row1\">\n<td class=\"col1\">1.1</td><td class=\"col2\">1.2</td></tr>\n<tr class=\"row2\"><td class=\"col1\">2.1</td></tr>\n"
+        );
+    }
+
+    #[test]
+    fn tablerowloop_all_variables() {
+        let text = concat!(
+            "{% tablerow v in (1..3) cols:2 %}",
+            "{{tablerowloop.length}}{{tablerowloop.index}}{{tablerowloop.index0}}",
+            "{{tablerowloop.rindex}}{{tablerowloop.rindex0}}",
+            "{{tablerowloop.col}}{{tablerowloop.col0}}",
+            "{% if tablerowloop.col_first %}F{% endif %}{% if tablerowloop.col_last %}L{% endif %}",
+            "{% if tablerowloop.first %}f{% endif %}{% if tablerowloop.last %}l{% endif %}",
+            "{% endtablerow %}",
+        );
+
+        let mut options = options();
+        options
+            .blocks
+            .register("if".to_string(), stdlib::IfBlock.into());
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(
+            output,
+            "<tr class=\"row1\">\n<td class=\"col1\">3103210Ff</td><td class=\"col2\">3212121L</td></tr>\n<tr class=\"row2\"><td class=\"col1\">3321010FLl</td></tr>\n"
+        );
+    }
+
+    #[test]
+    fn tablerow_object() {
+        let tablerow = TableRowObject::new(4, 6, 1, 3);
+        let tablerow = tablerow.as_object().unwrap();
+        assert_eq!(tablerow.get("row").unwrap().to_kstr(), "2");
+        assert_eq!(tablerow.get("col").unwrap().to_kstr(), "2");
+        assert_eq!(tablerow.get("col_last").unwrap().to_kstr(), "false");
+        assert_eq!(tablerow.get("rindex").unwrap().to_kstr(), "2");
+    }
+
+    #[test]
+    fn tablerow_empty() {
+        let text = "{% tablerow v in (1..0) %}{{v}}{% endtablerow %}";
+
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "<tr class=\"row1\">\n</tr>\n");
+    }
+
     #[test]
     fn test_for_parentloop_nil_when_not_present() {
         //NOTE: this test differs slightly from the liquid conformity test
@@ -1193,4 +1502,58 @@ mod test {
         let output = template.render(&runtime).unwrap();
         assert_eq!(output, "1.1 1.2 1.3 2.1 2.2 2.3 ");
     }
+
+    #[test]
+    fn test_for_parentloop_chain() {
+        let text = concat!(
+            "{% for a in (1..2) %}{% for b in (1..2) %}{% for c in (1..2) %}",
+            "{% if forloop.parentloop.parentloop.last and forloop.parentloop.first %}",
+            "{{ forloop.parentloop.parentloop.index }}{{ forloop.parentloop.index }}{{ forloop.index }} ",
+            "{% endif %}",
+            "{% endfor %}{% endfor %}{% endfor %}"
+        );
+
+        let mut options = options();
+        options
+            .blocks
+            .register("if".to_string(), stdlib::IfBlock.into());
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "211 212 ");
+    }
+
+    #[test]
+    fn else_on_empty_loop() {
+        let text = concat!(
+            "{% for i in missing %}{{ i }}{% else %}none{% endfor %} ",
+            "{% for i in array offset:3 %}{{ i }}{% else %}past{% endfor %} ",
+            "{% for i in array %}{{ i }}{% else %}unused{% endfor %}",
+        );
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("missing".into(), Value::Nil);
+        runtime.set_global(
+            "array".into(),
+            Value::Array(vec![Value::scalar(1), Value::scalar(2)]),
+        );
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "none past 12");
+    }
+
+    #[test]
+    fn else_errors() {
+        for text in [
+            "{% for i in array %}{% else %}{% else %}{% endfor %}",
+            "{% for i in array %}{% else i %}{% endfor %}",
+        ] {
+            assert!(parser::parse(text, &options()).is_err(), "{}", text);
+        }
+    }
 }
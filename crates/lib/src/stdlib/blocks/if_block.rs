@@ -2,7 +2,7 @@ use std::fmt;
 use std::io::Write;
 
 use liquid_core::error::ResultLiquidExt;
-use liquid_core::model::{ValueView, ValueViewCmp};
+use liquid_core::model::{ValueCow, ValueView, ValueViewCmp};
 use liquid_core::parser::BlockElement;
 use liquid_core::parser::TagToken;
 use liquid_core::Expression;
@@ -60,7 +60,19 @@ fn parse_if(
     options: &Language,
 ) -> Result<Box<dyn Renderable>> {
     let condition = parse_condition(arguments)?;
+    parse_branches(condition, true, tokens, options)
+}
 
+/// Parses the body of an `if`/`unless` along with its `elsif` and `else` branches.
+///
+/// `mode` is the value `condition` must evaluate to for the body to be rendered. Like Ruby
+/// Liquid, `elsif` branches are always regular `if` conditions, even within `unless`.
+fn parse_branches(
+    condition: Condition,
+    mode: bool,
+    tokens: &mut TagBlock<'_, '_>,
+    options: &Language,
+) -> Result<Box<dyn Renderable>> {
     let mut if_true = Vec::new();
     let mut if_false = None;
 
@@ -86,7 +98,7 @@ fn parse_if(
 
     Ok(Box::new(Conditional {
         condition,
-        mode: true,
+        mode,
         if_true,
         if_false,
     }))
@@ -123,33 +135,10 @@ impl ParseBlock for UnlessBlock {
         options: &Language,
     ) -> Result<Box<dyn Renderable>> {
         let condition = parse_condition(arguments)?;
-
-        let mut if_true = Vec::new();
-        let mut if_false = None;
-
-        while let Some(element) = tokens.next()? {
-            match element {
-                BlockElement::Tag(tag) => match tag.name() {
-                    "else" => {
-                        if_false = Some(tokens.parse_all(options)?);
-                        break;
-                    }
-                    _ => if_true.push(tag.parse(&mut tokens, options)?),
-                },
-                element => if_true.push(element.parse(&mut tokens, options)?),
-            }
-        }
-
-        let if_true = Template::new(if_true);
-        let if_false = if_false.map(Template::new);
+        let conditional = parse_branches(condition, false, &mut tokens, options)?;
 
         tokens.assert_empty();
-        Ok(Box::new(Conditional {
-            condition,
-            mode: false,
-            if_true,
-            if_false,
-        }))
+        Ok(conditional)
     }
 
     fn reflection(&self) -> &dyn BlockReflection {
@@ -173,7 +162,11 @@ impl Conditional {
     }
 
     fn trace(&self) -> String {
-        format!("{{% if {} %}}", self.condition)
+        if self.mode {
+            format!("{{% if {} %}}", self.condition)
+        } else {
+            format!("{{% unless {} %}}", self.condition)
+        }
     }
 }
 
@@ -196,7 +189,7 @@ impl Renderable for Conditional {
 }
 
 #[derive(Clone, Debug)]
-enum Condition {
+pub(crate) enum Condition {
     Binary(BinaryCondition),
     Existence(ExistenceCondition),
     Conjunction(Box<Condition>, Box<Condition>),
@@ -230,7 +223,7 @@ impl fmt::Display for Condition {
 }
 
 #[derive(Clone, Debug)]
-struct BinaryCondition {
+pub(crate) struct BinaryCondition {
     lh: Expression,
     comparison: ComparisonOperator,
     rh: Expression,
@@ -238,9 +231,9 @@ struct BinaryCondition {
 
 impl BinaryCondition {
     pub fn evaluate(&self, runtime: &dyn Runtime) -> Result<bool> {
-        let a = self.lh.evaluate(runtime)?;
+        let a = evaluate_operand(&self.lh, &self.rh, runtime)?;
         let ca = ValueViewCmp::new(a.as_view());
-        let b = self.rh.evaluate(runtime)?;
+        let b = evaluate_operand(&self.rh, &self.lh, runtime)?;
         let cb = ValueViewCmp::new(b.as_view());
 
         let result = match self.comparison {
@@ -257,6 +250,24 @@ impl BinaryCondition {
     }
 }
 
+/// Like Ruby, an undefined variable is nil when checking whether it is `nil`, `blank` or `empty`,
+/// as in `{% if page.title == blank %}`.
+fn evaluate_operand<'r>(
+    operand: &'r Expression,
+    other: &Expression,
+    runtime: &'r dyn Runtime,
+) -> Result<ValueCow<'r>> {
+    let checks_presence = match other {
+        Expression::Literal(literal) => literal.is_nil() || literal.as_state().is_some(),
+        _ => false,
+    };
+    if checks_presence {
+        Ok(operand.try_evaluate(runtime).unwrap_or_default())
+    } else {
+        operand.evaluate(runtime)
+    }
+}
+
 impl fmt::Display for BinaryCondition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} {} {}", self.lh, self.comparison, self.rh)
@@ -330,7 +341,7 @@ impl ComparisonOperator {
 }
 
 #[derive(Clone, Debug)]
-struct ExistenceCondition {
+pub(crate) struct ExistenceCondition {
     lh: Expression,
 }
 
@@ -423,7 +434,7 @@ fn parse_conjunction_chain(arguments: &mut PeekableTagTokenIter<'_>) -> Result<C
 }
 
 /// Common parsing for "if" and "unless" condition
-fn parse_condition(arguments: TagTokenIter<'_>) -> Result<Condition> {
+pub(crate) fn parse_condition(arguments: TagTokenIter<'_>) -> Result<Condition> {
     let mut arguments = PeekableTagTokenIter {
         iter: arguments,
         peeked: None,
@@ -575,6 +586,41 @@ mod test {
         assert_eq!(output, "unless body");
     }
 
+    #[test]
+    fn unless_elsif_else() {
+        let text = concat!(
+            "{% unless some_value == 1 %}",
+            "unless body",
+            "{% elsif some_value == 1 and other_value %}",
+            "elsif body",
+            "{% else %}",
+            "else body",
+            "{% endunless %}"
+        );
+
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("some_value".into(), Value::scalar(42f64));
+        runtime.set_global("other_value".into(), Value::scalar(true));
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "unless body");
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("some_value".into(), Value::scalar(1f64));
+        runtime.set_global("other_value".into(), Value::scalar(true));
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "elsif body");
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("some_value".into(), Value::scalar(1f64));
+        runtime.set_global("other_value".into(), Value::scalar(false));
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "else body");
+    }
+
     #[test]
     fn nested_if_else() {
         let text = concat!(
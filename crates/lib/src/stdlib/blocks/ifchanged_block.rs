@@ -83,7 +83,9 @@ impl Renderable for IfChanged {
     }
 }
 
-/// Remembers the content of the last rendered `ifstate` block.
+/// Remembers the content of the last rendered `ifchanged` block.
+///
+/// Like Ruby Liquid, this is shared by all `ifchanged` blocks within a render.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 struct ChangedRegister {
     last_rendered: Option<String>,
@@ -148,4 +150,20 @@ mod test {
         let output = template.render(&runtime).unwrap();
         assert_eq!(output, "\nHey! \nHey! Numbers are now bigger than 5!");
     }
+
+    #[test]
+    fn test_ifchanged_state_is_per_render() {
+        let text = "{% for a in (0..2) %}{% ifchanged %}same{% endifchanged %}{% endfor %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "same");
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "same");
+    }
 }
@@ -13,9 +13,11 @@ pub use self::for_block::ForBlock;
 pub use self::for_block::TableRowBlock;
 pub use self::if_block::IfBlock;
 pub use self::if_block::UnlessBlock;
+pub(crate) use self::if_block::{parse_condition, Condition};
 pub use self::ifchanged_block::IfChangedBlock;
 pub use self::raw_block::RawBlock;
 
 pub use self::for_block::ForloopObject;
 pub use self::for_block::Range;
 pub use self::for_block::RangeExpression;
+pub use self::for_block::TableRowObject;
@@ -1,6 +1,10 @@
 use std::cmp;
+use std::collections::HashMap;
 
-use liquid_core::model::ValueViewCmp;
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::model::{KStringRef, ObjectView, ValueViewCmp};
+use liquid_core::parser::{self, Comparator, Operator};
+use liquid_core::runtime::{LanguageRegister, StackFrame};
 use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
@@ -9,6 +13,7 @@ use liquid_core::{
 };
 use liquid_core::{Value, ValueCow, ValueView};
 
+use crate::stdlib::{parse_condition, Condition};
 use crate::{invalid_argument, invalid_input};
 
 fn as_sequence<'k>(input: &'k dyn ValueView) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
@@ -61,18 +66,57 @@ impl Filter for JoinFilter {
     }
 }
 
-fn nil_safe_compare(a: &dyn ValueView, b: &dyn ValueView) -> Option<cmp::Ordering> {
+/// Where `sort` places nils.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+enum NilOrder {
+    First,
+    #[default]
+    Last,
+}
+
+impl NilOrder {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "first" => Some(Self::First),
+            "last" => Some(Self::Last),
+            _ => None,
+        }
+    }
+
+    /// How a nil compares to anything else.
+    fn nil_ordering(self) -> cmp::Ordering {
+        match self {
+            Self::First => cmp::Ordering::Less,
+            Self::Last => cmp::Ordering::Greater,
+        }
+    }
+}
+
+fn nil_safe_compare(
+    a: &dyn ValueView,
+    b: &dyn ValueView,
+    nils: NilOrder,
+    comparator: Option<&dyn Comparator>,
+) -> Option<cmp::Ordering> {
     if a.is_nil() && b.is_nil() {
         Some(cmp::Ordering::Equal)
     } else if a.is_nil() {
-        Some(cmp::Ordering::Greater)
+        Some(nils.nil_ordering())
     } else if b.is_nil() {
-        Some(cmp::Ordering::Less)
+        Some(nils.nil_ordering().reverse())
+    } else if let Some(comparator) = comparator {
+        Some(comparator.compare(a, b))
     } else {
         ValueViewCmp::new(a).partial_cmp(&ValueViewCmp::new(b))
     }
 }
 
+fn is_date(value: &dyn ValueView) -> bool {
+    value
+        .as_scalar()
+        .is_some_and(|s| s.as_date().is_some() || s.as_date_time().is_some())
+}
+
 fn nil_safe_casecmp_key(value: &dyn ValueView) -> Option<String> {
     if value.is_nil() {
         None
@@ -86,21 +130,87 @@ fn nil_safe_casecmp(a: &Option<String>, b: &Option<String>) -> Option<cmp::Order
         (None, None) => Some(cmp::Ordering::Equal),
         (None, _) => Some(cmp::Ordering::Greater),
         (_, None) => Some(cmp::Ordering::Less),
-        (a, b) => a.partial_cmp(b),
+        (Some(a), Some(b)) => Some(natural_cmp(a, b)),
     }
 }
 
+/// Compare strings with their runs of digits as numbers, so `a2` comes before `a10`.
+fn natural_cmp(a: &str, b: &str) -> cmp::Ordering {
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return cmp::Ordering::Equal,
+            (None, Some(_)) => return cmp::Ordering::Less,
+            (Some(_), None) => return cmp::Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = take_digits(&mut a);
+                let y = take_digits(&mut b);
+                let ordering = numeric_cmp(&x, &y);
+                if ordering != cmp::Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    digits
+}
+
+/// Compare runs of digits of any length by value, then by their leading zeros.
+fn numeric_cmp(a: &str, b: &str) -> cmp::Ordering {
+    let a_value = a.trim_start_matches('0');
+    let b_value = b.trim_start_matches('0');
+    a_value
+        .len()
+        .cmp(&b_value.len())
+        .then_with(|| a_value.cmp(b_value))
+        .then_with(|| a.len().cmp(&b.len()))
+}
+
 #[derive(Debug, Default, FilterParameters)]
 struct PropertyArgs {
     #[parameter(description = "The property accessed by the filter.", arg_type = "str")]
     property: Option<Expression>,
 }
 
+#[derive(Debug, Default, FilterParameters)]
+struct SortArgs {
+    #[parameter(description = "The property to sort by.", arg_type = "str")]
+    property: Option<Expression>,
+
+    #[parameter(
+        description = "Whether nils sort \"first\" or \"last\". Defaults to \"last\".",
+        arg_type = "str"
+    )]
+    nils: Option<Expression>,
+
+    #[parameter(
+        description = "The name of a comparator registered with the parser, rather than the default order.",
+        arg_type = "str",
+        mode = "keyword"
+    )]
+    comparator: Option<Expression>,
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "sort",
     description = "Sorts items in an array. The order of the sorted array is case-sensitive.",
-    parameters(PropertyArgs),
+    parameters(SortArgs),
     parsed(SortFilter)
 )]
 pub struct Sort;
@@ -109,14 +219,15 @@ pub struct Sort;
 #[name = "sort"]
 struct SortFilter {
     #[parameters]
-    args: PropertyArgs,
+    args: SortArgs,
 }
 
-fn safe_property_getter<'a>(value: &'a Value, property: &str) -> &'a dyn ValueView {
+/// The `property` of `value`, or nil, computing it if `value` is a drop.
+fn property_of<'v>(value: &'v dyn ValueView, property: &str) -> ValueCow<'v> {
     value
         .as_object()
-        .and_then(|obj| obj.get(property))
-        .unwrap_or(&Value::Nil)
+        .and_then(|obj| obj.resolve(property))
+        .unwrap_or_default()
 }
 
 impl Filter for SortFilter {
@@ -128,27 +239,53 @@ impl Filter for SortFilter {
             return Err(invalid_input("Array of objects expected"));
         }
 
-        let mut sorted: Vec<Value> = input.iter().map(|v| v.to_value()).collect();
-        if let Some(property) = &args.property {
-            // Using unwrap is ok since all of the elements are objects
-            sorted.sort_by(|a, b| {
-                nil_safe_compare(
-                    safe_property_getter(a, property),
-                    safe_property_getter(b, property),
-                )
-                .unwrap_or(cmp::Ordering::Equal)
-            });
-        } else {
-            sorted.sort_by(|a, b| nil_safe_compare(a, b).unwrap_or(cmp::Ordering::Equal));
+        let nils = match args.nils {
+            Some(nils) => NilOrder::from_name(nils.as_str())
+                .ok_or_else(|| invalid_argument("nils", "Expected \"first\" or \"last\""))?,
+            None => NilOrder::default(),
+        };
+
+        let comparator = match args.comparator {
+            Some(name) => Some(
+                runtime
+                    .registers()
+                    .get_mut::<LanguageRegister>()
+                    .language()
+                    .and_then(|language| language.comparators.get(name.as_str()).cloned())
+                    .ok_or_else(|| invalid_argument("comparator", "Unknown comparator"))?,
+            ),
+            None => None,
+        };
+        let comparator = comparator.as_deref();
+
+        let mut sorted: Vec<_> = input
+            .iter()
+            .map(|v| match &args.property {
+                Some(property) => (property_of(*v, property), *v),
+                None => (ValueCow::Borrowed(*v), *v),
+            })
+            .collect();
+        // Parse date strings once, rather than on every comparison with a date.
+        if comparator.is_none() && sorted.iter().any(|(key, _)| is_date(key.as_view())) {
+            for (key, _) in &mut sorted {
+                if let Some(date) = key.as_scalar().and_then(|s| s.to_date_like()) {
+                    *key = ValueCow::Owned(Value::Scalar(date));
+                }
+            }
         }
-        Ok(Value::array(sorted))
+        sorted.sort_by(|a, b| {
+            nil_safe_compare(a.0.as_view(), b.0.as_view(), nils, comparator)
+                .unwrap_or(cmp::Ordering::Equal)
+        });
+        let result: Vec<_> = sorted.into_iter().map(|(_, v)| v.to_value()).collect();
+        Ok(Value::array(result))
     }
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "sort_natural",
-    description = "Sorts items in an array.",
+    description = "Sorts items in an array, ignoring case and comparing numbers by value, so `a2` comes before `a10`.",
     parameters(PropertyArgs),
     parsed(SortNaturalFilter)
 )]
@@ -173,11 +310,10 @@ impl Filter for SortNaturalFilter {
         let mut sorted: Vec<_> = if let Some(property) = &args.property {
             input
                 .iter()
-                .map(|v| v.to_value())
                 .map(|v| {
                     (
-                        nil_safe_casecmp_key(&safe_property_getter(&v, property).to_value()),
-                        v,
+                        nil_safe_casecmp_key(property_of(*v, property).as_view()),
+                        v.to_value(),
                     )
                 })
                 .collect()
@@ -226,7 +362,8 @@ impl Filter for WhereFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
         let property: &str = &args.property;
-        let target_value: Option<ValueCow<'_>> = args.target_value;
+        // Like Ruby, an explicit `nil` target is the same as no target.
+        let target_value: Option<ValueCow<'_>> = args.target_value.filter(|v| !v.is_nil());
 
         if let Some(array) = input.as_array() {
             if !array.values().all(|v| v.is_object()) {
@@ -238,64 +375,196 @@ impl Filter for WhereFilter {
             ));
         }
 
-        let input = as_sequence(input);
-        let array: Vec<_> = match target_value {
-            None => input
-                .filter_map(|v| v.as_object())
-                .filter(|object| {
-                    object
-                        .get(property)
-                        .map(|v| v.query_state(liquid_core::model::State::Truthy))
-                        .unwrap_or(false)
-                })
-                .map(|object| object.to_value())
-                .collect(),
-            Some(target_value) => input
-                .filter_map(|v| v.as_object())
-                .filter(|object| {
-                    object
-                        .get(property)
-                        .map(|value| {
-                            let value = ValueViewCmp::new(value);
-                            target_value == value
-                        })
-                        .unwrap_or(false)
-                })
-                .map(|object| object.to_value())
-                .collect(),
-        };
+        let array: Vec<_> = as_sequence(input)
+            .filter_map(|v| v.as_object())
+            .filter(|object| has_property(*object, property, target_value.as_ref()))
+            .map(|object| object.to_value())
+            .collect();
         Ok(Value::array(array))
     }
 }
 
+/// Whether `object`'s `property` equals `target_value`, or is truthy when there is no target.
+fn has_property(
+    object: &dyn ObjectView,
+    property: &str,
+    target_value: Option<&ValueCow<'_>>,
+) -> bool {
+    match (object.resolve(property), target_value) {
+        (Some(value), None) => value.query_state(liquid_core::model::State::Truthy),
+        (Some(value), Some(target_value)) => *target_value == ValueViewCmp::new(value.as_view()),
+        (None, _) => false,
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "find",
+    description = "Returns the first element of an array with a certain property value, or nil. \
+                   By default the target is any truthy value.",
+    parameters(WhereArgs),
+    parsed(FindFilter)
+)]
+pub struct Find;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "find"]
+struct FindFilter {
+    #[parameters]
+    args: WhereArgs,
+}
+
+impl Filter for FindFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        let property: &str = &args.property;
+        let target_value: Option<ValueCow<'_>> = args.target_value.filter(|v| !v.is_nil());
+
+        let found = as_sequence(input)
+            .filter_map(|v| v.as_object())
+            .find(|object| has_property(*object, property, target_value.as_ref()));
+        Ok(found.map(|object| object.to_value()).unwrap_or(Value::Nil))
+    }
+}
+
+#[derive(Debug, FilterParameters)]
+struct FindExpArgs {
+    #[parameter(
+        description = "The name each element is known by in the expression.",
+        arg_type = "str"
+    )]
+    variable: Expression,
+
+    #[parameter(
+        description = "The condition the element must meet, like in `{% if %}`.",
+        arg_type = "str"
+    )]
+    condition: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "find_exp",
+    description = "Returns the first element of an array meeting a condition, or nil, like `find_exp: \"item\", \"item.price > 100\"`.",
+    parameters(FindExpArgs),
+    parsed(FindExpFilter)
+)]
+pub struct FindExp;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "find_exp"]
+struct FindExpFilter {
+    #[parameters]
+    args: FindExpArgs,
+}
+
+impl Filter for FindExpFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let variable = expect_variable_name(args.variable.as_str())?;
+        let condition = parse_exp_condition(args.condition.as_str())?;
+
+        let elements = match elements(input) {
+            Some(elements) => elements,
+            None => return Ok(input.to_value()),
+        };
+
+        for element in elements {
+            if satisfies(&condition, variable, element, runtime)? {
+                return Ok(element.to_value());
+            }
+        }
+
+        Ok(Value::Nil)
+    }
+}
+
+/// Adds up the numbers in an array, treating anything else as `0`.
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "sum",
+    description = "Adds up the numbers in an array, or a property of its objects.",
+    parameters(PropertyArgs),
+    parsed(SumFilter)
+)]
+pub struct Sum;
+
+#[derive(Debug, Default, FromFilterParameters, Display_filter)]
+#[name = "sum"]
+struct SumFilter {
+    #[parameters]
+    args: PropertyArgs,
+}
+
+impl Filter for SumFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        // Like Ruby, anything that isn't a number counts as `0`.
+        let zero = Value::scalar(0);
+        let mut sum = zero.clone();
+        for element in as_sequence(input) {
+            let value = match &args.property {
+                Some(property) => property_of(element, property),
+                None => ValueCow::Borrowed(element),
+            };
+            let value = match value.as_scalar() {
+                Some(scalar) if scalar.to_integer().is_some() || scalar.to_float().is_some() => {
+                    value.as_view()
+                }
+                _ => &zero,
+            };
+            sum = Operator::Add.apply(&sum, value)?;
+        }
+        Ok(sum)
+    }
+}
+
 /// Removes any duplicate elements in an array.
 ///
-/// This has an O(n^2) worst-case complexity.
+/// This has an O(n^2) worst-case complexity.  Elements are the same when they compare equal,
+/// like `1` and `1.0`, keeping the first.
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "uniq",
-    description = "Removes any duplicate elements in an array.",
+    description = "Removes any duplicate elements in an array, or elements with a duplicate property.",
+    parameters(PropertyArgs),
     parsed(UniqFilter)
 )]
 pub struct Uniq;
 
-#[derive(Debug, Default, Display_filter)]
+#[derive(Debug, Default, FromFilterParameters, Display_filter)]
 #[name = "uniq"]
-struct UniqFilter;
+struct UniqFilter {
+    #[parameters]
+    args: PropertyArgs,
+}
 
 impl Filter for UniqFilter {
-    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-        // TODO(#267) optional property parameter
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
 
-        let array = input
-            .as_array()
-            .ok_or_else(|| invalid_input("Array expected"))?;
-        let mut deduped: Vec<Value> = Vec::with_capacity(array.size() as usize);
-        for x in array.values() {
-            if !deduped
+        let input: Vec<_> = as_sequence(input).collect();
+        if args.property.is_some() && !input.iter().all(|v| v.is_object()) {
+            return Err(invalid_input("Array of objects expected"));
+        }
+
+        let key = |value: &'_ dyn ValueView| -> Value {
+            match &args.property {
+                Some(property) => property_of(value, property).into_owned(),
+                None => value.to_value(),
+            }
+        };
+        let mut seen: Vec<Value> = Vec::with_capacity(input.len());
+        let mut deduped: Vec<Value> = Vec::with_capacity(input.len());
+        for x in input {
+            let x_key = key(x);
+            if !seen
                 .iter()
-                .any(|v| ValueViewCmp::new(v.as_view()) == ValueViewCmp::new(x))
+                .any(|v| ValueViewCmp::new(v.as_view()) == ValueViewCmp::new(x_key.as_view()))
             {
+                seen.push(x_key);
                 deduped.push(x.to_value())
             }
         }
@@ -340,7 +609,7 @@ struct MapArgs {
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "map",
-    description = "Extract `property` from the `Value::Object` elements of an array.",
+    description = "Extract `property` from the `Value::Object` elements of an array, or nil when missing.",
     parameters(MapArgs),
     parsed(MapFilter)
 )]
@@ -357,22 +626,34 @@ impl Filter for MapFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let array = input
-            .as_array()
-            .ok_or_else(|| invalid_input("Array expected"))?;
+        if !(input.is_array() || input.is_object() || input.is_nil()) {
+            return Err(invalid_input("Array expected"));
+        }
 
-        let result: Vec<_> = array
-            .values()
-            .filter_map(|v| {
-                v.as_object()
-                    .and_then(|v| v.get(&args.property))
-                    .map(|v| v.to_value())
-            })
+        // Like Ruby, nested arrays are flattened and missing properties are nil.
+        let mut elements = Vec::new();
+        if !input.is_nil() {
+            flatten_into(input, &mut elements);
+        }
+        let result: Vec<_> = elements
+            .into_iter()
+            .map(|v| property_of(v, &args.property).into_owned())
             .collect();
         Ok(Value::array(result))
     }
 }
 
+fn flatten_into<'v>(input: &'v dyn ValueView, elements: &mut Vec<&'v dyn ValueView>) {
+    match input.as_array() {
+        Some(array) => {
+            for value in array.values() {
+                flatten_into(value, elements);
+            }
+        }
+        None => elements.push(input),
+    }
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "compact",
@@ -393,28 +674,21 @@ impl Filter for CompactFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let array = input
-            .as_array()
-            .ok_or_else(|| invalid_input("Array expected"))?;
+        let array: Vec<_> = as_sequence(input).collect();
 
         let result: Vec<_> = if let Some(property) = &args.property {
-            if !array.values().all(|v| v.is_object()) {
+            if !array.iter().all(|v| v.is_object()) {
                 return Err(invalid_input("Array of objects expected"));
             }
             // Reject non objects that don't have the required property
             array
-                .values()
-                .filter(|v| {
-                    !v.as_object()
-                        .and_then(|obj| obj.get(property.as_str()))
-                        .map(|v| v.is_nil())
-                        .unwrap_or(true)
-                })
+                .iter()
+                .filter(|v| !property_of(**v, property).is_nil())
                 .map(|v| v.to_value())
                 .collect()
         } else {
             array
-                .values()
+                .iter()
                 .filter(|v| !v.is_nil())
                 .map(|v| v.to_value())
                 .collect()
@@ -450,17 +724,15 @@ impl Filter for ConcatFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let input = input
-            .as_array()
-            .ok_or_else(|| invalid_input("Array expected"))?;
-        let input = input.values().map(|v| v.to_value());
-
-        let array = args
-            .array
-            .as_array()
-            .ok_or_else(|| invalid_argument("array", "Array expected"))?;
+        let array = args.array.as_array().ok_or_else(|| {
+            invalid_argument("array", "Array expected")
+                .context("found", args.array.type_name().to_owned())
+        })?;
         let array = array.values().map(|v| v.to_value());
 
+        // Like Ruby, a lone value is concatenated as an array of one.
+        let input = as_sequence(input).map(|v| v.to_value());
+
         let result = input.chain(array);
         let result: Vec<_> = result.collect();
         Ok(Value::array(result))
@@ -529,11 +801,62 @@ impl Filter for LastFilter {
     }
 }
 
+/// The name elements are known by in an expression, like the `item` in `where_exp: "item", ...`.
+pub(crate) fn expect_variable_name(variable: &str) -> Result<&str> {
+    let is_identifier = variable
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if variable.is_empty() || !is_identifier {
+        return Err(invalid_argument("variable", "Identifier expected"));
+    }
+    Ok(variable)
+}
+
+/// The elements of an array, or the values of an object.
+pub(crate) fn elements(input: &dyn ValueView) -> Option<Vec<&dyn ValueView>> {
+    if let Some(array) = input.as_array() {
+        Some(array.values().collect())
+    } else {
+        input.as_object().map(|object| object.values().collect())
+    }
+}
+
+/// The condition is only known when rendering, so parse it like the condition of an `{% if %}`.
+pub(crate) fn parse_exp_condition(condition: &str) -> Result<Condition> {
+    parser::parse_tag_tokens(condition)
+        .and_then(parse_condition)
+        .context_key("condition")
+        .value_with(|| condition.to_owned().into())
+}
+
+pub(crate) fn satisfies(
+    condition: &Condition,
+    variable: &str,
+    element: &dyn ValueView,
+    runtime: &dyn Runtime,
+) -> Result<bool> {
+    let scope = element_scope(variable, element);
+    let frame = StackFrame::new(runtime, &scope);
+    condition.evaluate(&frame)
+}
+
+/// The variable naming an element, borrowing the element rather than copying it.
+pub(crate) fn element_scope<'e>(
+    variable: &'e str,
+    element: &'e dyn ValueView,
+) -> HashMap<KStringRef<'e>, &'e dyn ValueView> {
+    let mut scope = HashMap::new();
+    scope.insert(KStringRef::from_ref(variable), element);
+    scope
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
 
+    use liquid_core::model::{DisplayCow, KStringCow, ObjectRender, ObjectSource, State};
+
     #[test]
     fn unit_concat_nothing() {
         let input = liquid_core::value!([1f64, 2f64]);
@@ -621,7 +944,7 @@ mod tests {
         let input = liquid_core::value!(["a", "b", "c"]);
         assert_eq!(
             liquid_core::call_filter!(Join, input, 1f64).unwrap(),
-            "a1b1c"
+            "a1.0b1.0c"
         );
     }
 
@@ -636,7 +959,101 @@ mod tests {
         let input = liquid_core::value!(["a", 1f64, "c"]);
         assert_eq!(
             liquid_core::call_filter!(Join, input, ",").unwrap(),
-            liquid_core::value!("a,1,c")
+            liquid_core::value!("a,1.0,c")
+        );
+    }
+
+    /// Computes `price` from `cents` only when it's asked for.
+    #[derive(Debug)]
+    struct Product {
+        cents: i64,
+    }
+
+    impl ValueView for Product {
+        fn as_debug(&self) -> &dyn std::fmt::Debug {
+            self
+        }
+        fn render(&self) -> DisplayCow<'_> {
+            DisplayCow::Owned(Box::new(ObjectRender::new(self)))
+        }
+        fn source(&self) -> DisplayCow<'_> {
+            DisplayCow::Owned(Box::new(ObjectSource::new(self)))
+        }
+        fn type_name(&self) -> &'static str {
+            "object"
+        }
+        fn query_state(&self, state: State) -> bool {
+            state == State::Truthy
+        }
+        fn to_kstr(&self) -> KStringCow<'_> {
+            self.render().to_string().into()
+        }
+        fn to_value(&self) -> Value {
+            liquid_core::value!({ "cents": self.cents })
+        }
+        fn as_object(&self) -> Option<&dyn ObjectView> {
+            Some(self)
+        }
+    }
+
+    impl ObjectView for Product {
+        fn as_value(&self) -> &dyn ValueView {
+            self
+        }
+        fn size(&self) -> i64 {
+            1
+        }
+        fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
+            Box::new(std::iter::once("cents".into()))
+        }
+        fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+            Box::new(std::iter::once(&self.cents as &dyn ValueView))
+        }
+        fn iter<'k>(
+            &'k self,
+        ) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
+            Box::new(self.keys().zip(self.values()))
+        }
+        fn contains_key(&self, index: &str) -> bool {
+            matches!(index, "cents" | "price")
+        }
+        fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
+            match index {
+                "cents" => Some(&self.cents),
+                _ => None,
+            }
+        }
+        fn resolve<'s>(&'s self, index: &str) -> Option<ValueCow<'s>> {
+            match index {
+                "price" => Some(ValueCow::Owned(Value::scalar(self.cents as f64 / 100.0))),
+                _ => self.get(index).map(ValueCow::Borrowed),
+            }
+        }
+    }
+
+    #[test]
+    fn unit_drop_properties() {
+        let products = vec![Product { cents: 1250 }, Product { cents: 300 }];
+        let call = |filter: &dyn ParseFilter, args: Vec<Value>| {
+            let args = liquid_core::parser::FilterArguments {
+                positional: Box::new(args.into_iter().map(Expression::Literal)),
+                keyword: Box::new(std::iter::empty()),
+            };
+            let runtime = liquid_core::runtime::RuntimeBuilder::new().build();
+            filter.parse(args)?.evaluate(&products, &runtime)
+        };
+
+        assert_eq!(
+            call(&Map, vec![Value::scalar("price")]).unwrap(),
+            liquid_core::value!([12.5, 3.0])
+        );
+        assert_eq!(
+            call(&Sort, vec![Value::scalar("price")]).unwrap(),
+            liquid_core::value!([{ "cents": 300 }, { "cents": 1250 }])
+        );
+        assert_eq!(
+            call(&Where, vec![Value::scalar("price"), Value::scalar(3.0)]).unwrap(),
+            liquid_core::value!([{ "cents": 300 }])
         );
     }
 
@@ -650,6 +1067,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_sort_property_nils() {
+        let input = &liquid_core::value!([
+            { "title": "b", "date": "2020-02-01" },
+            { "title": "draft" },
+            { "title": "a", "date": "2020-01-01" },
+        ]);
+        let nils_last = liquid_core::value!([
+            { "title": "a", "date": "2020-01-01" },
+            { "title": "b", "date": "2020-02-01" },
+            { "title": "draft" },
+        ]);
+        let nils_first = liquid_core::value!([
+            { "title": "draft" },
+            { "title": "a", "date": "2020-01-01" },
+            { "title": "b", "date": "2020-02-01" },
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Sort, input, "date").unwrap(),
+            nils_last
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sort, input, "date", "last").unwrap(),
+            nils_last
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sort, input, "date", "first").unwrap(),
+            nils_first
+        );
+        liquid_core::call_filter!(Sort, input, "date", "middle").unwrap_err();
+    }
+
+    #[test]
+    fn unit_sort_dates_with_strings() {
+        let date = Value::scalar(liquid_core::model::Date::from_ymd(2020, 3, 1));
+        let input = &Value::Array(vec![
+            date.clone(),
+            Value::scalar("2020-02-01 10:00:00 +0000"),
+            Value::scalar("2020-01-15"),
+        ]);
+        let desired_result = Value::Array(vec![
+            Value::scalar("2020-01-15"),
+            Value::scalar("2020-02-01 10:00:00 +0000"),
+            date,
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Sort, input).unwrap(),
+            desired_result
+        );
+    }
+
+    #[test]
+    fn unit_sort_unknown_comparator() {
+        let input = &liquid_core::value!(["b", "a"]);
+        liquid_core::call_filter!(Sort, input; comparator = "semver").unwrap_err();
+    }
+
     #[test]
     fn unit_sort_natural() {
         let input = &liquid_core::value!(["Z", "b", "c", "a"]);
@@ -660,6 +1134,184 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_sort_natural_numbers() {
+        let input = &liquid_core::value!(["a10", "A2", "a1", "b", "a02", "10", "9"]);
+        let desired_result = liquid_core::value!(["9", "10", "a1", "A2", "a02", "a10", "b"]);
+        assert_eq!(
+            liquid_core::call_filter!(SortNatural, input).unwrap(),
+            desired_result
+        );
+
+        let input = &liquid_core::value!([{ "sku": "x-10" }, { "sku": "X-9" }, {}]);
+        let desired_result = liquid_core::value!([{ "sku": "X-9" }, { "sku": "x-10" }, {}]);
+        assert_eq!(
+            liquid_core::call_filter!(SortNatural, input, "sku").unwrap(),
+            desired_result
+        );
+    }
+
+    #[test]
+    fn unit_compact() {
+        let input = liquid_core::value!([1, nil, "a", nil, false]);
+        let desired_result = liquid_core::value!([1, "a", false]);
+        assert_eq!(
+            liquid_core::call_filter!(Compact, input).unwrap(),
+            desired_result
+        );
+
+        assert_eq!(
+            liquid_core::call_filter!(Compact, "a").unwrap(),
+            liquid_core::value!(["a"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Compact, Value::Nil).unwrap(),
+            liquid_core::value!([])
+        );
+    }
+
+    #[test]
+    fn unit_compact_property() {
+        let input = liquid_core::value!([
+            { "title": "a", "author": "me" },
+            { "title": "b", "author": nil },
+            { "title": "c" },
+            { "title": "d", "author": false },
+        ]);
+        let desired_result = liquid_core::value!([
+            { "title": "a", "author": "me" },
+            { "title": "d", "author": false },
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Compact, input, "author").unwrap(),
+            desired_result
+        );
+    }
+
+    #[test]
+    fn unit_concat_non_array() {
+        let input = liquid_core::value!(["a"]);
+        let error = liquid_core::call_filter!(Concat, input, "b").unwrap_err();
+        assert!(error.to_string().contains("found=string"), "{}", error);
+
+        assert_eq!(
+            liquid_core::call_filter!(Concat, "a", liquid_core::value!(["b"])).unwrap(),
+            liquid_core::value!(["a", "b"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Concat, Value::Nil, liquid_core::value!(["b"])).unwrap(),
+            liquid_core::value!(["b"])
+        );
+    }
+
+    #[test]
+    fn unit_find() {
+        let input = liquid_core::value!([
+            { "id": 1, "title": "a", "available": false },
+            { "id": 42, "title": "b", "available": true },
+            { "id": 42, "title": "c", "available": true },
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Find, input.clone(), "id", 42).unwrap(),
+            liquid_core::value!({ "id": 42, "title": "b", "available": true })
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Find, input.clone(), "available").unwrap(),
+            liquid_core::value!({ "id": 42, "title": "b", "available": true })
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Find, input, "id", 7).unwrap(),
+            liquid_core::Value::Nil
+        );
+    }
+
+    #[test]
+    fn unit_find_non_array() {
+        assert_eq!(
+            liquid_core::call_filter!(Find, liquid_core::value!({ "a": "ok" }), "a", "ok").unwrap(),
+            liquid_core::value!({ "a": "ok" })
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Find, liquid_core::value!([1, { "a": "ok" }]), "a").unwrap(),
+            liquid_core::value!({ "a": "ok" })
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Find, liquid_core::Value::Nil, "a").unwrap(),
+            liquid_core::Value::Nil
+        );
+    }
+
+    #[test]
+    fn unit_find_exp() {
+        let input = liquid_core::value!([
+            { "title": "a", "price": 50 },
+            { "title": "b", "price": 150 },
+            { "title": "c", "price": 250 },
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(FindExp, input.clone(), "item", "item.price > 100").unwrap(),
+            liquid_core::value!({ "title": "b", "price": 150 })
+        );
+        assert_eq!(
+            liquid_core::call_filter!(FindExp, input, "item", "item.price > 1000").unwrap(),
+            liquid_core::Value::Nil
+        );
+        assert_eq!(
+            liquid_core::call_filter!(FindExp, liquid_core::value!([1, 5, 10]), "n", "n >= 5")
+                .unwrap(),
+            liquid_core::value!(5)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(FindExp, "abc", "n", "n == 5").unwrap(),
+            liquid_core::value!("abc")
+        );
+        liquid_core::call_filter!(FindExp, liquid_core::value!([1]), "n.x", "true").unwrap_err();
+        liquid_core::call_filter!(FindExp, liquid_core::value!([1]), "n", "n %} {% if")
+            .unwrap_err();
+        liquid_core::call_filter!(FindExp, liquid_core::value!([1]), "n", "n %}true{% endif")
+            .unwrap_err();
+        assert_eq!(
+            liquid_core::call_filter!(FindExp, liquid_core::value!([1, 2]), "n", "n == 3 or n > 1")
+                .unwrap(),
+            liquid_core::value!(2)
+        );
+    }
+
+    #[test]
+    fn unit_sum() {
+        assert_eq!(
+            liquid_core::call_filter!(Sum, liquid_core::value!([1, 2, "3", nil, "a"])).unwrap(),
+            liquid_core::value!(6)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sum, liquid_core::value!([1, 2.5])).unwrap(),
+            liquid_core::value!(3.5)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sum, liquid_core::value!([])).unwrap(),
+            liquid_core::value!(0)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Sum, 4).unwrap(),
+            liquid_core::value!(4)
+        );
+        liquid_core::call_filter!(Sum, liquid_core::value!([i64::MAX, 1])).unwrap_err();
+    }
+
+    #[test]
+    fn unit_sum_property() {
+        let input = liquid_core::value!([
+            { "quantity": 1 },
+            { "quantity": 2 },
+            { "price": 3 },
+            { "quantity": 0.5 },
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Sum, input, "quantity").unwrap(),
+            liquid_core::value!(3.5)
+        );
+    }
+
     #[test]
     #[allow(clippy::float_cmp)] // Need to dig into this
     fn unit_last() {
@@ -741,7 +1393,30 @@ mod tests {
     #[test]
     fn unit_uniq_non_array() {
         let input = 0f64;
-        liquid_core::call_filter!(Uniq, input).unwrap_err();
+        assert_eq!(
+            liquid_core::call_filter!(Uniq, input).unwrap(),
+            liquid_core::value!([0f64])
+        );
+    }
+
+    #[test]
+    fn unit_uniq_property() {
+        let input = liquid_core::value!([
+            { "sku": 1, "size": "s" },
+            { "sku": 2, "size": "s" },
+            { "sku": 1.0, "size": "m" },
+            { "size": "l" },
+            { "size": "xl" },
+        ]);
+        let desired_result = liquid_core::value!([
+            { "sku": 1, "size": "s" },
+            { "sku": 2, "size": "s" },
+            { "size": "l" },
+        ]);
+        assert_eq!(
+            liquid_core::call_filter!(Uniq, input, "sku").unwrap(),
+            desired_result
+        );
     }
 
     #[test]
@@ -1,3 +1,5 @@
+use liquid_core::model::{DateTime, ScalarCow, TimeZone};
+use liquid_core::runtime::ClockRegister;
 use liquid_core::Expression;
 use liquid_core::Runtime;
 use liquid_core::{
@@ -10,6 +12,13 @@ use liquid_core::{Value, ValueView};
 struct DateArgs {
     #[parameter(description = "The format to return the date in.", arg_type = "str")]
     format: Expression,
+
+    #[parameter(
+        description = "The time zone to show the date in, like `Europe/Paris` or `+05:30`.",
+        arg_type = "str",
+        mode = "keyword"
+    )]
+    timezone: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -32,7 +41,27 @@ impl Filter for DateFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let date = input.as_scalar().and_then(|s| s.to_date_time());
+        let date = input.as_scalar().and_then(|s| {
+            // Unlike `DateTime::from_str`, use the render's clock.
+            match s.to_kstr().trim().to_lowercase().as_str() {
+                "now" | "today" => Some(runtime.registers().get_mut::<ClockRegister>().now()),
+                _ => to_date_time(&s),
+            }
+        });
+        let time_zone = match args.timezone {
+            Some(name) => Some(TimeZone::from_name(name.as_str()).ok_or_else(|| {
+                Error::with_msg("Unknown time zone").context("timezone", name.into_owned())
+            })?),
+            None => runtime
+                .registers()
+                .get_mut::<ClockRegister>()
+                .time_zone()
+                .cloned(),
+        };
+        let date = match time_zone {
+            Some(time_zone) => date.map(|date| date.in_time_zone(&time_zone)),
+            None => date,
+        };
         match date {
             Some(date) if !args.format.is_empty() => {
                 let s = date.format(args.format.as_str()).map_err(|_err| {
@@ -46,6 +75,18 @@ impl Filter for DateFilter {
     }
 }
 
+/// Like Ruby Liquid, dates are also accepted as midnight and integers as Unix timestamps.
+fn to_date_time(input: &ScalarCow<'_>) -> Option<DateTime> {
+    input
+        .to_date_time()
+        .or_else(|| {
+            input
+                .to_date()
+                .map(|date| DateTime::default().with_date(date))
+        })
+        .or_else(|| input.to_integer().and_then(DateTime::from_unix_timestamp))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,6 +112,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_date_iso8601() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, "2016-06-13T02:30:00+03:00", "%d %b %Y %H:%M %z")
+                .unwrap(),
+            liquid_core::value!("13 Jun 2016 02:30 +0300")
+        );
+    }
+
+    #[test]
+    fn unit_date_timestamp() {
+        assert_eq!(
+            liquid_core::call_filter!(Date, 1152098955, "%m/%d/%y").unwrap(),
+            liquid_core::value!("07/05/06")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Date, "1152098955", "%m/%d/%y").unwrap(),
+            liquid_core::value!("07/05/06")
+        );
+    }
+
+    #[test]
+    fn unit_date_date() {
+        let date = liquid_core::model::Date::from_ymd(2016, 6, 13);
+        assert_eq!(
+            liquid_core::call_filter!(Date, Value::scalar(date), "%a, %b %e %Y %T").unwrap(),
+            liquid_core::value!("Mon, Jun 13 2016 00:00:00")
+        );
+    }
+
+    #[test]
+    fn unit_date_now() {
+        use liquid_core::runtime::RuntimeBuilder;
+
+        let runtime = RuntimeBuilder::new().build();
+        let now = DateTime::from_str("2001-02-03 04:05:06 +0000").unwrap();
+        runtime.registers().get_mut::<ClockRegister>().set_now(now);
+
+        let filter = DateFilter {
+            args: DateArgs {
+                format: Expression::with_literal("%Y-%m-%d"),
+                timezone: None,
+            },
+        };
+        for input in ["now", "today", " Today "] {
+            assert_eq!(
+                filter.evaluate(&Value::scalar(input), &runtime).unwrap(),
+                liquid_core::value!("2001-02-03")
+            );
+        }
+    }
+
+    #[test]
+    fn unit_date_timezone() {
+        use liquid_core::runtime::RuntimeBuilder;
+
+        let runtime = RuntimeBuilder::new().build();
+        let filter = |timezone: Option<&'static str>| DateFilter {
+            args: DateArgs {
+                format: Expression::with_literal("%Y-%m-%d %H:%M %z"),
+                timezone: timezone.map(Expression::with_literal),
+            },
+        };
+        let input = Value::scalar("2016-06-13 22:30:00 +0000");
+
+        assert_eq!(
+            filter(Some("+05:30")).evaluate(&input, &runtime).unwrap(),
+            liquid_core::value!("2016-06-14 04:00 +0530")
+        );
+        filter(Some("Nowhere/Special"))
+            .evaluate(&input, &runtime)
+            .unwrap_err();
+
+        runtime
+            .registers()
+            .get_mut::<ClockRegister>()
+            .set_time_zone(TimeZone::from_name("-08:00"));
+        assert_eq!(
+            filter(None).evaluate(&input, &runtime).unwrap(),
+            liquid_core::value!("2016-06-13 14:30 -0800")
+        );
+        assert_eq!(
+            filter(Some("UTC")).evaluate(&input, &runtime).unwrap(),
+            liquid_core::value!("2016-06-13 22:30 +0000")
+        );
+    }
+
     #[test]
     fn unit_date_bad_input_type() {
         assert_eq!(
@@ -99,7 +227,7 @@ mod tests {
     fn unit_date_bad_format_type() {
         assert_eq!(
             liquid_core::call_filter!(Date, "13 Jun 2016 02:30:00 +0300", 0f64).unwrap(),
-            liquid_core::value!("0")
+            liquid_core::value!("0.0")
         );
     }
 
@@ -4,24 +4,54 @@ use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
 use liquid_core::{Value, ValueView};
 use regex::Regex;
 
-/// Returns the number of already escaped characters.
+/// Returns the number of already escaped characters, for an entity like `amp;`, `#39;` or
+/// `#x27;` following a `&`.
 fn nr_escaped(text: &str) -> usize {
-    for prefix in &["lt;", "gt;", "#39;", "quot;", "amp;"] {
-        if text.starts_with(prefix) {
-            return prefix.len();
-        }
+    let bytes = text.as_bytes();
+    let name_len = match bytes.first() {
+        Some(b'#') => match bytes.get(1) {
+            Some(b'x') | Some(b'X') => {
+                let digits = count_while(&bytes[2..], |b| b.is_ascii_hexdigit());
+                if digits == 0 {
+                    return 0;
+                }
+                2 + digits
+            }
+            _ => {
+                let digits = count_while(&bytes[1..], |b| b.is_ascii_digit());
+                if digits == 0 {
+                    return 0;
+                }
+                1 + digits
+            }
+        },
+        Some(_) => count_while(bytes, |b| b.is_ascii_alphabetic()),
+        None => 0,
+    };
+    if 0 < name_len && bytes.get(name_len) == Some(&b';') {
+        name_len + 1
+    } else {
+        0
     }
-    0
 }
 
-// The code is adapted from
-// https://github.com/rust-lang/rust/blob/master/src/librustdoc/html/escape.rs
-// Retrieved 2016-11-19.
+fn count_while(bytes: &[u8], pred: impl Fn(u8) -> bool) -> usize {
+    bytes.iter().take_while(|b| pred(**b)).count()
+}
+
 fn escape(input: &dyn ValueView, once_p: bool) -> Result<Value> {
     if input.is_nil() {
         return Ok(Value::Nil);
     }
-    let s = input.to_kstr();
+    Ok(Value::scalar(escape_html(&input.to_kstr(), once_p)))
+}
+
+/// Replaces the characters special to HTML, and XML, with entities, leaving entities that are
+/// already escaped alone when `once_p` is set.
+// The code is adapted from
+// https://github.com/rust-lang/rust/blob/master/src/librustdoc/html/escape.rs
+// Retrieved 2016-11-19.
+pub(crate) fn escape_html(s: &str, once_p: bool) -> String {
     let mut result = String::new();
     let mut last = 0;
     let mut skip = 0;
@@ -59,7 +89,7 @@ fn escape(input: &dyn ValueView, once_p: bool) -> Result<Value> {
     if last < s.len() {
         result.push_str(&s[last..]);
     }
-    Ok(Value::scalar(result))
+    result
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -110,11 +140,11 @@ pub struct StripHtml;
 #[name = "strip_html"]
 struct StripHtmlFilter;
 
-static MATCHERS: once_cell::sync::Lazy<[Regex; 4]> = once_cell::sync::Lazy::new(|| {
+// Blocks are matched in a single pass, so whichever opens first wins, e.g. a `<script>` inside
+// a comment goes with the comment.
+static MATCHERS: once_cell::sync::Lazy<[Regex; 2]> = once_cell::sync::Lazy::new(|| {
     [
-        Regex::new(r"(?is)<script.*?</script>").unwrap(),
-        Regex::new(r"(?is)<style.*?</style>").unwrap(),
-        Regex::new(r"(?is)<!--.*?-->").unwrap(),
+        Regex::new(r"(?is)<script.*?</script>|<!--.*?-->|<style.*?</style>").unwrap(),
         Regex::new(r"(?is)<.*?>").unwrap(),
     ]
 });
@@ -133,7 +163,7 @@ impl Filter for StripHtmlFilter {
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "newline_to_br",
-    description = "Replaces every newline (`\\n`) with an HTML line break (`<br>`).",
+    description = "Replaces every newline (`\\n` or `\\r\\n`) with an HTML line break (`<br />`).",
     parsed(NewlineToBrFilter)
 )]
 pub struct NewlineToBr;
@@ -144,9 +174,10 @@ struct NewlineToBrFilter;
 
 impl Filter for NewlineToBrFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-        // TODO handle windows line endings
         let input = input.to_kstr();
-        Ok(Value::scalar(input.replace('\n', "<br />\n")))
+        Ok(Value::scalar(
+            input.replace("\r\n", "\n").replace('\n', "<br />\n"),
+        ))
     }
 }
 
@@ -186,7 +217,23 @@ mod tests {
         );
         assert_eq!(
             liquid_core::call_filter!(EscapeOnce, "&lt;&gt;&amp;&#39;&quot;&xyz;").unwrap(),
-            liquid_core::value!("&lt;&gt;&amp;&#39;&quot;&amp;xyz;")
+            liquid_core::value!("&lt;&gt;&amp;&#39;&quot;&xyz;")
+        );
+    }
+
+    #[test]
+    fn unit_escape_once_entities() {
+        assert_eq!(
+            liquid_core::call_filter!(EscapeOnce, "&copy; &#169; &#xA9; &#Xa9; &nbsp;").unwrap(),
+            liquid_core::value!("&copy; &#169; &#xA9; &#Xa9; &nbsp;")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(EscapeOnce, "& &amp &#; &#x; &#12a; &a b; &;").unwrap(),
+            liquid_core::value!("&amp; &amp;amp &amp;#; &amp;#x; &amp;#12a; &amp;a b; &amp;;")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(EscapeOnce, "Tom & Jerry &amp;").unwrap(),
+            liquid_core::value!("Tom &amp; Jerry &amp;")
         );
     }
 
@@ -235,6 +282,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_strip_html_overlapping_blocks() {
+        assert_eq!(
+            liquid_core::call_filter!(StripHtml, "<!-- <script> -->text</script>").unwrap(),
+            liquid_core::value!("text")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(StripHtml, "<script>a = '<!--'</script>text-->").unwrap(),
+            liquid_core::value!("text-->")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(
+                StripHtml,
+                "<p>Hi</p>\n<style>\np { color: red; }\n</style>\n<script>\nalert(1);\n</script>there"
+            )
+            .unwrap(),
+            liquid_core::value!("Hi\n\nthere")
+        );
+    }
+
     #[test]
     fn unit_newline_to_br() {
         assert_eq!(
@@ -252,6 +319,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_newline_to_br_windows_line_endings() {
+        assert_eq!(
+            liquid_core::call_filter!(NewlineToBr, "a\r\nb\nc\rd").unwrap(),
+            liquid_core::value!("a<br />\nb<br />\nc\rd")
+        );
+    }
+
     #[test]
     fn unit_newline_to_br_one_argument() {
         liquid_core::call_filter!(NewlineToBr, "a\nb", 0f64).unwrap_err();
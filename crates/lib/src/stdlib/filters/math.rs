@@ -1,5 +1,9 @@
+use std::cmp;
 use std::convert::TryInto;
 
+#[cfg(feature = "decimal")]
+use liquid_core::model::Decimal;
+use liquid_core::parser::Operator;
 use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
@@ -24,14 +28,15 @@ struct AbsFilter;
 
 impl Filter for AbsFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-        let input = input
-            .as_scalar()
-            .ok_or_else(|| invalid_input("Number expected"))?;
-        input
-            .to_integer()
-            .map(|i| Value::scalar(i.abs()))
-            .or_else(|| input.to_float().map(|i| Value::scalar(i.abs())))
-            .ok_or_else(|| invalid_input("Number expected"))
+        match expect_input(input)? {
+            Number::Integer(i) => i
+                .checked_abs()
+                .map(Value::scalar)
+                .ok_or_else(|| invalid_input("Integer overflow")),
+            Number::Float(f) => Ok(Value::scalar(f.abs())),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => Ok(Value::scalar(d.abs())),
+        }
     }
 }
 
@@ -61,26 +66,7 @@ impl Filter for AtLeastFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let input = input
-            .as_scalar()
-            .ok_or_else(|| invalid_input("Number expected"))?;
-
-        let min = args
-            .min
-            .as_scalar()
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
-
-        let result = input
-            .to_integer()
-            .and_then(|i| min.to_integer().map(|min| Value::scalar(i.max(min))))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| min.to_float().map(|min| Value::scalar(i.max(min))))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
-
-        Ok(result)
+        clamp(input, &args.min, cmp::Ordering::Greater)
     }
 }
 
@@ -110,26 +96,138 @@ impl Filter for AtMostFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let input = input
-            .as_scalar()
-            .ok_or_else(|| invalid_input("Number expected"))?;
+        clamp(input, &args.max, cmp::Ordering::Less)
+    }
+}
+
+/// Whichever of `input` and `limit` is on the `keep` side of the other, as it is, integer or
+/// float, like Ruby.
+fn clamp(input: &dyn ValueView, limit: &dyn ValueView, keep: cmp::Ordering) -> Result<Value> {
+    let input = expect_input(input)?;
+    let limit = expect_operand(limit)?;
+
+    let ordering = match (input, limit) {
+        (Number::Integer(i), Number::Integer(l)) => i.cmp(&l),
+        #[cfg(feature = "decimal")]
+        (i, l) if i.is_decimal() || l.is_decimal() => i.to_decimal().cmp(&l.to_decimal()),
+        (i, l) => i
+            .to_float()
+            .partial_cmp(&l.to_float())
+            .unwrap_or(cmp::Ordering::Equal),
+    };
+    if ordering == keep || ordering == cmp::Ordering::Equal {
+        Ok(input.into())
+    } else {
+        Ok(limit.into())
+    }
+}
+
+/// A number, keeping integers apart from floats, like Ruby.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Number {
+    Integer(i64),
+    Float(f64),
+    #[cfg(feature = "decimal")]
+    Decimal(Decimal),
+}
+
+impl Number {
+    fn to_float(self) -> f64 {
+        match self {
+            Self::Integer(i) => i as f64,
+            Self::Float(f) => f,
+            #[cfg(feature = "decimal")]
+            Self::Decimal(d) => d.to_f64(),
+        }
+    }
 
-        let max = args
-            .max
-            .as_scalar()
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+    #[cfg(feature = "decimal")]
+    fn is_decimal(self) -> bool {
+        matches!(self, Self::Decimal(_))
+    }
 
-        let result = input
-            .to_integer()
-            .and_then(|i| max.to_integer().map(|max| Value::scalar(i.min(max))))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| max.to_float().map(|max| Value::scalar(i.min(max))))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+    #[cfg(feature = "decimal")]
+    fn to_decimal(self) -> Option<Decimal> {
+        match self {
+            Self::Integer(i) => Some(Decimal::from(i)),
+            Self::Float(f) => Decimal::from_f64(f),
+            Self::Decimal(d) => Some(d),
+        }
+    }
+}
 
-        Ok(result)
+impl From<Number> for Value {
+    fn from(number: Number) -> Self {
+        match number {
+            Number::Integer(i) => Value::scalar(i),
+            Number::Float(f) => Value::scalar(f),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => Value::scalar(d),
+        }
+    }
+}
+
+/// Interpret a value as a number, including strings like `"3.5"`.
+///
+/// Like Ruby, nil, e.g. a missing quantity, counts as `0`.
+fn to_number(value: &dyn ValueView) -> Option<Number> {
+    if value.is_nil() {
+        return Some(Number::Integer(0));
+    }
+    let value = value.as_scalar()?;
+    if let Some(i) = value.as_integer() {
+        return Some(Number::Integer(i));
+    }
+    if let Some(f) = value.as_float() {
+        return Some(f).filter(|f| f.is_finite()).map(Number::Float);
+    }
+    #[cfg(feature = "decimal")]
+    if let Some(d) = value.as_decimal() {
+        return Some(Number::Decimal(d));
+    }
+
+    let value = value.as_str()?.trim();
+    if let Ok(i) = value.parse::<i64>() {
+        return Some(Number::Integer(i));
+    }
+    // Only plain decimals, not `inf` or `1e5`.
+    let is_decimal = value
+        .bytes()
+        .all(|b| b.is_ascii_digit() || b == b'-' || b == b'+' || b == b'.');
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|f| is_decimal && f.is_finite())
+        .map(Number::Float)
+}
+
+fn expect_input(input: &dyn ValueView) -> Result<Number> {
+    to_number(input).ok_or_else(|| invalid_input("Number expected"))
+}
+
+fn expect_operand(operand: &dyn ValueView) -> Result<Number> {
+    to_number(operand).ok_or_else(|| invalid_argument("operand", "Number expected"))
+}
+
+fn expect_nonzero_operand(operand: &dyn ValueView) -> Result<Number> {
+    let operand = expect_operand(operand)?;
+    if operand.to_float() == 0.0 {
+        return Err(invalid_argument("operand", "Can't divide by zero"));
+    }
+    Ok(operand)
+}
+
+/// Apply an arithmetic operator, so the filters match expressions like `{{ a / b }}`.
+fn arithmetic(operator: Operator, input: Number, operand: Number) -> Result<Value> {
+    operator.apply(&Value::from(input), &Value::from(operand))
+}
+
+/// Convert a whole float to an integer, like the result of `floor`.
+fn float_to_integer(f: f64) -> Result<Value> {
+    if (i64::MIN as f64) <= f && f < (i64::MAX as f64) {
+        Ok(Value::scalar(f as i64))
+    } else {
+        Err(invalid_input("Integer overflow"))
     }
 }
 
@@ -159,26 +257,10 @@ impl Filter for PlusFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let input = input
-            .as_scalar()
-            .ok_or_else(|| invalid_input("Number expected"))?;
-
-        let operand = args
-            .operand
-            .as_scalar()
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
-
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i + o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i + o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let input = expect_input(input)?;
+        let operand = expect_operand(&args.operand)?;
 
-        Ok(result)
+        arithmetic(Operator::Add, input, operand)
     }
 }
 
@@ -208,26 +290,10 @@ impl Filter for MinusFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let input = input
-            .as_scalar()
-            .ok_or_else(|| invalid_input("Number expected"))?;
-
-        let operand = args
-            .operand
-            .as_scalar()
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
-
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i - o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i - o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let input = expect_input(input)?;
+        let operand = expect_operand(&args.operand)?;
 
-        Ok(result)
+        arithmetic(Operator::Subtract, input, operand)
     }
 }
 
@@ -257,26 +323,10 @@ impl Filter for TimesFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let input = input
-            .as_scalar()
-            .ok_or_else(|| invalid_input("Number expected"))?;
+        let input = expect_input(input)?;
+        let operand = expect_operand(&args.operand)?;
 
-        let operand = args
-            .operand
-            .as_scalar()
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
-
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i * o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i * o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
-
-        Ok(result)
+        arithmetic(Operator::Multiply, input, operand)
     }
 }
 
@@ -306,36 +356,10 @@ impl Filter for DividedByFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let input = input
-            .as_scalar()
-            .ok_or_else(|| invalid_input("Number expected"))?;
-
-        let operand = args
-            .operand
-            .as_scalar()
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
-
-        if let Some(o) = operand.to_integer() {
-            if o == 0 {
-                return Err(invalid_argument("operand", "Can't divide by zero"));
-            }
-        } else if let Some(o) = operand.to_float() {
-            if o == 0.0 {
-                return Err(invalid_argument("operand", "Can't divide by zero"));
-            }
-        }
-
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i / o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i / o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let input = expect_input(input)?;
+        let operand = expect_nonzero_operand(&args.operand)?;
 
-        Ok(result)
+        arithmetic(Operator::Divide, input, operand)
     }
 }
 
@@ -365,36 +389,10 @@ impl Filter for ModuloFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let input = input
-            .as_scalar()
-            .ok_or_else(|| invalid_input("Number expected"))?;
-
-        let operand = args
-            .operand
-            .as_scalar()
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
-
-        if let Some(o) = operand.to_integer() {
-            if o == 0 {
-                return Err(invalid_argument("operand", "Can't divide by zero"));
-            }
-        } else if let Some(o) = operand.to_float() {
-            if o == 0.0 {
-                return Err(invalid_argument("operand", "Can't divide by zero"));
-            }
-        }
-
-        let result = input
-            .to_integer()
-            .and_then(|i| operand.to_integer().map(|o| Value::scalar(i % o)))
-            .or_else(|| {
-                input
-                    .to_float()
-                    .and_then(|i| operand.to_float().map(|o| Value::scalar(i % o)))
-            })
-            .ok_or_else(|| invalid_argument("operand", "Number expected"))?;
+        let input = expect_input(input)?;
+        let operand = expect_nonzero_operand(&args.operand)?;
 
-        Ok(result)
+        arithmetic(Operator::Modulo, input, operand)
     }
 }
 
@@ -429,21 +427,78 @@ impl Filter for RoundFilter {
 
         let n = args.decimal_places.unwrap_or(0);
 
-        let input = input
-            .as_scalar()
-            .and_then(|s| s.to_float())
-            .ok_or_else(|| invalid_input("Number expected"))?;
-
-        match n.cmp(&0) {
-            std::cmp::Ordering::Equal => Ok(Value::scalar(input.round() as i64)),
-            std::cmp::Ordering::Less => Ok(Value::scalar(input.round() as i64)),
-            _ => {
-                let multiplier = 10.0_f64.powi(
-                    n.try_into()
-                        .map_err(|_| invalid_input("decimal-places was too large"))?,
-                );
-                Ok(Value::scalar((input * multiplier).round() / multiplier))
-            }
+        match expect_input(input)? {
+            Number::Integer(i) => round_integer(i, n),
+            Number::Float(f) => round_float(f, n),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => round_decimal(d, n),
+        }
+    }
+}
+
+/// Like Ruby, integers stay integers, only rounding to tens, hundreds, etc.
+fn round_integer(i: i64, n: i64) -> Result<Value> {
+    if 0 <= n {
+        return Ok(Value::scalar(i));
+    }
+    let multiplier = u32::try_from(-n)
+        .ok()
+        .and_then(|n| 10_u64.checked_pow(n))
+        .ok_or_else(|| invalid_input("decimal-places was too large"))?;
+    // Halves round away from zero
+    let remainder = i.unsigned_abs() % multiplier;
+    let down = i.unsigned_abs() - remainder;
+    let rounded = if multiplier <= remainder * 2 {
+        down.checked_add(multiplier)
+    } else {
+        Some(down)
+    };
+    let rounded = rounded
+        .and_then(|r| i64::try_from(r).ok())
+        .map(|r| if i < 0 { -r } else { r })
+        .ok_or_else(|| invalid_input("Integer overflow"))?;
+    Ok(Value::scalar(rounded))
+}
+
+/// Decimals round to decimals, or to integers like floats do when there are no decimal places.
+#[cfg(feature = "decimal")]
+fn round_decimal(d: Decimal, n: i64) -> Result<Value> {
+    let too_large = || invalid_input("decimal-places was too large");
+    if 0 < n {
+        let places = u32::try_from(n).map_err(|_| too_large())?;
+        return Ok(Value::scalar(d.round(places)));
+    }
+    // Shift the decimal point left to round to tens, hundreds, etc.
+    let shift = u32::try_from(-n).map_err(|_| too_large())?;
+    let shifted = d
+        .scale()
+        .checked_add(shift)
+        .and_then(|scale| Decimal::new(d.mantissa(), scale))
+        .ok_or_else(too_large)?;
+    shifted
+        .round(0)
+        .to_i64()
+        .and_then(|r| r.checked_mul(10_i64.checked_pow(shift)?))
+        .map(Value::scalar)
+        .ok_or_else(|| invalid_input("Integer overflow"))
+}
+
+fn round_float(input: f64, n: i64) -> Result<Value> {
+    match n.cmp(&0) {
+        cmp::Ordering::Equal => float_to_integer(input.round()),
+        cmp::Ordering::Less => {
+            let multiplier = 10.0_f64.powi(
+                (-n).try_into()
+                    .map_err(|_| invalid_input("decimal-places was too large"))?,
+            );
+            float_to_integer((input / multiplier).round() * multiplier)
+        }
+        cmp::Ordering::Greater => {
+            let multiplier = 10.0_f64.powi(
+                n.try_into()
+                    .map_err(|_| invalid_input("decimal-places was too large"))?,
+            );
+            Ok(Value::scalar((input * multiplier).round() / multiplier))
         }
     }
 }
@@ -462,11 +517,16 @@ struct CeilFilter;
 
 impl Filter for CeilFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-        let n = input
-            .as_scalar()
-            .and_then(|s| s.to_float())
-            .ok_or_else(|| invalid_input("Number expected"))?;
-        Ok(Value::scalar(n.ceil() as i64))
+        match expect_input(input)? {
+            Number::Integer(i) => Ok(Value::scalar(i)),
+            Number::Float(f) => float_to_integer(f.ceil()),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d
+                .ceil()
+                .to_i64()
+                .map(Value::scalar)
+                .ok_or_else(|| invalid_input("Integer overflow")),
+        }
     }
 }
 
@@ -484,11 +544,16 @@ struct FloorFilter;
 
 impl Filter for FloorFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-        let n = input
-            .as_scalar()
-            .and_then(|s| s.to_float())
-            .ok_or_else(|| invalid_input("Number expected"))?;
-        Ok(Value::scalar(n.floor() as i64))
+        match expect_input(input)? {
+            Number::Integer(i) => Ok(Value::scalar(i)),
+            Number::Float(f) => float_to_integer(f.floor()),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d
+                .floor()
+                .to_i64()
+                .map(Value::scalar)
+                .ok_or_else(|| invalid_input("Integer overflow")),
+        }
     }
 }
 
@@ -576,6 +641,29 @@ mod tests {
             Value::scalar(21.5)
         );
     }
+    #[test]
+    fn unit_at_least_at_most_mixed() {
+        // Whichever wins is kept as it is
+        assert_eq!(
+            liquid_core::call_filter!(AtLeast, 5, 4.5).unwrap(),
+            Value::scalar(5)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(AtMost, 4.5, 5).unwrap(),
+            Value::scalar(4.5)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(AtMost, "7", 5).unwrap(),
+            Value::scalar(5)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(AtLeast, Value::Nil, 1).unwrap(),
+            Value::scalar(1)
+        );
+        liquid_core::call_filter!(AtLeast, "a", 1).unwrap_err();
+        liquid_core::call_filter!(AtMost, 1, "a").unwrap_err();
+    }
+
     #[test]
     fn unit_plus() {
         assert_eq!(
@@ -645,6 +733,33 @@ mod tests {
             liquid_core::call_filter!(DividedBy, 5f64, 2f64).unwrap(),
             Value::scalar(2.5f64)
         );
+        assert_eq!(
+            liquid_core::call_filter!(DividedBy, 5i64, 2i64).unwrap(),
+            Value::scalar(2i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DividedBy, -7i64, 2i64).unwrap(),
+            Value::scalar(-4i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DividedBy, 7i64, -2i64).unwrap(),
+            Value::scalar(-4i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DividedBy, -6i64, 2i64).unwrap(),
+            Value::scalar(-3i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DividedBy, 20i64, 7.0f64).unwrap(),
+            Value::scalar(20.0 / 7.0)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DividedBy, "20", "8").unwrap(),
+            Value::scalar(2i64)
+        );
+        liquid_core::call_filter!(DividedBy, 1i64, 0i64).unwrap_err();
+        liquid_core::call_filter!(DividedBy, 1.0f64, 0.0f64).unwrap_err();
+        liquid_core::call_filter!(DividedBy, i64::MIN, -1i64).unwrap_err();
         liquid_core::call_filter!(DividedBy, true, 8.5).unwrap_err();
         liquid_core::call_filter!(DividedBy, 2.5, true).unwrap_err();
         liquid_core::call_filter!(DividedBy, 2.5).unwrap_err();
@@ -707,5 +822,189 @@ mod tests {
             liquid_core::call_filter!(Round, 1.23456f64, 3i64).unwrap(),
             Value::scalar(1.235f64)
         );
+        assert_eq!(
+            liquid_core::call_filter!(Round, 4.5612f64, 2i64).unwrap(),
+            Value::scalar(4.56f64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Round, 1250.5f64, -2i64).unwrap(),
+            Value::scalar(1300i64)
+        );
+    }
+
+    fn to_integer(value: Value) -> Option<i64> {
+        value.as_scalar().and_then(|s| s.to_integer())
+    }
+
+    #[test]
+    fn unit_rounding_keeps_integers() {
+        let big = 9_007_199_254_740_993i64;
+        assert_eq!(
+            to_integer(liquid_core::call_filter!(Round, 5i64, 2i64).unwrap()),
+            Some(5)
+        );
+        assert_eq!(
+            to_integer(liquid_core::call_filter!(Round, 1249i64, -2i64).unwrap()),
+            Some(1200)
+        );
+        assert_eq!(
+            to_integer(liquid_core::call_filter!(Round, -1250i64, -2i64).unwrap()),
+            Some(-1300)
+        );
+        assert_eq!(
+            to_integer(liquid_core::call_filter!(Round, big).unwrap()),
+            Some(big)
+        );
+        assert_eq!(
+            to_integer(liquid_core::call_filter!(Ceil, big).unwrap()),
+            Some(big)
+        );
+        assert_eq!(
+            to_integer(liquid_core::call_filter!(Floor, big).unwrap()),
+            Some(big)
+        );
+        liquid_core::call_filter!(Round, 5i64, -20i64).unwrap_err();
+    }
+
+    #[test]
+    fn unit_coercion() {
+        assert_eq!(
+            liquid_core::call_filter!(Plus, Value::Nil, 1i64).unwrap(),
+            Value::scalar(1i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Abs, Value::Nil).unwrap(),
+            Value::scalar(0i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Floor, " 3.5 ").unwrap(),
+            Value::scalar(3i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Times, "3", 4i64).unwrap(),
+            Value::scalar(12i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Ceil, "-1.5").unwrap(),
+            Value::scalar(-1i64)
+        );
+        for input in ["abc", "inf", "NaN", "1e5", ""] {
+            liquid_core::call_filter!(Floor, input).unwrap_err();
+            liquid_core::call_filter!(Plus, 1i64, input).unwrap_err();
+        }
+        liquid_core::call_filter!(Modulo, 7i64, Value::Nil).unwrap_err();
+    }
+
+    #[test]
+    fn unit_modulo_sign() {
+        assert_eq!(
+            liquid_core::call_filter!(Modulo, -7i64, 3i64).unwrap(),
+            Value::scalar(2i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Modulo, 7i64, -3i64).unwrap(),
+            Value::scalar(-2i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Modulo, -7.5, 2i64).unwrap(),
+            Value::scalar(0.5)
+        );
+        liquid_core::call_filter!(Modulo, 7i64, 0i64).unwrap_err();
+    }
+
+    #[test]
+    fn unit_integer_overflow() {
+        liquid_core::call_filter!(Plus, i64::MAX, 1i64).unwrap_err();
+        liquid_core::call_filter!(Minus, i64::MIN, 1i64).unwrap_err();
+        liquid_core::call_filter!(Times, i64::MAX, 2i64).unwrap_err();
+        liquid_core::call_filter!(Abs, i64::MIN).unwrap_err();
+        assert_eq!(
+            liquid_core::call_filter!(Modulo, i64::MIN, -1i64).unwrap(),
+            Value::scalar(0i64)
+        );
+        liquid_core::call_filter!(Floor, 1e300).unwrap_err();
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn unit_decimal() {
+        let d = |s: &str| Value::scalar(s.parse::<Decimal>().unwrap());
+        assert_eq!(
+            liquid_core::call_filter!(Plus, d("0.1"), d("0.2")).unwrap(),
+            d("0.3")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Plus, d("0.1"), d("0.2"))
+                .unwrap()
+                .to_kstr(),
+            "0.3"
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Minus, d("10.00"), 0.01f64)
+                .unwrap()
+                .to_kstr(),
+            "9.99"
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Times, d("19.99"), 3i64)
+                .unwrap()
+                .to_kstr(),
+            "59.97"
+        );
+        assert_eq!(
+            liquid_core::call_filter!(DividedBy, d("10.00"), 4i64)
+                .unwrap()
+                .to_kstr(),
+            "2.50"
+        );
+        liquid_core::call_filter!(DividedBy, d("10.00"), d("0.0")).unwrap_err();
+        assert_eq!(
+            liquid_core::call_filter!(Modulo, d("-7.5"), 2i64)
+                .unwrap()
+                .to_kstr(),
+            "0.5"
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Abs, d("-1.50"))
+                .unwrap()
+                .to_kstr(),
+            "1.50"
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Round, d("2.345"), 2i64)
+                .unwrap()
+                .to_kstr(),
+            "2.35"
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Round, d("1249.5"), -2i64).unwrap(),
+            Value::scalar(1200i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Round, d("2.5")).unwrap(),
+            Value::scalar(3i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Ceil, d("2.1")).unwrap(),
+            Value::scalar(3i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Floor, d("-2.1")).unwrap(),
+            Value::scalar(-3i64)
+        );
+        assert_eq!(
+            liquid_core::call_filter!(AtLeast, d("1.10"), d("1.1")).unwrap(),
+            d("1.10")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(AtMost, d("1.10"), 1i64).unwrap(),
+            Value::scalar(1i64)
+        );
+
+        // Without decimals, floats pick up rounding errors.
+        assert_ne!(
+            liquid_core::call_filter!(Plus, 0.1f64, 0.2f64).unwrap(),
+            Value::scalar(0.3f64)
+        );
     }
 }
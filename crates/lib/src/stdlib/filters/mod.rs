@@ -14,17 +14,26 @@ mod slice;
 mod string;
 mod url;
 
+#[cfg(feature = "jekyll")]
+pub(crate) use self::array::{
+    element_scope, elements, expect_variable_name, parse_exp_condition, satisfies,
+};
 pub use self::array::{
-    Compact, Concat, First, Join, Last, Map, Reverse, Sort, SortNatural, Uniq, Where,
+    Compact, Concat, Find, FindExp, First, Join, Last, Map, Reverse, Sort, SortNatural, Sum, Uniq,
+    Where,
 };
 pub use self::date::Date;
+#[cfg(feature = "jekyll")]
+pub(crate) use self::html::escape_html;
 pub use self::html::{Escape, EscapeOnce, NewlineToBr, StripHtml};
 pub use self::math::{
     Abs, AtLeast, AtMost, Ceil, DividedBy, Floor, Minus, Modulo, Plus, Round, Times,
 };
 pub use self::slice::Slice;
 pub use self::string::case::{Capitalize, Downcase, Upcase};
-pub use self::string::operate::{Append, Prepend, Remove, RemoveFirst, Replace, ReplaceFirst};
+pub use self::string::operate::{
+    Append, Prepend, Remove, RemoveFirst, RemoveLast, Replace, ReplaceFirst, ReplaceLast,
+};
 pub use self::string::strip::{Lstrip, Rstrip, Strip, StripNewlines};
 pub use self::string::truncate::{Truncate, TruncateWords};
 pub use self::string::Split;
@@ -45,7 +54,11 @@ struct SizeFilter;
 impl Filter for SizeFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
         if let Some(x) = input.as_scalar() {
-            Ok(Value::scalar(x.to_kstr().len() as i64))
+            let size = match x.as_bytes() {
+                Some(bytes) => bytes.len(),
+                None => x.to_kstr().len(),
+            };
+            Ok(Value::scalar(size as i64))
         } else if let Some(x) = input.as_array() {
             Ok(Value::scalar(x.size()))
         } else if let Some(x) = input.as_object() {
@@ -60,6 +73,13 @@ impl Filter for SizeFilter {
 struct DefaultArgs {
     #[parameter(description = "The default value.")]
     default: Expression,
+
+    #[parameter(
+        description = "Whether to keep an input of `false`, rather than replacing it. Defaults to false.",
+        arg_type = "bool",
+        mode = "keyword"
+    )]
+    allow_false: Option<Expression>,
 }
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -82,7 +102,11 @@ impl Filter for DefaultFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        if input.query_state(liquid_core::model::State::DefaultValue) {
+        let allow_false = args.allow_false.unwrap_or(false);
+        let is_false = input.as_scalar().and_then(|s| s.to_bool()) == Some(false);
+        if allow_false && is_false {
+            Ok(input.to_value())
+        } else if input.query_state(liquid_core::model::State::DefaultValue) {
             Ok(args.default.to_value())
         } else {
             Ok(input.to_value())
@@ -119,6 +143,14 @@ mod tests {
             .unwrap(),
             Value::scalar(5f64)
         );
+        assert_eq!(
+            liquid_core::call_filter!(
+                Size,
+                Value::scalar(liquid_core::model::Bytes::from(vec![0xff, 0x00, 0x10]))
+            )
+            .unwrap(),
+            Value::scalar(3f64)
+        );
     }
 
     #[test]
@@ -156,4 +188,31 @@ mod tests {
             Value::scalar(true)
         );
     }
+
+    #[test]
+    fn unit_default_allow_false() {
+        let filter = DefaultFilter {
+            args: DefaultArgs {
+                default: liquid_core::Expression::with_literal("bar"),
+                allow_false: Some(liquid_core::Expression::with_literal(true)),
+            },
+        };
+        let runtime = liquid_core::runtime::RuntimeBuilder::new().build();
+        assert_eq!(
+            filter.evaluate(&false, &runtime).unwrap(),
+            Value::scalar(false)
+        );
+        assert_eq!(
+            filter.evaluate(&Value::Nil, &runtime).unwrap(),
+            Value::scalar("bar")
+        );
+        assert_eq!(
+            filter.evaluate(&"", &runtime).unwrap(),
+            Value::scalar("bar")
+        );
+        assert_eq!(
+            filter.evaluate(&true, &runtime).unwrap(),
+            Value::scalar(true)
+        );
+    }
 }
@@ -83,7 +83,7 @@ impl Filter for SliceFilter {
             ))
         } else {
             let input = input.to_kstr();
-            let (offset, length) = canonicalize_slice(offset, length, input.len());
+            let (offset, length) = canonicalize_slice(offset, length, input.chars().count());
             Ok(Value::scalar(
                 input.chars().skip(offset).take(length).collect::<String>(),
             ))
@@ -136,6 +136,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_slice_multibyte() {
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "日本語のテキスト", -4, 2).unwrap(),
+            liquid_core::value!("テキ")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "héllo", 1, 10).unwrap(),
+            liquid_core::value!("éllo")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "héllo", -1).unwrap(),
+            liquid_core::value!("o")
+        );
+    }
+
+    #[test]
+    fn unit_slice_out_of_range() {
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "Liquid", 10, 2).unwrap(),
+            liquid_core::value!("")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, "Liquid", -10, 2).unwrap(),
+            liquid_core::value!("")
+        );
+    }
+
+    #[test]
+    fn unit_slice_array() {
+        let input = liquid_core::value!(["a", "b", "c", "d"]);
+        assert_eq!(
+            liquid_core::call_filter!(Slice, input.clone(), 1, 2).unwrap(),
+            liquid_core::value!(["b", "c"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, input.clone(), -2, 2).unwrap(),
+            liquid_core::value!(["c", "d"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, input.clone(), -1, 5).unwrap(),
+            liquid_core::value!(["d"])
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Slice, input, 4).unwrap(),
+            liquid_core::value!([])
+        );
+    }
+
     #[test]
     fn unit_slice_non_positive_length() {
         liquid_core::call_filter!(
@@ -52,7 +52,7 @@ struct ReplaceFirstArgs {
     #[parameter(description = "The text to search.", arg_type = "str")]
     search: Expression,
     #[parameter(
-        description = "The text to replace search result with. If not given, the filter will just delete search results«.",
+        description = "The text to replace search result with. If not given, the filter will just delete search results.",
         arg_type = "str"
     )]
     replace: Option<Expression>,
@@ -94,6 +94,50 @@ impl Filter for ReplaceFirstFilter {
     }
 }
 
+#[derive(Debug, FilterParameters)]
+struct ReplaceLastArgs {
+    #[parameter(description = "The text to search.", arg_type = "str")]
+    search: Expression,
+    #[parameter(
+        description = "The text to replace search result with. If not given, the filter will just delete search results.",
+        arg_type = "str"
+    )]
+    replace: Option<Expression>,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "replace_last",
+    description = "Replaces the last occurrence of the `search` with `replace`. If `replace` is not given, just deletes the occurrence.",
+    parameters(ReplaceLastArgs),
+    parsed(ReplaceLastFilter)
+)]
+pub struct ReplaceLast;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "replace_last"]
+struct ReplaceLastFilter {
+    #[parameters]
+    args: ReplaceLastArgs,
+}
+
+impl Filter for ReplaceLastFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input = input.to_kstr();
+
+        let search = args.search;
+        let replace = args.replace.unwrap_or_else(|| "".into());
+
+        Ok(Value::scalar(replace_last(
+            input.as_str(),
+            search.as_str(),
+            replace.as_str(),
+        )))
+    }
+}
+
 #[derive(Debug, FilterParameters)]
 struct RemoveArgs {
     #[parameter(description = "The text to remove.", arg_type = "str")]
@@ -160,6 +204,49 @@ impl Filter for RemoveFirstFilter {
     }
 }
 
+#[derive(Debug, FilterParameters)]
+struct RemoveLastArgs {
+    #[parameter(description = "The text to remove.", arg_type = "str")]
+    search: Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "remove_last",
+    description = "Removes the last occurrence of the given string.",
+    parameters(RemoveLastArgs),
+    parsed(RemoveLastFilter)
+)]
+pub struct RemoveLast;
+
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "remove_last"]
+struct RemoveLastFilter {
+    #[parameters]
+    args: RemoveLastArgs,
+}
+
+impl Filter for RemoveLastFilter {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input = input.to_kstr();
+
+        Ok(Value::scalar(replace_last(
+            input.as_str(),
+            args.search.as_str(),
+            "",
+        )))
+    }
+}
+
+fn replace_last(input: &str, search: &str, replace: &str) -> String {
+    match input.rfind(search) {
+        Some(index) => [&input[..index], replace, &input[index + search.len()..]].join(""),
+        None => input.to_owned(),
+    }
+}
+
 #[derive(Debug, FilterParameters)]
 struct AppendArgs {
     #[parameter(description = "The string to append to the input.", arg_type = "str")]
@@ -287,6 +374,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_remove_last() {
+        assert_eq!(
+            liquid_core::call_filter!(RemoveLast, "barbar", "bar").unwrap(),
+            liquid_core::value!("bar")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(RemoveLast, "barbar", "").unwrap(),
+            liquid_core::value!("barbar")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(RemoveLast, "barbar", "barbar").unwrap(),
+            liquid_core::value!("")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(RemoveLast, "barbar", "a").unwrap(),
+            liquid_core::value!("barbr")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(RemoveLast, "barbar", "x").unwrap(),
+            liquid_core::value!("barbar")
+        );
+    }
+
     #[test]
     fn unit_replace() {
         assert_eq!(
@@ -310,4 +421,28 @@ mod tests {
             liquid_core::value!("")
         );
     }
+
+    #[test]
+    fn unit_replace_last() {
+        assert_eq!(
+            liquid_core::call_filter!(ReplaceLast, "barbar", "bar", "foo").unwrap(),
+            liquid_core::value!("barfoo")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(ReplaceLast, "xoxobar", "xo", "foo").unwrap(),
+            liquid_core::value!("xofoobar")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(ReplaceLast, "", "bar", "foo").unwrap(),
+            liquid_core::value!("")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(ReplaceLast, "héllo héllo", "é", "e").unwrap(),
+            liquid_core::value!("héllo hello")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(ReplaceLast, "barbar", "bar").unwrap(),
+            liquid_core::value!("bar")
+        );
+    }
 }
@@ -3,13 +3,17 @@ use liquid_core::Runtime;
 use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
 use liquid_core::{Value, ValueView};
 
+/// Ruby's definition of whitespace, for `String#strip`.
+fn is_whitespace(c: char) -> bool {
+    matches!(c, '\0' | '\t' | '\n' | '\x0B' | '\x0C' | '\r' | ' ')
+}
+
 /// Removes all whitespace (tabs, spaces, and newlines) from both the left and right side of a
 /// string.
 ///
-/// It does not affect spaces between words.  Note that while this works for the case of tabs,
-/// spaces, and newlines, it also removes any other codepoints defined by the Unicode Derived Core
-/// Property `White_Space` (per [rust
-/// documentation](https://doc.rust-lang.org/std/primitive.str.html#method.trim_start).
+/// It does not affect spaces between words.  Like Ruby, whitespace is only null, horizontal tab,
+/// line feed, vertical tab, form feed, carriage return and space; other Unicode spaces, like a
+/// non-breaking space, are kept.
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "strip",
@@ -25,16 +29,13 @@ struct StripFilter;
 impl Filter for StripFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
         let input = input.to_kstr();
-        Ok(Value::scalar(input.trim().to_owned()))
+        Ok(Value::scalar(input.trim_matches(is_whitespace).to_owned()))
     }
 }
 
 /// Removes all whitespaces (tabs, spaces, and newlines) from the beginning of a string.
 ///
-/// The filter does not affect spaces between words.  Note that while this works for the case of
-/// tabs, spaces, and newlines, it also removes any other codepoints defined by the Unicode Derived
-/// Core Property `White_Space` (per [rust
-/// documentation](https://doc.rust-lang.org/std/primitive.str.html#method.trim_start).
+/// The filter does not affect spaces between words.  Whitespace is the same as for [`Strip`].
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "lstrip",
@@ -50,16 +51,15 @@ struct LstripFilter;
 impl Filter for LstripFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
         let input = input.to_kstr();
-        Ok(Value::scalar(input.trim_start().to_owned()))
+        Ok(Value::scalar(
+            input.trim_start_matches(is_whitespace).to_owned(),
+        ))
     }
 }
 
 /// Removes all whitespace (tabs, spaces, and newlines) from the right side of a string.
 ///
-/// The filter does not affect spaces between words.  Note that while this works for the case of
-/// tabs, spaces, and newlines, it also removes any other codepoints defined by the Unicode Derived
-/// Core Property `White_Space` (per [rust
-/// documentation](https://doc.rust-lang.org/std/primitive.str.html#method.trim_start).
+/// The filter does not affect spaces between words.  Whitespace is the same as for [`Strip`].
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "rstrip",
@@ -75,7 +75,9 @@ struct RstripFilter;
 impl Filter for RstripFilter {
     fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
         let input = input.to_kstr();
-        Ok(Value::scalar(input.trim_end().to_owned()))
+        Ok(Value::scalar(
+            input.trim_end_matches(is_whitespace).to_owned(),
+        ))
     }
 }
 
@@ -119,7 +121,7 @@ mod tests {
     fn unit_lstrip_non_string() {
         assert_eq!(
             liquid_core::call_filter!(Lstrip, 0f64).unwrap(),
-            liquid_core::value!("0")
+            liquid_core::value!("0.0")
         );
     }
 
@@ -182,7 +184,7 @@ mod tests {
     fn unit_rstrip_non_string() {
         assert_eq!(
             liquid_core::call_filter!(Rstrip, 0f64).unwrap(),
-            liquid_core::value!("0")
+            liquid_core::value!("0.0")
         );
     }
 
@@ -221,7 +223,7 @@ mod tests {
     fn unit_strip_non_string() {
         assert_eq!(
             liquid_core::call_filter!(Strip, 0f64).unwrap(),
-            liquid_core::value!("0")
+            liquid_core::value!("0.0")
         );
     }
 
@@ -240,6 +242,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_strip_ruby_whitespace() {
+        assert_eq!(
+            liquid_core::call_filter!(Strip, "\0\x0B\x0C test \x0C\x0B\0").unwrap(),
+            liquid_core::value!("test")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Lstrip, "\u{a0}\u{3000} test").unwrap(),
+            liquid_core::value!("\u{a0}\u{3000} test")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Rstrip, "test \u{a0}\u{2003}").unwrap(),
+            liquid_core::value!("test \u{a0}\u{2003}")
+        );
+    }
+
     #[test]
     fn unit_strip_trailing_sequence_only() {
         assert_eq!(
@@ -276,7 +294,23 @@ mod tests {
     fn unit_strip_newlines_non_string() {
         assert_eq!(
             liquid_core::call_filter!(StripNewlines, 0f64).unwrap(),
-            liquid_core::value!("0")
+            liquid_core::value!("0.0")
+        );
+    }
+
+    #[test]
+    fn unit_strip_newlines_carriage_returns() {
+        assert_eq!(
+            liquid_core::call_filter!(StripNewlines, "a\r\nb\rc\n\r").unwrap(),
+            liquid_core::value!("abc")
+        );
+    }
+
+    #[test]
+    fn unit_strip_newlines_nil() {
+        assert_eq!(
+            liquid_core::call_filter!(StripNewlines, liquid_core::Value::Nil).unwrap(),
+            liquid_core::value!("")
         );
     }
 
@@ -1,5 +1,3 @@
-use std::cmp;
-
 use liquid_core::Expression;
 use liquid_core::Result;
 use liquid_core::Runtime;
@@ -72,15 +70,11 @@ impl Filter for TruncateFilter {
 
         let length = args.length.unwrap_or(50) as usize;
         let truncate_string = args.ellipsis.unwrap_or_else(|| "...".into());
-        let diff = if length >= truncate_string.len() {
-            length - truncate_string.len()
-        } else {
-            0
-        };
-        let l = cmp::max(diff, 0);
+        let ellipsis_length = truncate_string.as_str().graphemes(true).count();
+        let l = length.saturating_sub(ellipsis_length);
 
         let input_string = input.to_kstr();
-        let result = if length < input_string.len() {
+        let result = if length < input_string.as_str().graphemes(true).count() {
             let result = UnicodeSegmentation::graphemes(input_string.as_str(), true)
                 .take(l)
                 .collect::<Vec<&str>>()
@@ -109,17 +103,22 @@ struct TruncateWordsArgs {
     ellipsis: Option<Expression>,
 }
 
+/// `truncatewords` shortens a string down to the number of words passed as a parameter, 15 by
+/// default.
+///
+/// Words are separated by any run of whitespace, and are joined back with a single space when
+/// the string is truncated. If the string has no more words than allowed, it is returned as-is.
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "truncatewords",
-    description = "Shortens a string down to the number of characters passed as a parameter.",
+    description = "Shortens a string down to the number of words passed as a parameter.",
     parameters(TruncateWordsArgs),
     parsed(TruncateWordsFilter)
 )]
 pub struct TruncateWords;
 
 #[derive(Debug, FromFilterParameters, Display_filter)]
-#[name = "truncate"]
+#[name = "truncatewords"]
 struct TruncateWordsFilter {
     #[parameters]
     args: TruncateWordsArgs,
@@ -129,17 +128,19 @@ impl Filter for TruncateWordsFilter {
     fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
         let args = self.args.evaluate(runtime)?;
 
-        let words = args.length.unwrap_or(50) as usize;
+        let words = match usize::try_from(args.length.unwrap_or(15)) {
+            Ok(words) => words,
+            Err(_) => return Ok(input.to_value()),
+        };
 
         let truncate_string = args.ellipsis.unwrap_or_else(|| "...".into());
 
-        let l = cmp::max(words, 0);
-
         let input_string = input.to_kstr();
 
-        let word_list: Vec<&str> = input_string.split(' ').collect();
-        let result = if words < word_list.len() {
-            let result = itertools::join(word_list.iter().take(l), " ") + truncate_string.as_str();
+        let mut word_list = input_string.split_whitespace();
+        let result = if word_list.clone().nth(words).is_some() {
+            let result =
+                itertools::join(word_list.by_ref().take(words), " ") + truncate_string.as_str();
             Value::scalar(result)
         } else {
             input.to_value()
@@ -238,13 +239,35 @@ mod tests {
             liquid_core::value!("Here is an a\u{310}, e\u{301}, ...")
         );
 
-        // Note that the 🇷🇺🇸🇹 is treated as a single grapheme cluster.
+        // Note that each flag, like 🇷🇺, is treated as a single grapheme cluster.
         assert_eq!(
-            liquid_core::call_filter!(Truncate, "Here is a RUST: 🇷🇺🇸🇹.", 20i64).unwrap(),
+            liquid_core::call_filter!(Truncate, "Here is a RUST: 🇷🇺🇸🇹🇷🇺🇸🇹.", 20i64).unwrap(),
             liquid_core::value!("Here is a RUST: 🇷🇺...")
         );
     }
 
+    #[test]
+    fn unit_truncate_multibyte() {
+        // Lengths count characters, not bytes, for both the input and the ellipsis.
+        assert_eq!(
+            liquid_core::call_filter!(Truncate, "Ground control to Major Tom.", 20i64, "…")
+                .unwrap(),
+            liquid_core::value!("Ground control to M…")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Truncate, "日本語のテキスト", 8i64).unwrap(),
+            liquid_core::value!("日本語のテキスト")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Truncate, "日本語のテキスト", 5i64, "…").unwrap(),
+            liquid_core::value!("日本語の…")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(Truncate, "héllo wörld", 2i64, "…").unwrap(),
+            liquid_core::value!("h…")
+        );
+    }
+
     #[test]
     fn unit_truncate_zero_arguments() {
         assert_eq!(
@@ -293,6 +316,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_truncatewords_default_length() {
+        let input = "a b c d e f g h i j k l m n o p q";
+        assert_eq!(
+            liquid_core::call_filter!(TruncateWords, input).unwrap(),
+            liquid_core::value!("a b c d e f g h i j k l m n o...")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(TruncateWords, "a b c d e f g h i j k l m n o").unwrap(),
+            liquid_core::value!("a b c d e f g h i j k l m n o")
+        );
+    }
+
+    #[test]
+    fn unit_truncatewords_whitespace() {
+        assert_eq!(
+            liquid_core::call_filter!(TruncateWords, "  one \n two\t\tthree  four", 2_i64).unwrap(),
+            liquid_core::value!("one two...")
+        );
+        assert_eq!(
+            liquid_core::call_filter!(TruncateWords, "  one  two  ", 2_i64).unwrap(),
+            liquid_core::value!("  one  two  ")
+        );
+    }
+
     #[test]
     fn unit_truncatewords_empty_string() {
         assert_eq!(
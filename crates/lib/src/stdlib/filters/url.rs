@@ -5,10 +5,13 @@ use liquid_core::{Value, ValueView};
 
 use crate::invalid_input;
 
+// Form-encoding, like Ruby's `CGI.escape`: spaces are left for `+`.
 const FRAGMENT: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
     .remove(b'-')
     .remove(b'.')
-    .remove(b'_');
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b' ');
 
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
@@ -30,7 +33,9 @@ impl Filter for UrlEncodeFilter {
 
         let s = input.to_kstr();
 
-        let result: String = percent_encoding::utf8_percent_encode(s.as_str(), FRAGMENT).collect();
+        let result: String = percent_encoding::utf8_percent_encode(s.as_str(), FRAGMENT)
+            .collect::<String>()
+            .replace(' ', "+");
         Ok(Value::scalar(result))
     }
 }
@@ -71,21 +76,24 @@ mod tests {
     fn unit_url_encode() {
         assert_eq!(
             liquid_core::call_filter!(UrlEncode, "foo bar").unwrap(),
-            liquid_core::value!("foo%20bar")
+            liquid_core::value!("foo+bar")
         );
         assert_eq!(
             liquid_core::call_filter!(UrlEncode, "foo+1@example.com").unwrap(),
             liquid_core::value!("foo%2B1%40example.com")
         );
+        assert_eq!(
+            liquid_core::call_filter!(UrlEncode, "a~b*c/d?e=f&g=h é").unwrap(),
+            liquid_core::value!("a~b%2Ac%2Fd%3Fe%3Df%26g%3Dh+%C3%A9")
+        );
     }
 
     #[test]
     fn unit_url_decode() {
-        // TODO Test case from shopify/liquid that we aren't handling:
-        // - assert_eq!(
-        //      liquid_core::call_filter!(url_decode, "foo+bar").unwrap(),
-        //      liquid_core::value!("foo bar")
-        //  );
+        assert_eq!(
+            liquid_core::call_filter!(UrlDecode, "foo+bar").unwrap(),
+            liquid_core::value!("foo bar")
+        );
         assert_eq!(
             liquid_core::call_filter!(UrlDecode, "foo%20bar").unwrap(),
             liquid_core::value!("foo bar")
@@ -94,5 +102,14 @@ mod tests {
             liquid_core::call_filter!(UrlDecode, "foo%2B1%40example.com").unwrap(),
             liquid_core::value!("foo+1@example.com")
         );
+        assert_eq!(
+            liquid_core::call_filter!(UrlDecode, "a~b%2Ac%2Fd%3Fe%3Df%26g%3Dh+%C3%A9").unwrap(),
+            liquid_core::value!("a~b*c/d?e=f&g=h é")
+        );
+    }
+
+    #[test]
+    fn unit_url_decode_malformed() {
+        liquid_core::call_filter!(UrlDecode, "%FF").unwrap_err();
     }
 }
@@ -9,3 +9,4 @@ pub use tags::*;
 pub use blocks::ForloopObject;
 pub use blocks::Range;
 pub use blocks::RangeExpression;
+pub use blocks::TableRowObject;
@@ -124,6 +124,59 @@ mod test {
         assert_eq!(output, "false");
     }
 
+    #[test]
+    fn assign_arithmetic() {
+        let text = concat!(
+            "{% assign total = price * qty + shipping %}{{ total }} ",
+            "{% assign rest = qty % 2 - 1 %}{{ rest }} ",
+            "{% assign half = qty / 2 %}{{ half }}",
+        );
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("price".into(), Value::scalar(2.5));
+        runtime.set_global("qty".into(), Value::scalar(3));
+        runtime.set_global("shipping".into(), Value::scalar(1));
+
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "8.5 0 1");
+    }
+
+    #[test]
+    fn assign_arithmetic_non_number() {
+        let text = "{% assign total = price + 'free' %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("price".into(), Value::scalar(2.5));
+
+        assert!(template.render(&runtime).is_err());
+    }
+
+    #[test]
+    fn assign_range() {
+        let text = concat!(
+            "{% assign pages = (1..num_pages) %}",
+            "{% for page in pages %}{{ page }}{% endfor %} {{ pages.size }}"
+        );
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("num_pages".into(), Value::scalar(4));
+
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "1234 4");
+    }
+
     #[test]
     fn assign_array_indexing() {
         let text = concat!("{% assign freestyle = tags[1] %}", "{{ freestyle }}");
@@ -3,7 +3,6 @@ use std::io::Write;
 
 use liquid_core::error::{ResultLiquidExt, ResultLiquidReplaceExt};
 use liquid_core::parser::TagToken;
-use liquid_core::parser::TryMatchToken;
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -48,21 +47,16 @@ impl ParseTag for CycleTag {
 /// Internal implementation of cycle, to allow easier testing.
 fn parse_cycle(mut arguments: TagTokenIter<'_>, _options: &Language) -> Result<Cycle> {
     let mut name = String::new();
+    let mut group = None;
     let mut values = Vec::new();
 
     let first = arguments.expect_next("Identifier or value expected")?;
     let second = arguments.next();
     match second.as_ref().map(TagToken::as_str) {
         Some(":") => {
-            name = match first.expect_identifier() {
-                TryMatchToken::Matches(name) => name.to_string(),
-                TryMatchToken::Fails(name) => match name.expect_literal() {
-                    // This will allow non string literals such as 0 to be parsed as such.
-                    // Is this ok or should more specific functions be created?
-                    TryMatchToken::Matches(name) => name.to_kstr().into_string(),
-                    TryMatchToken::Fails(name) => return name.raise_error().into_err(),
-                },
-            };
+            // Like Ruby Liquid, the group name is an expression so `{% cycle var: ... %}` groups
+            // on the value of `var`.
+            group = Some(first.expect_value().into_result()?);
         }
         Some(",") | None => {
             // first argument is the first item in the cycle
@@ -92,37 +86,61 @@ fn parse_cycle(mut arguments: TagTokenIter<'_>, _options: &Language) -> Result<C
         }
     }
 
-    if name.is_empty() {
+    if group.is_none() {
         name = itertools::join(values.iter(), "-");
     }
 
     // no more arguments should be supplied, trying to supply them is an error
     arguments.expect_nothing()?;
 
-    Ok(Cycle { name, values })
+    Ok(Cycle {
+        name,
+        group,
+        values,
+    })
 }
 
 #[derive(Clone, Debug)]
 struct Cycle {
     name: String,
+    group: Option<Expression>,
     values: Vec<Expression>,
 }
 
 impl Cycle {
     fn trace(&self) -> String {
-        format!(
-            "{{% cycle {} %}}",
-            itertools::join(self.values.iter(), ", ")
-        )
+        match &self.group {
+            Some(group) => format!(
+                "{{% cycle {}: {} %}}",
+                group,
+                itertools::join(self.values.iter(), ", ")
+            ),
+            None => format!(
+                "{{% cycle {} %}}",
+                itertools::join(self.values.iter(), ", ")
+            ),
+        }
+    }
+
+    fn name(&self, runtime: &dyn Runtime) -> String {
+        match &self.group {
+            // An undefined variable still names a group, using the variable itself.
+            Some(group) => match group.try_evaluate(runtime) {
+                Some(name) => name.to_kstr().into_string(),
+                None => group.to_string(),
+            },
+            None => self.name.clone(),
+        }
     }
 }
 
 impl Renderable for Cycle {
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        let name = self.name(runtime);
         let expr = runtime
             .registers()
             .get_mut::<CycleRegister>()
-            .cycle(&self.name, &self.values)
+            .cycle(&name, &self.values)
             .trace_with(|| self.trace().into())?;
         let value = expr.evaluate(runtime).trace_with(|| self.trace().into())?;
         write!(writer, "{}", value.render()).replace("Failed to render")?;
@@ -236,7 +254,27 @@ mod test {
 
         let output = template.render(&runtime);
 
-        assert_eq!(output.unwrap(), "1\n2\n3\n1\n");
+        assert_eq!(output.unwrap(), "1.0\n2.0\n3.0\n1.0\n");
+    }
+
+    #[test]
+    fn named_values_from_runtime() {
+        let text = concat!(
+            "{% cycle group: 'one', 'two' %}\n",
+            "{% cycle other: 'one', 'two' %}\n",
+            "{% cycle group: 'one', 'two' %}\n",
+            "{% cycle missing: 'one', 'two' %}\n"
+        );
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("group".into(), Value::scalar("a"));
+        runtime.set_global("other".into(), Value::scalar("a"));
+        let output = template.render(&runtime);
+
+        assert_eq!(output.unwrap(), "one\ntwo\none\none\n");
     }
 
     #[test]
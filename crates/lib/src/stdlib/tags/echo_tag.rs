@@ -0,0 +1,140 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::parser::FilterChain;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EchoTag;
+
+impl EchoTag {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TagReflection for EchoTag {
+    fn tag(&self) -> &'static str {
+        "echo"
+    }
+
+    fn description(&self) -> &'static str {
+        "output an expression, like `{{ }}`, usable within the `liquid` tag"
+    }
+}
+
+impl ParseTag for EchoTag {
+    fn parse(
+        &self,
+        mut arguments: TagTokenIter<'_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        let src = arguments
+            .expect_next("FilterChain expected.")?
+            .expect_filter_chain(options)
+            .into_result()?;
+
+        // no more arguments should be supplied, trying to supply them is an error
+        arguments.expect_nothing()?;
+
+        Ok(Box::new(Echo { src }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Echo {
+    src: FilterChain,
+}
+
+impl Echo {
+    fn trace(&self) -> String {
+        format!("{{% echo {} %}}", self.src)
+    }
+}
+
+impl Renderable for Echo {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        self.src
+            .render_to(writer, runtime)
+            .trace_with(|| self.trace().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::model::Value;
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options.tags.register("echo".to_string(), EchoTag.into());
+        options
+            .tags
+            .register("liquid".to_string(), stdlib::LiquidTag.into());
+        options
+            .filters
+            .register("upcase".to_string(), Box::new(stdlib::Upcase));
+        options
+            .blocks
+            .register("for".to_string(), stdlib::ForBlock.into());
+        options
+    }
+
+    #[test]
+    fn echo() {
+        let text = "{% echo title | upcase %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        runtime.set_global("title".into(), Value::scalar("liquid"));
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "LIQUID");
+    }
+
+    #[test]
+    fn echo_matches_output() {
+        let text = "{% echo 'a' | upcase %}{{ 'a' | upcase }}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "AA");
+    }
+
+    #[test]
+    fn echo_in_liquid() {
+        let text = "{% liquid\n for i in (1..3)\n  echo i\n endfor\n%}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "123");
+    }
+
+    #[test]
+    fn echo_requires_expression() {
+        let text = "{% echo %}";
+        let template = parser::parse(text, &options());
+        assert!(template.is_err());
+    }
+}
@@ -1,7 +1,10 @@
 use std::io::Write;
 
 use liquid_core::error::ResultLiquidExt;
-use liquid_core::model::KString;
+use liquid_core::model::{KString, KStringRef};
+use liquid_core::parser::FilterChain;
+use liquid_core::parser::TryMatchToken;
+use liquid_core::runtime::{Interrupt, InterruptRegister, LoopGuard};
 use liquid_core::Expression;
 use liquid_core::Language;
 use liquid_core::Renderable;
@@ -10,6 +13,9 @@ use liquid_core::{runtime::StackFrame, Runtime};
 use liquid_core::{Error, Result};
 use liquid_core::{ParseTag, TagReflection, TagTokenIter};
 
+use super::render_tag::{implicit_alias, parse_alias};
+use crate::stdlib::ForloopObject;
+
 #[derive(Copy, Clone, Debug, Default)]
 pub struct IncludeTag;
 
@@ -33,14 +39,51 @@ impl ParseTag for IncludeTag {
     fn parse(
         &self,
         mut arguments: TagTokenIter<'_>,
-        _options: &Language,
+        options: &Language,
     ) -> Result<Box<dyn Renderable>> {
         let partial = arguments.expect_next("Identifier or literal expected.")?;
 
-        let partial = partial.expect_value().into_result()?;
+        // The name may be computed, like `{% include 'card-' | append: kind %}`
+        let partial = partial.expect_filter_chain(options).into_result()?;
+
+        let mut token = arguments.next();
+        let mut with = None;
+        let mut for_ = None;
+        match token.as_ref().map(|t| t.as_str()) {
+            Some("with") => {
+                let val = arguments
+                    .expect_next("expected value")?
+                    .expect_value()
+                    .into_result()?;
+
+                let (alias, next) = parse_alias(&mut arguments)?;
+                with = Some((val, alias));
+                token = next;
+            }
+            Some("for") => {
+                let val = arguments
+                    .expect_next("expected value")?
+                    .expect_value()
+                    .into_result()?;
+
+                let (alias, next) = parse_alias(&mut arguments)?;
+                for_ = Some((val, alias));
+                token = next;
+            }
+            _ => {}
+        }
+
+        // Ruby Liquid separates the parameters from the partial name with a comma
+        // (`{% include 'card', title: product.title %}`) while it's optional here.
+        if let Some(comma) = token.take() {
+            token = match comma.expect_str(",") {
+                TryMatchToken::Matches(()) => arguments.next(),
+                TryMatchToken::Fails(comma) => Some(comma),
+            };
+        }
 
         let mut vars: Vec<(KString, Expression)> = Vec::new();
-        while let Ok(next) = arguments.expect_next("") {
+        while let Some(next) = token.take() {
             let id = next.expect_identifier().into_result()?.to_string();
 
             arguments
@@ -56,18 +99,23 @@ impl ParseTag for IncludeTag {
                     .into_result()?,
             ));
 
-            if let Ok(comma) = arguments.expect_next("") {
-                // stop looking for variables if there is no comma
-                // currently allows for one trailing comma
-                if comma.expect_str(",").into_result().is_err() {
-                    break;
-                }
+            // currently allows for one trailing comma
+            if let Some(comma) = arguments.next() {
+                comma
+                    .expect_str(",")
+                    .into_result_custom_msg("`,` is needed to separate variables")?;
+                token = arguments.next();
             }
         }
 
         arguments.expect_nothing()?;
 
-        Ok(Box::new(Include { partial, vars }))
+        Ok(Box::new(Include {
+            partial,
+            with,
+            for_,
+            vars,
+        }))
     }
 
     fn reflection(&self) -> &dyn TagReflection {
@@ -77,10 +125,33 @@ impl ParseTag for IncludeTag {
 
 #[derive(Debug)]
 struct Include {
-    partial: Expression,
+    partial: FilterChain,
+    with: Option<(Expression, Option<KString>)>,
+    for_: Option<(Expression, Option<KString>)>,
     vars: Vec<(KString, Expression)>,
 }
 
+impl Include {
+    fn trace(&self) -> String {
+        format!("{{% include {} %}}", self.partial)
+    }
+
+    /// Render the partial named `name` with the variables of `scope`.
+    fn render_partial(
+        &self,
+        partial: &dyn Renderable,
+        name: &str,
+        writer: &mut dyn Write,
+        scope: &dyn Runtime,
+    ) -> Result<()> {
+        partial
+            .render_to(writer, scope)
+            .trace_with(|| self.trace().into())
+            .context_key_with(|| self.partial.to_string().into())
+            .value_with(|| name.to_owned().into())
+    }
+}
+
 impl Renderable for Include {
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
         let value = self.partial.evaluate(runtime)?;
@@ -110,13 +181,57 @@ impl Renderable for Include {
             let partial = scope
                 .partials()
                 .get(&name)
-                .trace_with(|| format!("{{% include {} %}}", self.partial).into())?;
-
-            partial
-                .render_to(writer, &scope)
-                .trace_with(|| format!("{{% include {} %}}", self.partial).into())
-                .context_key_with(|| self.partial.to_string().into())
-                .value_with(|| name.to_string().into())?;
+                .trace_with(|| self.trace().into())?;
+
+            if let Some((val, alias)) = &self.for_ {
+                let value = val.evaluate(&scope).trace_with(|| self.trace().into())?;
+                let alias = alias.clone().unwrap_or_else(|| implicit_alias(&name));
+
+                // like Ruby Liquid, anything that isn't an array is included once
+                let Some(array) = value.as_array() else {
+                    let mut root = std::collections::HashMap::new();
+                    root.insert(alias.as_ref(), value.as_view());
+                    let scope = StackFrame::new(&scope, &root);
+                    return self.render_partial(partial.as_ref(), &name, writer, &scope);
+                };
+
+                let len = array.size() as usize;
+                let _loop = LoopGuard::enter(runtime);
+                for (i, v) in array.values().enumerate() {
+                    let forloop = ForloopObject::new(i, len);
+                    let mut root = std::collections::HashMap::new();
+                    root.insert(
+                        KStringRef::from_static("forloop"),
+                        &forloop as &dyn ValueView,
+                    );
+                    root.insert(alias.as_ref(), v);
+                    let scope = StackFrame::new(&scope, &root);
+
+                    self.render_partial(partial.as_ref(), &name, writer, &scope)
+                        .context_key("index")
+                        .value_with(|| format!("{}", i + 1).into())?;
+
+                    // given that we're at the end of the loop body
+                    // already, dealing with a `continue` signal is just
+                    // clearing the interrupt and carrying on as normal. A
+                    // `break` requires some special handling, though.
+                    let current_interrupt =
+                        scope.registers().get_mut::<InterruptRegister>().unwind();
+                    if let Some(Interrupt::Break) = current_interrupt {
+                        break;
+                    }
+                }
+            } else if let Some((val, alias)) = &self.with {
+                let value = val.evaluate(&scope).trace_with(|| self.trace().into())?;
+                let alias = alias.clone().unwrap_or_else(|| implicit_alias(&name));
+
+                let mut root = std::collections::HashMap::new();
+                root.insert(alias.as_ref(), value.as_view());
+                let scope = StackFrame::new(&scope, &root);
+                self.render_partial(partial.as_ref(), &name, writer, &scope)?;
+            } else {
+                self.render_partial(partial.as_ref(), &name, writer, &scope)?;
+            }
         }
 
         Ok(())
@@ -156,6 +271,7 @@ mod test {
                 "example.txt" => Some(r#"{{'whooo' | size}}{%comment%}What happens{%endcomment%} {%if num < numTwo%}wat{%else%}wot{%endif%} {%if num > numTwo%}wat{%else%}wot{%endif%}"#.into()),
                 "example_var.txt" => Some(r#"{{example_var}}"#.into()),
                 "example_multi_var.txt" => Some(r#"{{example_var}} {{example}}"#.into()),
+                "product" => Some(r#"{{forloop.index}}:{{product}} "#.into()),
                 _ => None
             }
         }
@@ -256,6 +372,33 @@ mod test {
         assert_eq!(output, "hello world");
     }
 
+    #[test]
+    fn include_variables_leading_comma() {
+        let text =
+            "{% include 'example_multi_var.txt', example_var:\"hello\", example:\"world\" %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "hello world");
+    }
+
+    #[test]
+    fn include_variables_missing_comma() {
+        let text = "{% include 'example_multi_var.txt' example_var:\"hello\" example:\"world\" %}";
+        let options = options();
+        let template = parser::parse(text, &options);
+        assert!(template.is_err());
+    }
+
     #[test]
     fn include_multiple_variables_trailing_comma() {
         let text = "{% include 'example_multi_var.txt' example_var:\"hello\", example:\"dogs\", %}";
@@ -274,6 +417,65 @@ mod test {
         assert_eq!(output, "hello dogs");
     }
 
+    #[test]
+    fn include_with() {
+        let text = "{% include 'example_var.txt' with \"hello\" as example_var %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "hello");
+    }
+
+    #[test]
+    fn include_for() {
+        let text = "{% include 'product' for products %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        runtime.set_global(
+            "products".into(),
+            Value::Array(vec![Value::scalar("a"), Value::scalar("b")]),
+        );
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "1:a 2:b ");
+    }
+
+    #[test]
+    fn include_for_scalar() {
+        let text = "{% include 'example_var.txt' for product as example_var %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        runtime.set_global("product".into(), Value::scalar("a"));
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "a");
+    }
+
     #[test]
     fn no_file() {
         let text = "{% include 'file_does_not_exist.liquid' %}";
@@ -296,4 +498,31 @@ mod test {
         let output = template.render(&runtime);
         assert!(output.is_err());
     }
+
+    #[test]
+    fn include_computed_name() {
+        let text = concat!(
+            "{% include name example_var: 'a' %}",
+            // Further filter arguments would be ambiguous with variables, but `with` isn't
+            "{% include 'example_' | append: kind | append: '.txt' with 'b' as example_var %}",
+        );
+        let mut options = options();
+        options
+            .filters
+            .register("append".to_string(), Box::new(stdlib::Append));
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        runtime.set_global("name".into(), Value::scalar("example_var.txt"));
+        runtime.set_global("kind".into(), Value::scalar("var"));
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "ab");
+    }
 }
@@ -33,12 +33,11 @@ impl ParseTag for IncrementTag {
         mut arguments: TagTokenIter<'_>,
         _options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        let id = arguments
-            .expect_next("Identifier expected.")?
-            .expect_identifier()
-            .into_result()?
-            .to_string()
-            .into();
+        // Like Ruby Liquid, the counter name may be omitted to use an anonymous counter.
+        let id = match arguments.next() {
+            Some(id) => id.expect_identifier().into_result()?.to_string().into(),
+            None => liquid_core::model::KString::from_static(""),
+        };
 
         // no more arguments should be supplied, trying to supply them is an error
         arguments.expect_nothing()?;
@@ -95,12 +94,11 @@ impl ParseTag for DecrementTag {
         mut arguments: TagTokenIter<'_>,
         _options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        let id = arguments
-            .expect_next("Identifier expected.")?
-            .expect_identifier()
-            .into_result()?
-            .to_string()
-            .into();
+        // Like Ruby Liquid, the counter name may be omitted to use an anonymous counter.
+        let id = match arguments.next() {
+            Some(id) => id.expect_identifier().into_result()?.to_string().into(),
+            None => liquid_core::model::KString::from_static(""),
+        };
 
         // no more arguments should be supplied, trying to supply them is an error
         arguments.expect_nothing()?;
@@ -192,6 +190,18 @@ mod test {
         assert_eq!(output, "0110");
     }
 
+    #[test]
+    fn anonymous_counter() {
+        let text = "{% increment %}{% increment %}{% decrement %}{% increment val %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "0110");
+    }
+
     #[test]
     fn assign_and_increment() {
         let text = "{%- assign val = 9 -%}{% increment val %}{% increment val %}{{ val }}";
@@ -0,0 +1,99 @@
+use std::io::Write;
+
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct InlineCommentTag;
+
+impl InlineCommentTag {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TagReflection for InlineCommentTag {
+    fn tag(&self) -> &'static str {
+        "#"
+    }
+
+    fn description(&self) -> &'static str {
+        "a comment that is not rendered, like `{% # this %}`"
+    }
+}
+
+impl ParseTag for InlineCommentTag {
+    fn parse(
+        &self,
+        _arguments: TagTokenIter<'_>,
+        _options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        // The grammar keeps the comment as a single raw token, which is ignored.
+        Ok(Box::new(InlineComment))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct InlineComment;
+
+impl Renderable for InlineComment {
+    fn render_to(&self, _writer: &mut dyn Write, _runtime: &dyn Runtime) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::parser;
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("#".to_string(), InlineCommentTag.into());
+        options
+            .tags
+            .register("liquid".to_string(), stdlib::LiquidTag.into());
+        options
+            .tags
+            .register("echo".to_string(), stdlib::EchoTag.into());
+        options
+    }
+
+    #[test]
+    fn inline_comment() {
+        let text = "a{% # this is a comment %}b{%# another one, with {{ liquid }} %}c{%- # -%} d";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "abcd");
+    }
+
+    #[test]
+    fn inline_comment_in_liquid() {
+        let text = "{% liquid\n # a comment\n echo 'a'\n #echo 'b'\n%}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "a");
+    }
+}
@@ -1,10 +1,14 @@
+use std::fmt;
 use std::io::Write;
 
+use liquid_core::error::ResultLiquidExt;
 use liquid_core::runtime::{Interrupt, InterruptRegister};
+use liquid_core::Error;
 use liquid_core::Language;
 use liquid_core::Renderable;
 use liquid_core::Result;
 use liquid_core::Runtime;
+use liquid_core::ValueView;
 use liquid_core::{ParseTag, TagReflection, TagTokenIter};
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -32,9 +36,8 @@ impl ParseTag for BreakTag {
         mut arguments: TagTokenIter<'_>,
         _options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        // no arguments should be supplied, trying to supply them is an error
-        arguments.expect_nothing()?;
-        Ok(Box::new(Break))
+        let levels = parse_levels(&mut arguments)?;
+        Ok(Box::new(Break { levels }))
     }
 
     fn reflection(&self) -> &dyn TagReflection {
@@ -43,18 +46,58 @@ impl ParseTag for BreakTag {
 }
 
 #[derive(Copy, Clone, Debug)]
-struct Break;
+struct Break {
+    levels: usize,
+}
 
 impl Renderable for Break {
     fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
-        runtime
-            .registers()
-            .get_mut::<InterruptRegister>()
-            .set(Interrupt::Break);
+        let mut interrupt = runtime.registers().get_mut::<InterruptRegister>();
+        check_levels(&interrupt, self.levels).trace_with(|| self.to_string().into())?;
+        interrupt.set_levels(Interrupt::Break, self.levels);
         Ok(())
     }
 }
 
+impl fmt::Display for Break {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{% break {} %}}", self.levels)
+    }
+}
+
+/// How many loops out the interrupt targets, like the `2` in `{% break 2 %}`.
+fn parse_levels(arguments: &mut TagTokenIter<'_>) -> Result<usize> {
+    let levels = match arguments.next() {
+        Some(token) => {
+            let levels = token
+                .expect_literal()
+                .into_result()?
+                .as_scalar()
+                .and_then(|s| s.to_integer())
+                .filter(|levels| 0 < *levels)
+                .ok_or_else(|| Error::with_msg("Expected a positive number of loops"))?;
+            levels as usize
+        }
+        None => 1,
+    };
+
+    // no more arguments should be supplied, trying to supply them is an error
+    arguments.expect_nothing()?;
+    Ok(levels)
+}
+
+/// An interrupt may only target loops around it, though a lone `{% break %}` or `{% continue %}`
+/// outside of any loop stops the template, like Ruby.
+fn check_levels(interrupt: &InterruptRegister, levels: usize) -> Result<()> {
+    if 1 < levels && interrupt.loops() < levels {
+        return Error::with_msg("Not enough loops to interrupt")
+            .context("loops", interrupt.loops().to_string())
+            .context("requested", levels.to_string())
+            .into_err();
+    }
+    Ok(())
+}
+
 #[derive(Copy, Clone, Debug, Default)]
 pub struct ContinueTag;
 
@@ -80,9 +123,8 @@ impl ParseTag for ContinueTag {
         mut arguments: TagTokenIter<'_>,
         _options: &Language,
     ) -> Result<Box<dyn Renderable>> {
-        // no arguments should be supplied, trying to supply them is an error
-        arguments.expect_nothing()?;
-        Ok(Box::new(Continue))
+        let levels = parse_levels(&mut arguments)?;
+        Ok(Box::new(Continue { levels }))
     }
 
     fn reflection(&self) -> &dyn TagReflection {
@@ -91,23 +133,31 @@ impl ParseTag for ContinueTag {
 }
 
 #[derive(Copy, Clone, Debug)]
-struct Continue;
+struct Continue {
+    levels: usize,
+}
 
 impl Renderable for Continue {
     fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
-        runtime
-            .registers()
-            .get_mut::<InterruptRegister>()
-            .set(Interrupt::Continue);
+        let mut interrupt = runtime.registers().get_mut::<InterruptRegister>();
+        check_levels(&interrupt, self.levels).trace_with(|| self.to_string().into())?;
+        interrupt.set_levels(Interrupt::Continue, self.levels);
         Ok(())
     }
 }
 
+impl fmt::Display for Continue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{% continue {} %}}", self.levels)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     use liquid_core::parser;
+    use liquid_core::partials::{self, PartialCompiler};
     use liquid_core::runtime;
     use liquid_core::runtime::RuntimeBuilder;
 
@@ -236,4 +286,154 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_break_outer() {
+        let text = concat!(
+            "{% for outer in (0..3) %}",
+            "enter-{{outer}}; ",
+            "{% for inner in (6..10) %}",
+            "{% if outer == 1 and inner == 8 %}break, {% break 2 %}{% endif %}",
+            "{{ inner }}, ",
+            "{% endfor %}",
+            "exit-{{outer}}\n",
+            "{% endfor %}",
+            "done"
+        );
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let rt = RuntimeBuilder::new().build();
+        let output = template.render(&rt).unwrap();
+        assert_eq!(
+            output,
+            concat!(
+                "enter-0; 6, 7, 8, 9, 10, exit-0\n",
+                "enter-1; 6, 7, break, done",
+            )
+        );
+    }
+
+    #[test]
+    fn test_continue_outer() {
+        let text = concat!(
+            "{% for outer in (0..2) %}",
+            "enter-{{outer}}; ",
+            "{% for inner in (6..10) %}",
+            "{% if inner == 8 %}continue, {% continue 2 %}{% endif %}",
+            "{{ inner }}, ",
+            "{% endfor %}",
+            "exit-{{outer}}\n",
+            "{% endfor %}"
+        );
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let rt = RuntimeBuilder::new().build();
+        let output = template.render(&rt).unwrap();
+        assert_eq!(
+            output,
+            concat!(
+                "enter-0; 6, 7, continue, ",
+                "enter-1; 6, 7, continue, ",
+                "enter-2; 6, 7, continue, ",
+            )
+        );
+    }
+
+    #[test]
+    fn test_invalid_levels() {
+        for text in [
+            "{% for i in (0..1) %}{% break 0 %}{% endfor %}",
+            "{% for i in (0..1) %}{% break 'a' %}{% endfor %}",
+            "{% for i in (0..1) %}{% continue 1 2 %}{% endfor %}",
+        ] {
+            let template = parser::parse(text, &options()).map(runtime::Template::new);
+            assert!(template.is_err(), "{}", text);
+        }
+    }
+    #[test]
+    fn test_levels_beyond_loops() {
+        for text in [
+            "{% for i in (0..1) %}{% break 2 %}{% endfor %}",
+            "{% for i in (0..1) %}{% continue 2 %}{% endfor %}",
+            "{% break 2 %}",
+        ] {
+            let template = parser::parse(text, &options())
+                .map(runtime::Template::new)
+                .unwrap();
+            let rt = RuntimeBuilder::new().build();
+            assert!(template.render(&rt).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_levels_through_tablerow_and_include() {
+        let mut options = options();
+        options
+            .blocks
+            .register("tablerow".to_string(), stdlib::TableRowBlock.into());
+        options
+            .tags
+            .register("include".to_string(), stdlib::IncludeTag.into());
+        let mut source = partials::InMemorySource::new();
+        source.add("stop", "{% break 2 %}");
+        let partials = partials::EagerCompiler::new(source)
+            .compile(std::sync::Arc::new(options.clone()))
+            .unwrap();
+
+        let text = concat!(
+            "{% for i in (0..2) %}{{i}}",
+            "{% tablerow j in (0..2) %}{{j}}{% include 'stop' %}{% endtablerow %}",
+            "after{% endfor %}done",
+        );
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+        let rt = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&rt).unwrap();
+        assert_eq!(
+            output,
+            "0<tr class=\"row1\">\n<td class=\"col1\">0</td></tr>\ndone"
+        );
+
+        // `include ... for` loops over its collection, so counts as a loop too
+        let text = "{% for i in (0..2) %}{{i}}{% include 'stop' for (1..3) %}after{% endfor %}done";
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+        let rt = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&rt).unwrap();
+        assert_eq!(output, "0done");
+    }
+
+    #[test]
+    fn test_loops_restored_after_error() {
+        let text = "{% for i in (0..1) %}{{ missing }}{% endfor %}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let rt = RuntimeBuilder::new().build();
+        assert!(template.render(&rt).is_err());
+        assert_eq!(rt.registers().get_mut::<InterruptRegister>().loops(), 0);
+    }
+
+    #[test]
+    fn test_break_outside_loop() {
+        let text = "before{% break %}after";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let rt = RuntimeBuilder::new().build();
+        let output = template.render(&rt).unwrap();
+        assert_eq!(output, "before");
+    }
 }
@@ -0,0 +1,166 @@
+use std::io::Write;
+
+use liquid_core::error::ResultLiquidExt;
+use liquid_core::parser;
+use liquid_core::Language;
+use liquid_core::Renderable;
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::Template;
+use liquid_core::{ParseTag, TagReflection, TagTokenIter};
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LiquidTag;
+
+impl LiquidTag {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TagReflection for LiquidTag {
+    fn tag(&self) -> &'static str {
+        "liquid"
+    }
+
+    fn description(&self) -> &'static str {
+        "write multiple tags, one per line, within a single set of delimiters"
+    }
+}
+
+impl ParseTag for LiquidTag {
+    fn parse(
+        &self,
+        arguments: TagTokenIter<'_>,
+        options: &Language,
+    ) -> Result<Box<dyn Renderable>> {
+        // The grammar splits the body into a tag per line.
+        let statements = parser::parse_liquid_lines(arguments, options).trace("{% liquid %}")?;
+
+        Ok(Box::new(Liquid {
+            statements: Template::new(statements),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Liquid {
+    statements: Template,
+}
+
+impl Renderable for Liquid {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+        self.statements
+            .render_to(writer, runtime)
+            .trace("{% liquid %}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use liquid_core::runtime;
+    use liquid_core::runtime::RuntimeBuilder;
+
+    use crate::stdlib;
+
+    fn options() -> Language {
+        let mut options = Language::default();
+        options
+            .tags
+            .register("liquid".to_string(), LiquidTag.into());
+        options
+            .tags
+            .register("assign".to_string(), stdlib::AssignTag.into());
+        options
+            .tags
+            .register("increment".to_string(), stdlib::IncrementTag.into());
+        options
+            .blocks
+            .register("if".to_string(), stdlib::IfBlock.into());
+        options
+            .blocks
+            .register("for".to_string(), stdlib::ForBlock.into());
+        options
+    }
+
+    #[test]
+    fn liquid_statements() {
+        let text = concat!(
+            "{% liquid\n",
+            "  assign limit = 2\n",
+            "  for i in (1..5) limit: limit\n",
+            "    if i > 1\n",
+            "      increment counter\n",
+            "    endif\n",
+            "    increment counter\n",
+            "  endfor\n",
+            "%}{{ limit }}",
+        );
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "0122");
+    }
+
+    #[test]
+    fn liquid_whitespace_control() {
+        let text = "a {%- liquid assign x = 1 -%} b{{ x }}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "ab1");
+    }
+
+    #[test]
+    fn liquid_empty() {
+        let text = "{% liquid %}{% liquid\n\n%}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn liquid_comments() {
+        let text = "{% liquid\n  # set up\n  assign x = 'a' # not a comment\n%}{{ x }}";
+        assert!(parser::parse(text, &options()).is_err());
+
+        let text = "{% liquid\n  # set up\n  assign x = 'a'\n\n%}{{ x }}";
+        let template = parser::parse(text, &options())
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let runtime = RuntimeBuilder::new().build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "a");
+    }
+
+    #[test]
+    fn liquid_unclosed_block() {
+        let text = "{% liquid\n if true\n assign x = 1\n%}";
+        let template = parser::parse(text, &options());
+        assert!(template.is_err());
+    }
+
+    #[test]
+    fn liquid_unknown_tag() {
+        let text = "{% liquid\n unknown\n%}";
+        let template = parser::parse(text, &options());
+        assert!(template.is_err());
+    }
+}
@@ -2,7 +2,7 @@ use std::io::Write;
 
 use liquid_core::error::ResultLiquidExt;
 use liquid_core::model::KString;
-use liquid_core::parser::TryMatchToken;
+use liquid_core::parser::{TagToken, TryMatchToken};
 use liquid_core::runtime::GlobalFrame;
 use liquid_core::runtime::Interrupt;
 use liquid_core::runtime::InterruptRegister;
@@ -49,6 +49,7 @@ impl ParseTag for RenderTag {
 
         let mut token = arguments.next();
         let mut vars: Vec<(KString, Expression)> = Vec::new();
+        let mut with = None;
         let mut for_ = None;
         match token.as_ref().map(|t| t.as_str()) {
             Some("with") => {
@@ -57,48 +58,23 @@ impl ParseTag for RenderTag {
                     .expect_value()
                     .into_result()?;
 
-                arguments
-                    .expect_next("\"as\" expected.")?
-                    .expect_str("as")
-                    .into_result_custom_msg("expected \"as\" to be used for the assignment")?;
-
-                vars.push((
-                    arguments
-                        .expect_next("expected identifier")?
-                        .expect_identifier()
-                        .into_result()?
-                        .to_string()
-                        .into(),
-                    val,
-                ));
-                token = arguments.next();
+                let (alias, next) = parse_alias(&mut arguments)?;
+                with = Some((val, alias));
+                token = next;
             }
             Some("for") => {
                 let range = arguments.expect_next("Array or range expected.")?;
-                let range = match range.expect_value() {
-                    TryMatchToken::Matches(array) => RangeExpression::Array(array),
-                    TryMatchToken::Fails(range) => match range.expect_range() {
-                        TryMatchToken::Matches((start, stop)) => {
-                            RangeExpression::Counted(start, stop)
-                        }
+                let range = match range.expect_range() {
+                    TryMatchToken::Matches((start, stop)) => RangeExpression::Counted(start, stop),
+                    TryMatchToken::Fails(range) => match range.expect_value() {
+                        TryMatchToken::Matches(array) => RangeExpression::Array(array),
                         TryMatchToken::Fails(range) => return range.raise_error().into_err(),
                     },
                 };
 
-                arguments
-                    .expect_next("\"as\" expected")?
-                    .expect_str("as")
-                    .into_result_custom_msg("\"as\" expected")?;
-
-                let var_name = arguments
-                    .expect_next("Identifier expected.")?
-                    .expect_identifier()
-                    .into_result()?
-                    .to_string()
-                    .into();
-
-                token = arguments.next();
-                for_ = Some((range, var_name));
+                let (alias, next) = parse_alias(&mut arguments)?;
+                for_ = Some((range, alias));
+                token = next;
             }
             _ => {}
         };
@@ -133,6 +109,7 @@ impl ParseTag for RenderTag {
 
         Ok(Box::new(Render {
             partial,
+            with,
             for_,
             vars,
         }))
@@ -143,13 +120,41 @@ impl ParseTag for RenderTag {
     }
 }
 
+/// Parses the optional `as <identifier>` following `with`/`for`, returning the alias along with
+/// the first token after it.
+pub(super) fn parse_alias<'a>(
+    arguments: &mut TagTokenIter<'a>,
+) -> Result<(Option<KString>, Option<TagToken<'a>>)> {
+    let token = arguments.next();
+    match token.as_ref().map(|t| t.as_str()) {
+        Some("as") => {
+            let alias = arguments
+                .expect_next("Identifier expected.")?
+                .expect_identifier()
+                .into_result()?
+                .to_string()
+                .into();
+            Ok((Some(alias), arguments.next()))
+        }
+        _ => Ok((None, token)),
+    }
+}
+
 #[derive(Debug)]
 struct Render {
     partial: Expression,
-    for_: Option<(RangeExpression, KString)>,
+    with: Option<(Expression, Option<KString>)>,
+    for_: Option<(RangeExpression, Option<KString>)>,
     vars: Vec<(KString, Expression)>,
 }
 
+/// Without an explicit `as`, the partial's value is bound to a variable named after the partial.
+pub(super) fn implicit_alias(name: &str) -> KString {
+    let name = name.rsplit('/').next().unwrap_or(name);
+    let name = name.strip_suffix(".liquid").unwrap_or(name);
+    KString::from_ref(name)
+}
+
 impl Renderable for Render {
     fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
         let value = self.partial.evaluate(runtime)?;
@@ -160,7 +165,8 @@ impl Renderable for Render {
         }
         let name = value.to_kstr().into_owned();
 
-        if let Some((range, var_name)) = &self.for_ {
+        if let Some((range, alias)) = &self.for_ {
+            let var_name = alias.clone().unwrap_or_else(|| implicit_alias(&name));
             let range = range
                 .evaluate(runtime)
                 .trace_with(|| format!("{{% render {} %}}", self.partial).into())?;
@@ -201,14 +207,23 @@ impl Renderable for Render {
                     // clearing the interrupt and carrying on as normal. A
                     // `break` requires some special handling, though.
                     let current_interrupt =
-                        scope.registers().get_mut::<InterruptRegister>().reset();
+                        scope.registers().get_mut::<InterruptRegister>().unwind();
                     if let Some(Interrupt::Break) = current_interrupt {
                         break;
                     }
                 }
             }
         } else {
+            let alias;
             let mut root = std::collections::HashMap::new();
+            if let Some((val, explicit)) = &self.with {
+                let value = val
+                    .try_evaluate(runtime)
+                    .ok_or_else(|| Error::with_msg("failed to evaluate value"))?;
+
+                alias = explicit.clone().unwrap_or_else(|| implicit_alias(&name));
+                root.insert(alias.as_ref(), value);
+            }
             for (id, val) in &self.vars {
                 let value = val
                     .try_evaluate(runtime)
@@ -267,6 +282,8 @@ mod test {
                 "example_var.txt" => Some(r#"{{example_var}}"#.into()),
                 "example_multi_var.txt" => Some(r#"{{example_var}} {{example}}"#.into()),
                 "missing_extension.liquid" => Some(r#"{{example_var}}"#.into()),
+                "example_var.liquid" => Some(r#"{{example_var}}"#.into()),
+                "assigns.txt" => Some(r#"{% assign leaked = 1 %}"#.into()),
                 _ => None
             }
         }
@@ -347,6 +364,60 @@ mod test {
         assert_eq!(output, "hello");
     }
 
+    #[test]
+    fn render_with_implicit_alias() {
+        let text = "{% render 'example_var' with \"hello\" %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "hello");
+    }
+
+    #[test]
+    fn render_for_implicit_alias() {
+        let text = "{% render 'example_var' for (1..3) %}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&runtime).unwrap();
+        assert_eq!(output, "123");
+    }
+
+    #[test]
+    fn render_does_not_leak_assigns() {
+        let text = "{% render 'assigns.txt' %}{{ leaked }}";
+        let options = options();
+        let template = parser::parse(text, &options)
+            .map(runtime::Template::new)
+            .unwrap();
+
+        let partials = partials::OnDemandCompiler::<TestSource>::empty()
+            .compile(::std::sync::Arc::new(options))
+            .unwrap();
+        let runtime = RuntimeBuilder::new()
+            .set_partials(partials.as_ref())
+            .build();
+        let output = template.render(&runtime);
+        assert!(output.is_err());
+    }
+
     #[test]
     fn render_scope() {
         let text = "{% assign numTwo = 10 %}{% render 'example.txt', num: 5 %}";
@@ -13,7 +13,7 @@
 //!     .parse("Liquid! {{num | minus: 2}}").unwrap();
 //!
 //! let mut globals = liquid::object!({
-//!     "num": 4f64
+//!     "num": 4
 //! });
 //!
 //! let output = template.render(&globals).unwrap();
@@ -39,9 +39,11 @@ pub mod model {
 
 pub use crate::parser::*;
 pub use crate::template::*;
+pub use liquid_core::from_value;
 pub use liquid_core::model::{_ObjectView as ObjectView, _ValueView as ValueView};
 pub use liquid_core::object;
 pub use liquid_core::to_object;
+pub use liquid_core::to_value;
 pub use liquid_core::Error;
 pub use liquid_core::Object;
 #[doc(hidden)]
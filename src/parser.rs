@@ -4,6 +4,7 @@ use std::path;
 use std::sync;
 
 use liquid_core::error::{Result, ResultLiquidExt, ResultLiquidReplaceExt};
+use liquid_core::model;
 use liquid_core::parser;
 use liquid_core::runtime;
 
@@ -22,6 +23,13 @@ where
     blocks: parser::PluginRegistry<Box<dyn parser::ParseBlock>>,
     tags: parser::PluginRegistry<Box<dyn parser::ParseTag>>,
     filters: parser::PluginRegistry<Box<dyn parser::ParseFilter>>,
+    highlighter: Option<sync::Arc<dyn parser::SyntaxHighlighter>>,
+    markdown: Option<sync::Arc<dyn parser::MarkdownRenderer>>,
+    comparators: parser::PluginRegistry<sync::Arc<dyn parser::Comparator>>,
+    time_zone: Option<model::TimeZone>,
+    strict_variables: bool,
+    formatter: Option<sync::Arc<dyn parser::ValueFormatter>>,
+    fragment_cache: Option<sync::Arc<dyn parser::FragmentCache>>,
     partials: Option<P>,
 }
 
@@ -48,9 +56,12 @@ where
             .tag(stdlib::BreakTag)
             .tag(stdlib::ContinueTag)
             .tag(stdlib::CycleTag)
+            .tag(stdlib::EchoTag)
             .tag(stdlib::IncludeTag)
+            .tag(stdlib::InlineCommentTag)
             .tag(stdlib::IncrementTag)
             .tag(stdlib::DecrementTag)
+            .tag(stdlib::LiquidTag)
             .tag(stdlib::RenderTag)
             .block(stdlib::RawBlock)
             .block(stdlib::IfBlock)
@@ -75,6 +86,8 @@ where
             .filter(stdlib::Downcase)
             .filter(stdlib::Escape)
             .filter(stdlib::EscapeOnce)
+            .filter(stdlib::Find)
+            .filter(stdlib::FindExp)
             .filter(stdlib::First)
             .filter(stdlib::Floor)
             .filter(stdlib::Join)
@@ -88,8 +101,10 @@ where
             .filter(stdlib::Prepend)
             .filter(stdlib::Remove)
             .filter(stdlib::RemoveFirst)
+            .filter(stdlib::RemoveLast)
             .filter(stdlib::Replace)
             .filter(stdlib::ReplaceFirst)
+            .filter(stdlib::ReplaceLast)
             .filter(stdlib::Reverse)
             .filter(stdlib::Round)
             .filter(stdlib::Rstrip)
@@ -101,6 +116,7 @@ where
             .filter(stdlib::Strip)
             .filter(stdlib::StripHtml)
             .filter(stdlib::StripNewlines)
+            .filter(stdlib::Sum)
             .filter(stdlib::Times)
             .filter(stdlib::Truncate)
             .filter(stdlib::TruncateWords)
@@ -134,18 +150,81 @@ where
         self
     }
 
+    /// Set how blocks like `{% highlight %}` render code.
+    pub fn highlighter<H: parser::SyntaxHighlighter + 'static>(mut self, highlighter: H) -> Self {
+        self.highlighter = Some(sync::Arc::new(highlighter));
+        self
+    }
+
+    /// Set how filters like `markdownify` render Markdown.
+    pub fn markdown_renderer<M: parser::MarkdownRenderer + 'static>(mut self, renderer: M) -> Self {
+        self.markdown = Some(sync::Arc::new(renderer));
+        self
+    }
+
+    /// Inserts a named comparator, used by filters like `sort: comparator: "name"`
+    pub fn comparator<C: parser::Comparator + 'static>(
+        mut self,
+        name: &str,
+        comparator: C,
+    ) -> Self {
+        self.comparators
+            .register(name.to_owned(), sync::Arc::new(comparator));
+        self
+    }
+
+    /// Set how `{{ }}` renders values, like dates in ISO-8601 or floats with fixed precision.
+    pub fn formatter<F: parser::ValueFormatter + 'static>(mut self, formatter: F) -> Self {
+        self.formatter = Some(sync::Arc::new(formatter));
+        self
+    }
+
+    /// Set where blocks like `{% cache %}` store their output, like an
+    /// [`LruFragmentCache`][parser::LruFragmentCache].
+    pub fn fragment_cache<C: parser::FragmentCache + 'static>(mut self, cache: C) -> Self {
+        self.fragment_cache = Some(sync::Arc::new(cache));
+        self
+    }
+
+    /// Set the time zone filters like `date` show dates in, unless told otherwise.
+    pub fn time_zone(mut self, time_zone: model::TimeZone) -> Self {
+        self.time_zone = Some(time_zone);
+        self
+    }
+
+    /// Error on looking up members of nil, like `city` in `{{ user.address.city }}` when
+    /// `address` is nil, rather than rendering nothing.
+    pub fn strict_variables(mut self, strict: bool) -> Self {
+        self.strict_variables = strict;
+        self
+    }
+
     /// Set which partial-templates will be available.
     pub fn partials<N: partials::PartialCompiler>(self, partials: N) -> ParserBuilder<N> {
         let Self {
             blocks,
             tags,
             filters,
+            highlighter,
+            markdown,
+            comparators,
+            time_zone,
+            strict_variables,
+            formatter,
+            fragment_cache,
             partials: _partials,
         } = self;
         ParserBuilder {
             blocks,
             tags,
             filters,
+            highlighter,
+            markdown,
+            comparators,
+            time_zone,
+            strict_variables,
+            formatter,
+            fragment_cache,
             partials: Some(partials),
         }
     }
@@ -156,6 +235,13 @@ where
             blocks,
             tags,
             filters,
+            highlighter,
+            markdown,
+            comparators,
+            time_zone,
+            strict_variables,
+            formatter,
+            fragment_cache,
             partials,
         } = self;
 
@@ -163,13 +249,23 @@ where
         options.blocks = blocks;
         options.tags = tags;
         options.filters = filters;
+        options.highlighter = highlighter;
+        options.markdown = markdown;
+        options.comparators = comparators;
+        options.strict_variables = strict_variables;
+        options.formatter = formatter;
+        options.fragment_cache = fragment_cache;
         let options = sync::Arc::new(options);
         let partials = partials
             .map(|p| p.compile(options.clone()))
             .map(|r| r.map(Some))
             .unwrap_or(Ok(None))?
             .map(|p| p.into());
-        let p = Parser { options, partials };
+        let p = Parser {
+            options,
+            partials,
+            time_zone,
+        };
         Ok(p)
     }
 }
@@ -183,6 +279,13 @@ where
             blocks: Default::default(),
             tags: Default::default(),
             filters: Default::default(),
+            highlighter: Default::default(),
+            markdown: Default::default(),
+            comparators: Default::default(),
+            time_zone: Default::default(),
+            strict_variables: Default::default(),
+            formatter: Default::default(),
+            fragment_cache: Default::default(),
             partials: Default::default(),
         }
     }
@@ -218,6 +321,7 @@ where
 pub struct Parser {
     options: sync::Arc<parser::Language>,
     partials: Option<sync::Arc<dyn runtime::PartialStore + Send + Sync>>,
+    time_zone: Option<model::TimeZone>,
 }
 
 impl Parser {
@@ -245,6 +349,12 @@ impl Parser {
         Ok(Template {
             template,
             partials: self.partials.clone(),
+            shared_globals: None,
+            name: None,
+            now: None,
+            time_zone: self.time_zone.clone(),
+            seed: None,
+            language: self.options.clone(),
         })
     }
 
@@ -2,16 +2,65 @@ use std::io::Write;
 use std::sync;
 
 use liquid_core::error::Result;
+use liquid_core::model::{DateTime, KString, TimeZone};
+use liquid_core::parser;
 use liquid_core::runtime;
 use liquid_core::runtime::PartialStore;
-use liquid_core::runtime::Renderable;
+use liquid_core::Object;
+use liquid_core::Runtime;
 
 pub struct Template {
     pub(crate) template: runtime::Template,
     pub(crate) partials: Option<sync::Arc<dyn PartialStore + Send + Sync>>,
+    pub(crate) shared_globals: Option<sync::Arc<dyn crate::ObjectView + Send + Sync>>,
+    pub(crate) name: Option<KString>,
+    pub(crate) now: Option<DateTime>,
+    pub(crate) time_zone: Option<TimeZone>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) language: sync::Arc<parser::Language>,
 }
 
 impl Template {
+    /// Name the template by its path within the partials, like `docs/index.md`, for tags that
+    /// resolve partials relative to it.
+    pub fn with_name<S: Into<KString>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Render as if it were `now`, like for `{{ "now" | date: "%Y" }}`, rather than reading the
+    /// clock.
+    pub fn with_now(mut self, now: DateTime) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Show dates in `time_zone`, like for `{{ created_at | date: "%H:%M" }}`, overriding
+    /// [`ParserBuilder::time_zone`][crate::ParserBuilder::time_zone].
+    pub fn with_time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = Some(time_zone);
+        self
+    }
+
+    /// Seed the random numbers used by filters like `sample`, so every render picks the same
+    /// elements.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Make `globals` available to every render without copying them, like a large `site`
+    /// shared by many pages.
+    ///
+    /// The globals passed to `render` hide shared ones of the same name.
+    pub fn with_shared_globals(
+        mut self,
+        globals: sync::Arc<dyn crate::ObjectView + Send + Sync>,
+    ) -> Self {
+        self.shared_globals = Some(globals);
+        self
+    }
+
     /// Renders an instance of the Template, using the given globals.
     pub fn render(&self, globals: &dyn crate::ObjectView) -> Result<String> {
         const BEST_GUESS: usize = 10_000;
@@ -28,8 +77,36 @@ impl Template {
             Some(ref partials) => runtime.set_partials(partials.as_ref()),
             None => runtime,
         };
+        let runtime = match self.shared_globals {
+            Some(ref globals) => runtime.set_shared_globals(globals.clone()),
+            None => runtime,
+        };
         let runtime = runtime.build();
-        self.template.render_to(writer, &runtime)
+        {
+            let mut clock = runtime.registers().get_mut::<runtime::ClockRegister>();
+            if let Some(now) = self.now {
+                clock.set_now(now);
+            }
+            clock.set_time_zone(self.time_zone.clone());
+        }
+        if let Some(seed) = self.seed {
+            runtime
+                .registers()
+                .get_mut::<runtime::RandomRegister>()
+                .set_seed(seed);
+        }
+        runtime
+            .registers()
+            .get_mut::<runtime::LanguageRegister>()
+            .set_language(self.language.clone());
+        match self.name {
+            Some(ref name) => {
+                let root = Object::new();
+                let runtime = runtime::StackFrame::new(&runtime, &root).with_name(name.clone());
+                runtime::render_with_layouts(&self.template, writer, &runtime)
+            }
+            None => runtime::render_with_layouts(&self.template, writer, &runtime),
+        }
     }
 }
 
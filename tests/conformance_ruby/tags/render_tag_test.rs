@@ -185,7 +185,6 @@ fn test_break_through_render() {
 }
 
 #[test]
-#[should_panic] // `increment` without a variable is not supported yet
 fn test_increment_is_isolated_between_renders() {
     assert_template_result!(
         "010",
@@ -196,7 +195,6 @@ fn test_increment_is_isolated_between_renders() {
 }
 
 #[test]
-#[should_panic] // `decrement` without a variable is not supported yet
 fn test_decrement_is_isolated_between_renders() {
     assert_template_result!(
         "-1-2-1",
@@ -236,7 +234,6 @@ fn test_includes_will_not_render_inside_nested_sibling_tags() {
 }
 
 #[test]
-#[should_panic] // Implicit name is not supported yet
 fn test_render_tag_with() {
     assert_template_result!(
         "Product: Draft 151cm ",
@@ -123,3 +123,33 @@ fn test_not_nil() {
     let text = " {% if var != null %} true {% else %} false {% endif %} ";
     assert_template_result!("  true  ", text, o!({"var": 1}));
 }
+
+#[test]
+fn test_is_string_blank() {
+    let text = " {% if var == blank %} true {% else %} false {% endif %} ";
+    assert_template_result!("  true  ", text, o!({"var": ""}));
+    assert_template_result!("  true  ", text, o!({"var": "  "}));
+    assert_template_result!("  true  ", text, o!({ "var": nil }));
+    assert_template_result!("  false  ", text, o!({"var": "hello"}));
+    assert_template_result!("  false  ", text, o!({"var": 0}));
+}
+
+#[test]
+fn test_is_not_string_empty() {
+    let text = " {% if var != empty %} true {% else %} false {% endif %} ";
+    assert_template_result!("  false  ", text, o!({"var": ""}));
+    assert_template_result!("  true  ", text, o!({"var": " "}));
+    assert_template_result!("  false  ", text, o!({"var": {}}));
+}
+
+#[test]
+fn test_undefined_is_nil_blank_and_empty() {
+    for text in [
+        " {% if var == nil %} true {% else %} false {% endif %} ",
+        " {% if var == blank %} true {% else %} false {% endif %} ",
+        " {% if empty == var.title %} true {% else %} false {% endif %} ",
+        " {% unless var != blank %} true {% else %} false {% endunless %} ",
+    ] {
+        assert_template_result!("  true  ", text);
+    }
+}
@@ -0,0 +1,157 @@
+use std::any::Any;
+use std::cmp::Ordering;
+
+use liquid_core::model::{CustomScalar, DisplayCow, KStringCow, ScalarCow, State};
+use liquid_core::Result;
+use liquid_core::Runtime;
+use liquid_core::{Display_filter, Filter, FilterReflection, ParseFilter};
+use liquid_core::{Value, ValueView};
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+struct Money {
+    cents: i64,
+}
+
+impl ValueView for Money {
+    fn as_debug(&self) -> &dyn std::fmt::Debug {
+        self
+    }
+    fn render(&self) -> DisplayCow<'_> {
+        DisplayCow::Owned(Box::new(format!(
+            "${}.{:02}",
+            self.cents / 100,
+            self.cents % 100
+        )))
+    }
+    fn source(&self) -> DisplayCow<'_> {
+        self.render()
+    }
+    fn type_name(&self) -> &'static str {
+        "money"
+    }
+    fn query_state(&self, state: State) -> bool {
+        match state {
+            State::Truthy => self.cents != 0,
+            State::DefaultValue | State::Empty | State::Blank => self.cents == 0,
+        }
+    }
+    fn to_kstr(&self) -> KStringCow<'_> {
+        KStringCow::from_string(self.render().to_string())
+    }
+    fn to_value(&self) -> Value {
+        Value::scalar(ScalarCow::custom(self.clone()))
+    }
+    fn as_scalar(&self) -> Option<ScalarCow<'_>> {
+        Some(ScalarCow::custom(self.clone()))
+    }
+}
+
+impl CustomScalar for Money {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn custom_eq(&self, other: &dyn CustomScalar) -> bool {
+        other.downcast_ref::<Money>() == Some(self)
+    }
+    fn custom_cmp(&self, other: &dyn CustomScalar) -> Option<Ordering> {
+        self.partial_cmp(other.downcast_ref::<Money>()?)
+    }
+}
+
+fn money(cents: i64) -> Value {
+    Value::scalar(ScalarCow::custom(Money { cents }))
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(name = "cents", description = "tests helper", parsed(CentsFilter))]
+pub struct CentsFilterParser;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "cents"]
+pub struct CentsFilter;
+
+impl Filter for CentsFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let cents = input
+            .as_scalar()
+            .and_then(|s| s.to_integer())
+            .ok_or_else(|| liquid_core::Error::with_msg("Integer expected"))?;
+        Ok(money(cents))
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(name = "double", description = "tests helper", parsed(DoubleFilter))]
+pub struct DoubleFilterParser;
+
+#[derive(Debug, Default, Display_filter)]
+#[name = "double"]
+pub struct DoubleFilter;
+
+impl Filter for DoubleFilter {
+    fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+        let scalar = input.as_scalar();
+        let input = scalar
+            .as_ref()
+            .and_then(|s| s.as_custom())
+            .and_then(|c| c.downcast_ref::<Money>())
+            .ok_or_else(|| liquid_core::Error::with_msg("Money expected"))?;
+        Ok(money(input.cents * 2))
+    }
+}
+
+fn liquid() -> liquid::Parser {
+    liquid::ParserBuilder::with_stdlib()
+        .filter(CentsFilterParser)
+        .filter(DoubleFilterParser)
+        .build()
+        .unwrap()
+}
+
+fn render(text: &str, globals: &liquid::Object) -> Result<String> {
+    liquid().parse(text)?.render(globals)
+}
+
+#[test]
+fn custom_scalar_passes_through_filters() {
+    let globals = liquid::object!({});
+    assert_eq!(
+        render("{{ 625 | cents | double }}", &globals).unwrap(),
+        "$12.50"
+    );
+    render("{{ 625 | double }}", &globals).unwrap_err();
+    render(r#"{{ "$6.25" | double }}"#, &globals).unwrap_err();
+}
+
+#[test]
+fn custom_scalar_in_conditions() {
+    let mut globals = liquid::Object::new();
+    globals.insert("price".into(), money(1250));
+    globals.insert("budget".into(), money(2000));
+    globals.insert("free".into(), money(0));
+
+    assert_eq!(
+        render(
+            "{% if price < budget %}yes{% endif %}{% if price == budget %}no{% endif %}",
+            &globals
+        )
+        .unwrap(),
+        "yes"
+    );
+    assert_eq!(
+        render(
+            "{% if free %}no{% else %}free{% endif %} {{ free | default: price }}",
+            &globals
+        )
+        .unwrap(),
+        "free $12.50"
+    );
+    assert_eq!(
+        render(
+            "{% assign total = 625 | cents | double %}{% if total == price %}same{% endif %}",
+            &globals
+        )
+        .unwrap(),
+        "same"
+    );
+}
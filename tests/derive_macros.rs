@@ -177,6 +177,33 @@ pub fn test_derive_mixed_filter_err() {
         .is_err());
 }
 
+#[test]
+pub fn test_derive_mixed_filter_call() {
+    let rendered = liquid_core::call_filter!(
+        derive_macros_test_filters::TestMixedFilterParser,
+        0,
+        false,
+        "2019-02-08 15:34:25 -0800",
+        "2019-02-08";
+        a = 5,
+        type = 0
+    )
+    .unwrap();
+    assert_eq!(
+        rendered,
+        liquid_core::value!("<a: 5; b: false; c: None, d: 2019-02-08 15:34:25 -0800, e: 2019-02-08, f: None, type: 0>")
+    );
+
+    liquid_core::call_filter!(
+        derive_macros_test_filters::TestMixedFilterParser,
+        0,
+        false,
+        "2019-02-08 15:34:25 -0800",
+        "2019-02-08"
+    )
+    .unwrap_err();
+}
+
 #[test]
 pub fn test_derive_mixed_filter_reflection() {
     let filter = derive_macros_test_filters::TestMixedFilterParser;
@@ -118,3 +118,102 @@ fn test_borrow_object() {
     assert_eq!(uut.contains_key("s"), true);
     assert!(uut.get("s").is_some());
 }
+
+#[derive(Debug)]
+struct NotAValue;
+
+#[derive(ObjectView, ValueView, Debug)]
+struct TestAttributes {
+    #[object(rename = "title")]
+    name: String,
+    price: i64,
+    #[object(skip)]
+    secret: NotAValue,
+}
+
+#[test]
+fn test_attributes_object() {
+    let uut = TestAttributes {
+        name: String::from("Shirt"),
+        price: 10,
+        secret: NotAValue,
+    };
+    let _ = &uut.secret;
+
+    assert_eq!(uut.size(), 2i64);
+    assert_eq!(
+        uut.keys().map(|k| k.into_owned()).collect::<Vec<_>>(),
+        vec!["title", "price"]
+    );
+    assert_eq!(uut.contains_key("title"), true);
+    assert_eq!(uut.contains_key("name"), false);
+    assert_eq!(uut.contains_key("secret"), false);
+    assert_eq!(uut.get("title").unwrap().to_kstr(), "Shirt");
+    assert!(uut.get("name").is_none());
+    assert!(uut.get("secret").is_none());
+    assert_eq!(
+        uut.to_value(),
+        liquid::model::value!({"title": "Shirt", "price": 10})
+    );
+}
+
+#[test]
+fn test_attributes_render() {
+    let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    let product = TestAttributes {
+        name: String::from("Shirt"),
+        price: 10,
+        secret: NotAValue,
+    };
+    let globals = liquid::object!({ "product": product.to_value() });
+    let template = parser
+        .parse("{{ product.title }} {{ product.price }}")
+        .unwrap();
+    assert_eq!(template.render(&globals).unwrap(), "Shirt 10");
+
+    let template = parser.parse("{{ product.secret }}").unwrap();
+    template.render(&globals).unwrap_err();
+}
+
+#[derive(ObjectView, ValueView, Debug)]
+struct TestLazyGlobals {
+    title: String,
+    total: liquid::model::Lazy,
+}
+
+#[test]
+fn test_lazy_global() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let total = {
+        let calls = calls.clone();
+        liquid::model::Lazy::new(move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            liquid::model::Value::scalar(42)
+        })
+    };
+    let globals = TestLazyGlobals {
+        title: String::from("Orders"),
+        total: total.clone(),
+    };
+    let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+
+    let template = parser.parse("{{ title }}").unwrap();
+    assert_eq!(template.render(&globals).unwrap(), "Orders");
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    let template = parser
+        .parse("{{ total }} {% if total > 40 %}{{ total | plus: 1 }}{% endif %}")
+        .unwrap();
+    assert_eq!(template.render(&globals).unwrap(), "42 43");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // As a value inside an object, computed again as clones don't share the computed value
+    let mut globals = liquid::Object::new();
+    globals.insert("title".into(), liquid::model::Value::scalar("Orders"));
+    globals.insert("total".into(), liquid::model::Value::Lazy(total));
+    assert_eq!(template.render(&globals).unwrap(), "42 43");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
@@ -193,6 +193,21 @@ pub fn replace_first() {
     assert_eq!(output, "foo2bar".to_string());
 }
 
+#[test]
+pub fn replace_last() {
+    let text = "{{ text | replace_last: 'bar', 'foo' }} {{ text | remove_last: 'bar' }}";
+    let globals = liquid::object!({
+        "text": "bar2bar",
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "bar2foo bar2".to_string());
+}
+
 #[test]
 pub fn replace() {
     let text = "{{ text | replace: 'bar', 'foo' }}";
@@ -373,7 +388,7 @@ pub fn split_sort_join() {
 #[test]
 pub fn modulo() {
     let text = "{{ num | modulo: 2 }}";
-    let samples = [(4_f64, "0"), (3_f64, "1"), (5.1, "1.0999999999999996")];
+    let samples = [(4_f64, "0.0"), (3_f64, "1.0"), (5.1, "1.0999999999999996")];
     for t in &samples {
         let globals = liquid::object!({"num": t.0});
         let template = liquid::ParserBuilder::with_stdlib()
@@ -386,6 +401,22 @@ pub fn modulo() {
     }
 }
 
+#[test]
+pub fn arithmetic_matches_filters() {
+    let text = concat!(
+        "{{ -7 / 2 }} {{ -7 | divided_by: 2 }} ",
+        "{{ -7 % 3 }} {{ -7 | modulo: 3 }} ",
+        "{{ 7 % -3 }} {{ 7 | modulo: -3 }}",
+    );
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&liquid::object!({})).unwrap();
+    assert_eq!(output, "-4 -4 2 2 -2 -2");
+}
+
 #[test]
 pub fn escape() {
     let text = "{{ var | escape }}";
@@ -416,7 +447,8 @@ pub fn escape_once() {
         ("text", "text"),
         ("1 < 2 & 3", "1 &lt; 2 &amp; 3"),
         ("1 &lt; 2 &amp; 3", "1 &lt; 2 &amp; 3"),
-        ("&xyz;", "&amp;xyz;"),
+        ("&xyz;", "&xyz;"),
+        ("&#169; &#xA9; &", "&#169; &#xA9; &amp;"),
         ("<>&'\"", "&lt;&gt;&amp;&#39;&quot;"),
         ("&lt;&gt;&amp;&#39;&quot;", "&lt;&gt;&amp;&#39;&quot;"),
     ];
@@ -539,3 +571,265 @@ fn test_compact() {
     let output = template.render(&globals).unwrap();
     assert_eq!(output, "A C".to_string());
 }
+
+#[test]
+pub fn date_now() {
+    let text = r#"{{ "now" | date: "%Y" }} {% render "footer" %}"#;
+    let globals = liquid::object!({});
+    let mut partials = liquid::partials::InMemorySource::new();
+    partials.add("footer", r#"{{ "today" | date: "%d %B" }}"#);
+    let template = liquid::ParserBuilder::with_stdlib()
+        .partials(liquid::partials::EagerCompiler::new(partials))
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap()
+        .with_now(liquid::model::DateTime::from_ymd(1999, 12, 31));
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "1999 31 December".to_string());
+}
+
+#[test]
+pub fn sample_in_render() {
+    let text = r#"{{ list | sample }} {% render "footer", list: list %}"#;
+    let globals = liquid::object!({ "list": ["a", "b", "c", "d", "e", "f", "g", "h"] });
+    let mut partials = liquid::partials::InMemorySource::new();
+    partials.add("footer", r#"{{ list | sample }}"#);
+    let parser = liquid::ParserBuilder::with_stdlib()
+        .filter(liquid_lib::jekyll::Sample)
+        .partials(liquid::partials::EagerCompiler::new(partials))
+        .build()
+        .unwrap();
+    let render = |seed| {
+        parser
+            .parse(text)
+            .unwrap()
+            .with_seed(seed)
+            .render(&globals)
+            .unwrap()
+    };
+    let first = render(7);
+    assert_eq!(first, render(7));
+
+    // The partial continues the template's sequence rather than starting over
+    let reference = parser
+        .parse("{{ list | sample }} {{ list | sample }}")
+        .unwrap()
+        .with_seed(7)
+        .render(&globals)
+        .unwrap();
+    assert_eq!(first, reference);
+}
+
+#[test]
+pub fn date_time_zone() {
+    let text = concat!(
+        r#"{{ sent | date: "%H:%M %z" }} "#,
+        r#"{{ sent | date: "%H:%M %z", timezone: "UTC" }} {% render "footer" %}"#,
+    );
+    let globals = liquid::object!({ "sent": "2020-06-01 23:30:00 +0000" });
+    let mut partials = liquid::partials::InMemorySource::new();
+    partials.add("footer", r#"{{ "now" | date: "%d %H:%M" }}"#);
+    let parser = liquid::ParserBuilder::with_stdlib()
+        .partials(liquid::partials::EagerCompiler::new(partials))
+        .time_zone(liquid::model::TimeZone::from_name("+02:00").unwrap())
+        .build()
+        .unwrap();
+    let template = parser
+        .parse(text)
+        .unwrap()
+        .with_now(liquid::model::DateTime::from_ymd(1999, 12, 31));
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "01:30 +0200 23:30 +0000 31 02:00".to_string());
+
+    let template = parser
+        .parse(r#"{{ sent | date: "%H:%M" }}"#)
+        .unwrap()
+        .with_time_zone(liquid::model::TimeZone::utc());
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "23:30".to_string());
+}
+
+#[test]
+pub fn where_available() {
+    let text = concat!(
+        r#"{% assign available = products | where: "available", true %}"#,
+        r#"{% for product in available %}{{ product.title }} {% endfor %}"#,
+        r#"{{ products | where: "available" | map: "title" | join: " " }} "#,
+        r#"{{ products | where: "type", "kitchen" | map: "title" | join: ", " }}"#,
+    );
+    let globals = liquid::object!({
+        "products": [
+            { "title": "Vacuum", "type": "house", "available": true },
+            { "title": "Spatula", "type": "kitchen", "available": false },
+            { "title": "Television", "type": "lounge", "available": true },
+            { "title": "Garlic press", "type": "kitchen" },
+        ],
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(
+        output,
+        "Vacuum Television Vacuum Television Spatula, Garlic press".to_string()
+    );
+}
+
+#[test]
+pub fn sum() {
+    let text = r#"{{ cart.items | sum: "quantity" }} {{ cart.items | map: "price" | sum }}"#;
+    let globals = liquid::object!({
+        "cart": {
+            "items": [
+                { "quantity": 2, "price": 1.5 },
+                { "quantity": 3, "price": 2 },
+            ],
+        },
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "5 3.5".to_string());
+}
+
+#[test]
+pub fn clamp() {
+    let text =
+        r#"{% for qty in quantities %}{{ qty | at_least: 1 | at_most: stock }} {% endfor %}"#;
+    let globals = liquid::object!({ "quantities": [0, 2, 9, nil], "stock": 3 });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "1 2 3 1 ".to_string());
+}
+
+#[derive(Debug)]
+struct SemVer;
+
+impl liquid_core::parser::Comparator for SemVer {
+    fn compare(&self, a: &dyn liquid::ValueView, b: &dyn liquid::ValueView) -> std::cmp::Ordering {
+        let parts = |v: &dyn liquid::ValueView| -> Vec<u64> {
+            v.to_kstr()
+                .split('.')
+                .map(|p| p.parse().unwrap_or(0))
+                .collect()
+        };
+        parts(a).cmp(&parts(b))
+    }
+}
+
+#[test]
+pub fn sort_comparator() {
+    let text =
+        r#"{{ releases | sort: "version", comparator: "semver" | map: "version" | join: " " }}"#;
+    let globals = liquid::object!({
+        "releases": [
+            { "version": "1.10.0" },
+            { "version": "1.2.0" },
+            { "version": nil },
+            { "version": "1.2.10" },
+        ],
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .comparator("semver", SemVer)
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "1.2.0 1.2.10 1.10.0 ".to_string());
+
+    let text = r#"{{ releases | sort: comparator: "unknown" }}"#;
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    template.render(&globals).unwrap_err();
+}
+
+#[test]
+pub fn number_promotion() {
+    let samples = [
+        ("{{ 1 | plus: 2 }}", "3"),
+        ("{{ 1 | plus: 2.0 }}", "3.0"),
+        ("{{ 5.5 | minus: 0.5 }}", "5.0"),
+        ("{{ 2 | times: 1.5 }}", "3.0"),
+        ("{{ 10 | divided_by: 4 }}", "2"),
+        ("{{ 10 | divided_by: 4.0 }}", "2.5"),
+        ("{{ 4.0 | divided_by: 2 }}", "2.0"),
+        ("{{ 7 | modulo: 2.0 }}", "1.0"),
+    ];
+    for (text, expected) in samples {
+        let template = liquid::ParserBuilder::with_stdlib()
+            .build()
+            .unwrap()
+            .parse(text)
+            .unwrap();
+        let output = template.render(&liquid::Object::new()).unwrap();
+        assert_eq!(output, expected, "{}", text);
+    }
+}
+
+#[test]
+pub fn collection_literals() {
+    let text = concat!(
+        r#"{% assign pair = ["a", "b"] %}{% assign opts = {"size": 2, "tags": pair} %}"#,
+        r#"{{ pair | join: "-" }} {{ pair.size }} {{ opts.size }} {{ opts.tags.last }} "#,
+        "{% for x in [3, n, 1] %}{{ x }}{% endfor %} ",
+        "{{ [3, n, 1] | sort | join: ',' }} {{ opts | map: 'size' }}",
+    );
+    let globals = liquid::object!({
+        "n": 5,
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(output, "a-b 2 2 b 351 1,3,5 2".to_string());
+}
+
+#[test]
+pub fn range_values() {
+    let text = concat!(
+        "{% assign r = (1..n) %}",
+        "{{ r }} {{ r.size }} {{ r.first }}-{{ r.last }} {{ r[1] }} ",
+        "{{ r | join: ',' }} {{ r | reverse | join: ',' }} {{ r | map: 'x' | size }} ",
+        "{{ r | concat: (6..7) | join: ',' }} {{ r | sum }}",
+        "{% for i in r reversed %}{{ i }}{% endfor %}"
+    );
+    let globals = liquid::object!({
+        "n": 5,
+    });
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    let output = template.render(&globals).unwrap();
+    assert_eq!(
+        output,
+        "1..5 5 1-5 2 1,2,3,4,5 5,4,3,2,1 5 1,2,3,4,5,6,7 1554321".to_string()
+    );
+}
+
+#[test]
+pub fn range_too_long() {
+    let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+    let globals = liquid::object!({});
+    let template = parser
+        .parse("{% assign r = (1..1000000000000) %}{{ r.last }}{{ r | join: ',' }}")
+        .unwrap();
+    template.render(&globals).unwrap_err();
+}
@@ -36,3 +36,30 @@ pub fn pass_between_threads() {
     // Wait for threads to finish
     handles.into_iter().map(|h| h.join()).last();
 }
+
+#[test]
+pub fn share_globals_between_threads() {
+    let site: Arc<dyn liquid::ObjectView + Send + Sync> = Arc::new(liquid::object!({
+        "site": {"title": "Blog", "posts": ["a", "b", "c"]},
+    }));
+    let template = liquid::ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse("{{ page.title }} - {{ site.title }} ({{ site.posts | size }})")
+        .unwrap()
+        .with_shared_globals(Arc::clone(&site));
+    let template = Arc::new(template);
+
+    let handles: Vec<_> = ["First", "Second"]
+        .into_iter()
+        .map(|title| {
+            let template = Arc::clone(&template);
+            thread::spawn(move || {
+                let globals = liquid::object!({"page": {"title": title}});
+                template.render(&globals).unwrap()
+            })
+        })
+        .collect();
+    let outputs: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(outputs, ["First - Blog (3)", "Second - Blog (3)"]);
+}
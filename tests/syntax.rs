@@ -68,3 +68,69 @@ topic1-bar\
 ",
     );
 }
+
+#[test]
+pub fn nil_safe_navigation() {
+    let globals = object!({
+        "user": {"name": "Ann", "address": nil},
+    });
+    let text = "[{{ user.address.city }}][{{ user.address.city.name | default: 'none' }}]{% if user.address.city %}yes{% endif %}";
+
+    let template = ParserBuilder::with_stdlib()
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    assert_eq!(template.render(&globals).unwrap(), "[][none]");
+
+    let template = ParserBuilder::with_stdlib()
+        .strict_variables(true)
+        .build()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    template.render(&globals).unwrap_err();
+
+    // Only nil is navigable, not missing variables or other values.
+    let parser = ParserBuilder::with_stdlib().build().unwrap();
+    for text in ["{{ missing.city }}", "{{ user.name.city }}"] {
+        let template = parser.parse(text).unwrap();
+        template.render(&globals).unwrap_err();
+    }
+}
+
+#[derive(Debug)]
+struct FixedFloats;
+
+impl liquid_core::parser::ValueFormatter for FixedFloats {
+    fn format(&self, value: &dyn ValueView) -> Option<String> {
+        let scalar = value.as_scalar()?;
+        match scalar.type_name() {
+            "fractional number" => Some(format!("{:.2}", scalar.to_float()?)),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+pub fn value_formatter() {
+    let parser = ParserBuilder::with_stdlib()
+        .formatter(FixedFloats)
+        .build()
+        .unwrap();
+    let globals = object!({"price": 2.5, "sizes": [1.0, "M", 3]});
+
+    let template = parser
+        .parse("{{ price }} {{ price | times: 2 }} {{ sizes }} {% echo price %} {{ 'x' }}{{ 7 }}")
+        .unwrap();
+    assert_eq!(
+        template.render(&globals).unwrap(),
+        "2.50 5.00 1.00M3 2.50 x7"
+    );
+
+    // Only output is formatted, not values passed between filters.
+    let template = parser
+        .parse("{% assign p = price | append: '' %}{{ p }} {% capture c %}{{ price }}{% endcapture %}{{ c }}")
+        .unwrap();
+    assert_eq!(template.render(&globals).unwrap(), "2.5 2.50");
+}